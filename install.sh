@@ -86,7 +86,7 @@ mkdir -p "$INSTALL_DIR" "$DATA_DIR"
 mv "$BINARY" "$INSTALL_DIR/$BINARY"
 chmod +x "$INSTALL_DIR/$BINARY"
 
-for dir in catalogs collections composers compositions inventories schemas; do
+for dir in catalogs chorales collections composers compositions inventories people references schemas taxonomy; do
 	rm -rf "$DATA_DIR/$dir"
 	mv "data/$dir" "$DATA_DIR/$dir"
 done
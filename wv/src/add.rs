@@ -3,7 +3,7 @@ use std::path::{Path, PathBuf};
 
 use crate::parse::{load_composition, path_for_id};
 use crate::types::Composition;
-use crate::validate::Validator;
+use crate::validate::{Level, Validator};
 
 #[derive(Debug)]
 pub enum AddError {
@@ -89,7 +89,7 @@ pub fn prepare_composition_with<P: AsRef<Path>, Q: AsRef<Path>>(
 	let errors = validator.validate_composition_file(source);
 	let non_path_errors: Vec<_> = errors
 		.iter()
-		.filter(|e| !e.message.contains("doesn't match path"))
+		.filter(|e| e.severity == Level::Error && !e.message.contains("doesn't match path"))
 		.collect();
 
 	if !non_path_errors.is_empty() {
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use clap::{Parser, Subcommand};
 use werkverzeichnis::commands;
+use werkverzeichnis::commands::validate::ReportFormat;
 use werkverzeichnis::config::{resolve_data_dir, Config};
 use werkverzeichnis::output::print;
 use werkverzeichnis::add::{generate_id, generate_unique_id};
@@ -14,6 +15,8 @@ struct Cli {
 	#[command(subcommand)]
 	command: Commands,
 }
+// Parsed once per run; boxing the `get` flags would buy nothing.
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
 	ParseComposition {
@@ -44,6 +47,13 @@ enum Commands {
 		#[arg(long, value_name = "PATH")]
 		data_dir: Option<PathBuf>,
 	},
+	MigrateInstrumentation {
+		paths: Vec<PathBuf>,
+		#[arg(long)]
+		write: bool,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
 	Merge {
 		path: PathBuf,
 		#[arg(long, value_name = "PATH")]
@@ -85,6 +95,30 @@ enum Commands {
 		xref: Option<String>,
 		#[arg(long, short = 'c', help = "Expand collection(s) as input", num_args = 1..)]
 		collection: Option<Vec<String>>,
+		#[arg(long, value_name = "YEARS", help = "Filter by composition date (e.g., 1723, 1720..1730, ..1750)")]
+		composed: Option<String>,
+		#[arg(long, value_name = "DURATION", help = "Keep works that can be performed within DURATION (e.g., 1200, 20:00, PT20M)")]
+		max_duration: Option<String>,
+		#[arg(long, value_name = "FORM", help = "Filter by form, including its subforms (e.g., cantata)")]
+		form: Option<String>,
+		#[arg(long, value_name = "KEY", help = "Filter by the key of a work or any movement (e.g., D, c#, D.* for any mode, minor, modal)")]
+		key: Option<String>,
+		#[arg(long, help = "Order results by composition date")]
+		chronological: bool,
+		#[arg(long, help = "Find works by nickname (e.g., moonlight)")]
+		nickname: Option<String>,
+		#[arg(long, help = "Show arrangements, versions, parodies and other related works")]
+		related: bool,
+		#[arg(long, help = "Show attribution history with the references cited for each claim")]
+		sources: bool,
+		#[arg(long, value_name = "SLUG", help = "Find works crediting a librettist, poet, dedicatee, arranger or editor")]
+		person: Option<String>,
+		#[arg(long, value_name = "SLUG", help = "Find works written for a liturgical occasion (e.g., advent-1, trinity+4)")]
+		occasion: Option<String>,
+		#[arg(long, value_name = "TEXT", help = "Find works and movements whose sung text begins with TEXT")]
+		first_line: Option<String>,
+		#[arg(long, requires = "movements", help = "With --movements, list every movement with its sung text")]
+		text: bool,
 		#[arg(long, value_name = "PATH")]
 		data_dir: Option<PathBuf>,
 	},
@@ -118,8 +152,59 @@ enum Commands {
 		#[arg(long, value_name = "PATH")]
 		data_dir: Option<PathBuf>,
 	},
+	Incipit {
+		id: Option<String>,
+		#[arg(long, value_name = "PATH", help = "Movement path, e.g. 2 or 1.3")]
+		movement: Option<String>,
+		#[arg(long, help = "Print the incipit as SVG instead of text")]
+		svg: bool,
+		#[arg(long, value_name = "PAE", conflicts_with = "id", help = "Find incipits containing this melody, at any pitch level")]
+		search: Option<String>,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
+	Chorale {
+		#[arg(value_name = "MELODY", help = "Tune name, slug or Zahn number (e.g., \"Jesu, meine Freude\")")]
+		name: String,
+		#[arg(short, long, help = "Terse output (IDs only)")]
+		terse: bool,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
+	Occasion {
+		#[arg(value_name = "DATE", help = "Calendar date (YYYY-MM-DD)")]
+		date: String,
+		#[arg(short, long, help = "Terse output (IDs only)")]
+		terse: bool,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
 	Validate {
 		path: Option<PathBuf>,
+		#[arg(long, value_enum, default_value_t = ReportFormat::Text, help = "Output format")]
+		format: ReportFormat,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
+	Fmt {
+		paths: Vec<PathBuf>,
+		#[arg(long, help = "List files that are not canonical without rewriting them")]
+		check: bool,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
+	Lint {
+		paths: Vec<PathBuf>,
+		#[arg(long, help = "Apply safe rewrites in place")]
+		fix: bool,
+		#[arg(long, value_name = "PATH")]
+		data_dir: Option<PathBuf>,
+	},
+	Dupes {
+		#[arg(long, value_name = "SLUG", help = "Only compare works attributed to this composer")]
+		composer: Option<String>,
+		#[arg(long, default_value_t = werkverzeichnis::dupes::DEFAULT_THRESHOLD, help = "Lowest score (0-100) to report")]
+		threshold: u32,
 		#[arg(long, value_name = "PATH")]
 		data_dir: Option<PathBuf>,
 	},
@@ -154,6 +239,12 @@ enum Commands {
 		#[arg(long, value_name = "PATH", global = true)]
 		data_dir: Option<PathBuf>,
 	},
+	Catalog {
+		#[command(subcommand)]
+		action: CatalogAction,
+		#[arg(long, value_name = "PATH", global = true)]
+		data_dir: Option<PathBuf>,
+	},
 }
 #[derive(Subcommand)]
 enum CollectionAction {
@@ -172,6 +263,19 @@ enum CollectionAction {
 		query: String,
 	},
 }
+#[derive(Subcommand)]
+enum CatalogAction {
+	List {
+		#[arg(help = "Only catalogs usable for this composer")]
+		composer: Option<String>,
+	},
+	Show {
+		#[arg(help = "Catalog scheme (e.g., bwv, op)")]
+		scheme: String,
+		#[arg(long, help = "Show the definition as merged for this composer")]
+		composer: Option<String>,
+	},
+}
 fn data_dir_or_exit(cli_arg: Option<&PathBuf>, config: &Config) -> PathBuf {
 	match resolve_data_dir(cli_arg, config) {
 		Ok(path) => path,
@@ -203,6 +307,10 @@ fn main() {
 			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
 			commands::sort::run_sort_key(&scheme, &number, composer.as_deref(), &data_dir);
 		}
+		Commands::MigrateInstrumentation { paths, write, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			commands::instrumentation::run_migrate(&paths, write, &data_dir);
+		}
 		Commands::Merge { path, data_dir } => {
 			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
 			commands::merge::run(&path, &data_dir);
@@ -227,6 +335,18 @@ fn main() {
 			strict,
 			xref,
 			collection,
+			composed,
+			max_duration,
+			form,
+			key,
+			chronological,
+			nickname,
+			related,
+			sources,
+			person,
+			occasion,
+			first_line,
+			text,
 			data_dir,
 		} => {
 			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
@@ -246,6 +366,18 @@ fn main() {
 				strict,
 				xref,
 				collection,
+				composed,
+				max_duration,
+				form,
+				key,
+				chronological,
+				nickname,
+				related,
+				sources,
+				person,
+				occasion,
+				first_line,
+				text,
 			};
 			commands::get::run(args, data_dir, &config);
 		}
@@ -283,9 +415,37 @@ fn main() {
 			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
 			commands::render::run(&data_dir, &config);
 		}
-		Commands::Validate { path, data_dir } => {
+		Commands::Incipit { id, movement, svg, search, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			let args = commands::incipit::IncipitArgs { id, movement, svg, search };
+			commands::incipit::run(args, &data_dir, &config);
+		}
+		Commands::Chorale { name, terse, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			let args = commands::chorale::ChoraleArgs { name, terse };
+			commands::chorale::run(args, &data_dir, &config);
+		}
+		Commands::Occasion { date, terse, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			let args = commands::occasion::OccasionArgs { date, terse };
+			commands::occasion::run(args, &data_dir, &config);
+		}
+		Commands::Validate { path, format, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			commands::validate::run(path.as_deref(), format, &data_dir);
+		}
+		Commands::Fmt { paths, check, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			commands::fmt::run(&paths, check, &data_dir);
+		}
+		Commands::Lint { paths, fix, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			commands::lint::run(&paths, fix, &data_dir, &config.display);
+		}
+		Commands::Dupes { composer, threshold, data_dir } => {
 			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
-			commands::validate::run(path.as_deref(), &data_dir);
+			let composer = composer.map(|value| value.to_lowercase());
+			commands::dupes::run(composer.as_deref(), threshold, &data_dir);
 		}
 		Commands::Add {
 			path,
@@ -322,6 +482,19 @@ fn main() {
 				}
 			}
 		}
+		Commands::Catalog { action, data_dir } => {
+			let data_dir = data_dir_or_exit(data_dir.as_ref(), &config);
+			match action {
+				CatalogAction::List { composer } => {
+					let composer = composer.map(|value| value.to_lowercase());
+					commands::catalog::list(composer.as_deref(), &data_dir);
+				}
+				CatalogAction::Show { scheme, composer } => {
+					let composer = composer.map(|value| value.to_lowercase());
+					commands::catalog::show(&scheme.to_lowercase(), composer.as_deref(), &data_dir);
+				}
+			}
+		}
 	}
 }
 
@@ -376,4 +549,14 @@ mod tests {
 			_ => panic!("expected add command"),
 		}
 	}
+
+	#[test]
+	fn validate_format_is_one_of_the_report_formats() {
+		let cli = Cli::try_parse_from(["wv", "validate", "--format", "sarif"]).unwrap();
+		match cli.command {
+			Commands::Validate { format, .. } => assert_eq!(format, ReportFormat::Sarif),
+			_ => panic!("expected validate command"),
+		}
+		assert!(Cli::try_parse_from(["wv", "validate", "--format", "xml"]).is_err());
+	}
 }
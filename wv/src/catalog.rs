@@ -1,12 +1,12 @@
-use regex::{Regex, RegexBuilder};
+use regex::{Captures, Match, Regex, RegexBuilder};
 use serde_json::Value;
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::cmp::Ordering;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use crate::types::{CatalogConstraint, CatalogDefinition, Composer};
+use crate::types::{CatalogConstraint, CatalogDefinition, CatalogGroup, Composer, InputRule};
 use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -41,7 +41,7 @@ impl Ord for SortValue {
 	}
 }
 
-fn parse_roman(s: &str) -> i64 {
+pub(crate) fn parse_roman(s: &str) -> i64 {
 	let s = s.to_uppercase();
 	if !s.chars().all(|c| "IVXLCDM".contains(c)) {
 		return 0;
@@ -210,12 +210,10 @@ fn read_catalog_def(
 	};
 
 	if let Some(definition) = definition.as_ref() {
-		if let Some(pattern) = definition.pattern.as_deref() {
-			cached_regex_result(pattern).map_err(|error| CatalogLoadError::InvalidPattern {
-				catalog: composer.map_or_else(|| scheme.to_string(), |composer| format!("{}/{}", composer, scheme)),
-				message: error.to_string(),
-			})?;
-		}
+		compiled_patterns(definition).map_err(|error| CatalogLoadError::InvalidPattern {
+			catalog: composer.map_or_else(|| scheme.to_string(), |composer| format!("{}/{}", composer, scheme)),
+			message: error.to_string(),
+		})?;
 		validate_catalog_formats(definition).map_err(|message| CatalogLoadError::InvalidFormat {
 			catalog: composer.map_or_else(|| scheme.to_string(), |composer| format!("{}/{}", composer, scheme)),
 			message,
@@ -292,23 +290,67 @@ pub fn merge_catalog_definitions(
 
 	let mut definition = merged.unwrap_or_else(|| composer.clone());
 	definition.constraints = constraints;
+	// A composer's patterns replace the shared ones as a whole, so a shared
+	// `pattern` is not tried before a composer's `patterns`.
+	if composer.pattern.is_some() || composer.patterns.is_some() {
+		definition.pattern = composer.pattern.clone();
+		definition.patterns = composer.patterns.clone();
+	}
 	definition
 }
 
-fn parse_number_with_regex(number: &str, re: &Regex, max_group: usize) -> Option<Vec<Option<String>>> {
-	let caps = re.captures(number)?;
+/// `pattern` followed by the alternatives in `patterns`.
+pub(crate) fn definition_patterns(defn: &CatalogDefinition) -> impl Iterator<Item = &str> {
+	defn.pattern
+		.as_deref()
+		.into_iter()
+		.chain(defn.patterns.iter().flatten().map(String::as_str))
+}
+
+pub(crate) fn compiled_patterns(defn: &CatalogDefinition) -> Result<Vec<Rc<Regex>>, CatalogPatternError> {
+	definition_patterns(defn).map(cached_regex_result).collect()
+}
+
+/// A number matched by one of a definition's patterns.
+pub(crate) struct ParsedNumber<'h> {
+	regex: Rc<Regex>,
+	captures: Captures<'h>,
+}
+
+impl<'h> ParsedNumber<'h> {
+	/// The 1-based index of `group` in the pattern that matched.
+	pub fn index_of(&self, group: &CatalogGroup) -> Option<usize> {
+		match group {
+			CatalogGroup::Index(index) => (*index >= 1 && *index < self.captures.len()).then_some(*index),
+			CatalogGroup::Name(name) => self
+				.regex
+				.capture_names()
+				.position(|candidate| candidate == Some(name.as_str())),
+		}
+	}
 
-	let mut result = Vec::new();
-	for i in 1..=max_group {
-		result.push(caps.get(i).map(|m| m.as_str().to_string()));
+	pub fn get(&self, group: &CatalogGroup) -> Option<Match<'h>> {
+		self.index_of(group).and_then(|index| self.captures.get(index))
 	}
-	Some(result)
 }
 
-fn sort_key_with_regex(number: &str, re: &Regex, defn: &CatalogDefinition, max_group: usize) -> Vec<SortValue> {
-	let captures = match parse_number_with_regex(number, re, max_group) {
-		Some(c) => c,
-		None => return vec![SortValue::Int(999999999), SortValue::Str(number.to_string())],
+/// Matches `number` against each pattern in turn. Invalid patterns are
+/// skipped here; loading and validation report them.
+pub(crate) fn parse_number<'h>(number: &'h str, defn: &CatalogDefinition) -> Option<ParsedNumber<'h>> {
+	definition_patterns(defn).find_map(|pattern| {
+		let regex = cached_regex(pattern)?;
+		let captures = regex.captures(number)?;
+		Some(ParsedNumber { regex, captures })
+	})
+}
+
+pub(crate) fn has_pattern(defn: &CatalogDefinition) -> bool {
+	definition_patterns(defn).next().is_some()
+}
+
+fn sort_key_from_parsed(number: &str, parsed: Option<&ParsedNumber>, defn: &CatalogDefinition) -> Vec<SortValue> {
+	let Some(parsed) = parsed else {
+		return vec![SortValue::Int(999999999), SortValue::Str(number.to_string())];
 	};
 
 	let sort_keys = match &defn.sort_keys {
@@ -319,8 +361,7 @@ fn sort_key_with_regex(number: &str, re: &Regex, defn: &CatalogDefinition, max_g
 	let mut key = Vec::new();
 
 	for sk in sort_keys {
-		let idx = sk.group - 1;
-		let raw = captures.get(idx).and_then(|o| o.clone());
+		let raw = parsed.get(&sk.group).map(|m| m.as_str());
 		let typ = sk.sort_type.as_str();
 
 		let missing = if sk.none_last.unwrap_or(false) {
@@ -333,7 +374,7 @@ fn sort_key_with_regex(number: &str, re: &Regex, defn: &CatalogDefinition, max_g
 			None => {
 				key.push(missing);
 			}
-			Some(s) if s.is_empty() => {
+			Some("") => {
 				key.push(missing);
 			}
 			Some(s) => match typ {
@@ -342,11 +383,11 @@ fn sort_key_with_regex(number: &str, re: &Regex, defn: &CatalogDefinition, max_g
 					key.push(SortValue::Int(val));
 				}
 				"roman" => {
-					let val = parse_roman(&s);
+					let val = parse_roman(s);
 					key.push(SortValue::Int(val));
 				}
 				_ => {
-					key.push(SortValue::Str(s));
+					key.push(SortValue::Str(s.to_string()));
 				}
 			},
 		}
@@ -356,48 +397,18 @@ fn sort_key_with_regex(number: &str, re: &Regex, defn: &CatalogDefinition, max_g
 }
 
 pub fn sort_key(number: &str, defn: &CatalogDefinition) -> Vec<SortValue> {
-	let pattern = match &defn.pattern {
-		Some(p) => p,
-		None => return vec![SortValue::Str(number.to_string())],
-	};
-	let Some(re) = cached_regex(pattern) else {
-		return vec![SortValue::Int(999999999), SortValue::Str(number.to_string())];
-	};
-	let max_group = defn
-		.sort_keys
-		.as_ref()
-		.map(|sks| sks.iter().map(|sk| sk.group).max().unwrap_or(0))
-		.unwrap_or(0);
-
-	sort_key_with_regex(number, &re, defn, max_group)
+	if !has_pattern(defn) {
+		return vec![SortValue::Str(number.to_string())];
+	}
+	sort_key_from_parsed(number, parse_number(number, defn).as_ref(), defn)
 }
 
 pub fn sort_numbers(numbers: &mut [String], defn: Option<&CatalogDefinition>) {
 	match defn {
-		Some(d) => {
-			let pattern = match &d.pattern {
-				Some(p) => p,
-				None => {
-					numbers.sort();
-					return;
-				}
-			};
-			let Some(re) = cached_regex(pattern) else {
-				numbers.sort();
-				return;
-			};
-			let max_group = d
-				.sort_keys
-				.as_ref()
-				.map(|sks| sks.iter().map(|sk| sk.group).max().unwrap_or(0))
-				.unwrap_or(0);
-
-			numbers.sort_by(|a, b| {
-				sort_key_with_regex(a, &re, d, max_group)
-					.cmp(&sort_key_with_regex(b, &re, d, max_group))
-			});
+		Some(d) if has_pattern(d) => {
+			numbers.sort_by_cached_key(|number| sort_key_from_parsed(number, parse_number(number, d).as_ref(), d));
 		}
-		None => numbers.sort(),
+		_ => numbers.sort(),
 	}
 }
 
@@ -430,38 +441,25 @@ pub fn matches_group(number: &str, group: &str, defn: Option<&CatalogDefinition>
 		}
 	}
 
-	let pattern = match &defn.pattern {
-		Some(p) => p,
-		None => return number.starts_with(group),
-	};
-
-	let Some(re) = cached_regex(pattern) else {
+	if !has_pattern(defn) {
 		return number.starts_with(group);
-	};
-
-	let max_group = defn
-		.sort_keys
-		.as_ref()
-		.map(|sks| sks.iter().map(|sk| sk.group).max().unwrap_or(0))
-		.unwrap_or(0);
+	}
 
-	let num_captures = match parse_number_with_regex(number, &re, max_group) {
-		Some(c) => c,
-		None => return false,
+	let Some(num_captures) = parse_number(number, defn) else {
+		return false;
 	};
 
-	let grp_captures = match parse_number_with_regex(group, &re, max_group) {
-		Some(c) => c,
-		None => return number.starts_with(group),
+	let Some(grp_captures) = parse_number(group, defn) else {
+		return number.starts_with(group);
 	};
 
-	let groups_to_compare: Vec<usize> = match &defn.group_by {
-		Some(gb) => gb.clone(),
+	let groups_to_compare: Vec<&CatalogGroup> = match &defn.group_by {
+		Some(gb) => gb.iter().collect(),
 		None => {
 			defn.sort_keys
 				.as_ref()
 				.map(|sks| {
-					let groups: Vec<usize> = sks.iter().map(|sk| sk.group).collect();
+					let groups: Vec<&CatalogGroup> = sks.iter().map(|sk| &sk.group).collect();
 					if groups.len() > 1 {
 						groups[..groups.len() - 1].to_vec()
 					} else {
@@ -472,12 +470,9 @@ pub fn matches_group(number: &str, group: &str, defn: Option<&CatalogDefinition>
 		}
 	};
 
-	for &grp_idx in &groups_to_compare {
-		if grp_idx == 0 || grp_idx > max_group {
-			continue;
-		}
-		let num_val = num_captures.get(grp_idx - 1).and_then(|v| v.as_ref());
-		let grp_val = grp_captures.get(grp_idx - 1).and_then(|v| v.as_ref());
+	for grp in groups_to_compare {
+		let num_val = num_captures.get(grp).map(|m| m.as_str());
+		let grp_val = grp_captures.get(grp).map(|m| m.as_str());
 
 		match (num_val, grp_val) {
 			(Some(n), Some(g)) => {
@@ -495,9 +490,9 @@ pub fn matches_group(number: &str, group: &str, defn: Option<&CatalogDefinition>
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CatalogDomainError {
-	BelowMinimum { group: usize, name: Option<String>, value: i64, min: i64 },
-	AboveMaximum { group: usize, name: Option<String>, value: i64, max: i64 },
-	OutsideRanges { group: usize, name: Option<String>, value: i64 },
+	BelowMinimum { group: CatalogGroup, name: Option<String>, value: i64, min: i64 },
+	AboveMaximum { group: CatalogGroup, name: Option<String>, value: i64, max: i64 },
+	OutsideRanges { group: CatalogGroup, name: Option<String>, value: i64 },
 }
 
 impl std::fmt::Display for CatalogDomainError {
@@ -522,42 +517,37 @@ impl std::fmt::Display for CatalogDomainError {
 fn constraint_accepts(value: i64, constraint: &CatalogConstraint) -> Result<(), CatalogDomainError> {
 	if let Some(min) = constraint.min {
 		if value < min {
-			return Err(CatalogDomainError::BelowMinimum { group: constraint.group, name: constraint.name.clone(), value, min });
+			return Err(CatalogDomainError::BelowMinimum { group: constraint.group.clone(), name: constraint.name.clone(), value, min });
 		}
 	}
 	if let Some(max) = constraint.max {
 		if value > max {
-			return Err(CatalogDomainError::AboveMaximum { group: constraint.group, name: constraint.name.clone(), value, max });
+			return Err(CatalogDomainError::AboveMaximum { group: constraint.group.clone(), name: constraint.name.clone(), value, max });
 		}
 	}
 	if let Some(ranges) = &constraint.ranges {
 		if !ranges.iter().any(|range| value >= range.min && value <= range.max) {
-			return Err(CatalogDomainError::OutsideRanges { group: constraint.group, name: constraint.name.clone(), value });
+			return Err(CatalogDomainError::OutsideRanges { group: constraint.group.clone(), name: constraint.name.clone(), value });
 		}
 	}
 	Ok(())
 }
 
 pub fn validate_catalog_domain(number: &str, defn: &CatalogDefinition) -> Result<(), CatalogDomainError> {
-	let Some(pattern) = &defn.pattern else {
-		return Ok(());
-	};
-	let Some(re) = cached_regex(pattern) else {
-		return Ok(());
-	};
-	let Some(captures) = re.captures(number) else {
+	let Some(parsed) = parse_number(number, defn) else {
 		return Ok(());
 	};
 
 	if let Some(constraints) = &defn.constraints {
 		for constraint in constraints {
-			let Some(value) = captures.get(constraint.group) else {
+			let Some(value) = parsed.get(&constraint.group) else {
 				continue;
 			};
+			let index = parsed.index_of(&constraint.group);
 			let sort_type = defn
 				.sort_keys
 				.as_ref()
-				.and_then(|keys| keys.iter().find(|key| key.group == constraint.group))
+				.and_then(|keys| keys.iter().find(|key| parsed.index_of(&key.group) == index))
 				.map(|key| key.sort_type.as_str())
 				.unwrap_or("int");
 			let value = match sort_type {
@@ -578,6 +568,175 @@ pub fn normalize_catalog_number(number: &str) -> String {
 	number.to_lowercase()
 }
 
+/// The category a value names, as spelled in the definition. Case is ignored.
+pub(crate) fn find_category<'a>(value: &str, defn: &'a CatalogDefinition) -> Option<&'a str> {
+	defn.categories
+		.as_ref()?
+		.keys()
+		.find(|category| category.eq_ignore_ascii_case(value))
+		.map(String::as_str)
+}
+
+/// "iii 32", "iii/32" and "iii:32" all become "iii:32"; a bare category is
+/// returned on its own. `None` when the input does not begin with one.
+fn join_category(value: &str, arabic: bool, defn: &CatalogDefinition) -> Option<String> {
+	let is_separator = |c: char| c.is_whitespace() || c == '/' || c == ':';
+	let (head, rest) = match value.find(is_separator) {
+		Some(at) => (&value[..at], value[at..].trim_start_matches(is_separator)),
+		None => (value, ""),
+	};
+	let category = find_category(head, defn).or_else(|| {
+		let number = head.parse::<i64>().ok().filter(|_| arabic)?;
+		defn.categories
+			.as_ref()?
+			.keys()
+			.find(|category| parse_roman(category) == number)
+			.map(String::as_str)
+	})?;
+	let category = category.to_lowercase();
+	if rest.is_empty() {
+		Some(category)
+	} else {
+		Some(format!("{}:{}", category, rest))
+	}
+}
+
+/// Turns a number as typed ("KV 331", "Op. 27, Nr. 2", "Hob. XVI/52") into
+/// its stored form by applying the definition's `input_rules` in order.
+/// Without a definition this is [`normalize_catalog_number`].
+pub fn normalize_input(number: &str, defn: Option<&CatalogDefinition>) -> String {
+	let mut value = normalize_catalog_number(number.trim());
+	let Some(defn) = defn else {
+		return value;
+	};
+
+	for rule in defn.input_rules.iter().flatten() {
+		match rule {
+			InputRule::StripPrefix(prefixes) => {
+				for prefix in prefixes {
+					if let Some(rest) = value.strip_prefix(prefix.to_lowercase().as_str()) {
+						value = rest.trim_start().to_string();
+						break;
+					}
+				}
+			}
+			InputRule::Replace { pattern, with } => {
+				if let Some(regex) = cached_regex(pattern) {
+					value = regex.replace_all(&value, with.as_str()).trim().to_string();
+				}
+			}
+			InputRule::Category { arabic } => {
+				if let Some(joined) = join_category(&value, *arabic, defn) {
+					value = joined;
+				}
+			}
+		}
+	}
+	value
+}
+
+/// Schemes a composer's numbers can be given in: the composer's own catalogs
+/// and the global ones.
+pub fn available_schemes<P: AsRef<Path>>(data_dir: P, composer: &str) -> Result<BTreeSet<String>, CatalogLoadError> {
+	let data_dir = data_dir.as_ref();
+	let catalogs_dir = data_dir.join("catalogs");
+	let mut schemes: BTreeSet<String> = match std::fs::read_dir(&catalogs_dir) {
+		Ok(entries) => entries
+			.filter_map(Result::ok)
+			.map(|entry| entry.path())
+			.filter(|path| path.extension().is_some_and(|e| e == "json"))
+			.filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+			.collect(),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => BTreeSet::new(),
+		Err(error) => return Err(CatalogLoadError::Io { path: catalogs_dir, message: error.to_string() }),
+	};
+	let composer_path = data_dir.join("composers").join(format!("{}.json", composer));
+	if let Some(catalogs) = read_optional_json::<Composer>(&composer_path)?.and_then(|composer| composer.catalogs) {
+		schemes.extend(catalogs.into_keys());
+	}
+	Ok(schemes)
+}
+
+/// Reads a scheme as typed for a composer: the scheme in any case ("BWV"),
+/// an alias ("kv"), or a number typed with its prefix ("K331", "BWV1007",
+/// "D.960"). Returns the scheme and, for a fused token, the number after the
+/// prefix; `None` when the token names none of the composer's schemes.
+pub fn resolve_scheme<P: AsRef<Path>>(
+	data_dir: P,
+	composer: &str,
+	token: &str,
+) -> Result<Option<(String, Option<String>)>, CatalogLoadError> {
+	let data_dir = data_dir.as_ref();
+	let schemes = available_schemes(data_dir, composer)?;
+	if let Some(scheme) = schemes.iter().find(|scheme| scheme.eq_ignore_ascii_case(token.trim())) {
+		return Ok(Some((scheme.clone(), None)));
+	}
+	let mut definitions = Vec::new();
+	for scheme in schemes {
+		if let Some(defn) = load_catalog_def(data_dir, &scheme, Some(composer))? {
+			definitions.push((scheme, defn));
+		}
+	}
+	Ok(resolve_scheme_token(token, &definitions))
+}
+
+/// Matches a token against the definitions' aliases, then against their
+/// aliases and `strip_prefix` rules as a prefix followed by a number.
+pub(crate) fn resolve_scheme_token(
+	token: &str,
+	schemes: &[(String, CatalogDefinition)],
+) -> Option<(String, Option<String>)> {
+	let token = token.trim().to_lowercase();
+	for (scheme, defn) in schemes {
+		if defn.aliases.iter().flatten().any(|alias| alias.to_lowercase() == token) {
+			return Some((scheme.clone(), None));
+		}
+	}
+
+	// The longest prefix wins, so "kv331" is read as "kv" + "331", not "k" + "v331".
+	let mut best: Option<(usize, &str, &str)> = None;
+	for (scheme, defn) in schemes {
+		let strip_prefixes = defn.input_rules.iter().flatten().filter_map(|rule| match rule {
+			InputRule::StripPrefix(prefixes) => Some(prefixes),
+			_ => None,
+		});
+		for prefix in strip_prefixes.flatten().chain(defn.aliases.iter().flatten()) {
+			let prefix = prefix.to_lowercase();
+			let Some(rest) = token.strip_prefix(prefix.as_str()) else {
+				continue;
+			};
+			let rest = rest.trim_start();
+			if rest.chars().any(|c| c.is_ascii_digit()) && best.is_none_or(|(length, _, _)| prefix.len() > length) {
+				best = Some((prefix.len(), scheme, rest));
+			}
+		}
+	}
+	best.map(|(_, scheme, rest)| (scheme.to_string(), Some(rest.to_string())))
+}
+
+pub(crate) fn validate_input_rules(definition: &CatalogDefinition) -> Result<(), String> {
+	for rule in definition.input_rules.iter().flatten() {
+		match rule {
+			InputRule::StripPrefix(prefixes) => {
+				if prefixes.iter().any(String::is_empty) {
+					return Err("strip_prefix entries must not be empty".into());
+				}
+			}
+			InputRule::Replace { pattern, .. } => {
+				if let Err(error) = cached_regex_result(pattern) {
+					return Err(format!("invalid input rule pattern '{}': {}", pattern, error));
+				}
+			}
+			InputRule::Category { .. } => {
+				if definition.categories.is_none() {
+					return Err("category input rule requires categories".into());
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
 pub(crate) fn group_key(number: &str, defn: &CatalogDefinition) -> Option<String> {
 	group_key_inner(number, defn).map(|(key, _)| key)
 }
@@ -596,17 +755,14 @@ fn group_key_inner(number: &str, defn: &CatalogDefinition) -> Option<(String, bo
 	if group_by.is_empty() {
 		return None;
 	}
-	let pattern = defn.pattern.as_ref()?;
-	let re = cached_regex(pattern)?;
-	let captures = re.captures(number)?;
-	let grouped: HashSet<usize> = group_by.iter().copied().collect();
+	let parsed = parse_number(number, defn)?;
+	let mut grouped = HashSet::new();
 	let mut parts = Vec::with_capacity(group_by.len());
 
-	for &index in group_by {
-		if index == 0 || index >= captures.len() {
-			return None;
-		}
-		match captures.get(index) {
+	for group in group_by {
+		let index = parsed.index_of(group)?;
+		grouped.insert(index);
+		match parsed.captures.get(index) {
 			Some(value) => {
 				let value = normalize_catalog_number(value.as_str());
 				parts.push(format!("{}:{}", value.len(), value));
@@ -615,9 +771,10 @@ fn group_key_inner(number: &str, defn: &CatalogDefinition) -> Option<(String, bo
 		}
 	}
 
-	let has_detail = (1..captures.len()).any(|index| {
+	let has_detail = (1..parsed.captures.len()).any(|index| {
 		!grouped.contains(&index)
-			&& captures
+			&& parsed
+				.captures
 				.get(index)
 				.map_or(false, |value| !value.as_str().is_empty())
 	});
@@ -637,10 +794,79 @@ pub fn looks_like_group(number: &str, defn: &CatalogDefinition) -> bool {
 	key.iter().rev().take_while(|v| **v == SortValue::NoneFirst).count() > 0
 }
 
+/// Checks each of the definition's `examples` against its own rules: the
+/// number must match `pattern`, fall inside the constraints and display as
+/// given, each `input` must normalize to it, and the examples must be listed
+/// in sort order.
+pub fn check_examples(scheme: &str, defn: &CatalogDefinition) -> Vec<String> {
+	let Some(examples) = &defn.examples else {
+		return Vec::new();
+	};
+	let patterned = has_pattern(defn);
+	let mut problems = Vec::new();
+	let mut parsed = Vec::new();
+
+	for example in examples {
+		// The stored form is itself a valid input, so the rules must leave it alone.
+		let stored = normalize_catalog_number(&example.number);
+		for input in std::iter::once(&example.number).chain(&example.input) {
+			let normalized = normalize_input(input, Some(defn));
+			if normalized != stored {
+				problems.push(format!(
+					"example input '{}' normalizes to '{}', not '{}'",
+					input, normalized, stored
+				));
+			}
+		}
+		if patterned && parse_number(&example.number, defn).is_none() {
+			problems.push(format!("example '{}' does not match the pattern", example.number));
+			continue;
+		}
+		parsed.push(example);
+		if let Err(error) = validate_catalog_domain(&example.number, defn) {
+			problems.push(format!("example '{}' is outside the domain: {}", example.number, error));
+		}
+		let display = crate::display::format_catalog(scheme, &example.number, Some(defn));
+		if display != example.display {
+			problems.push(format!(
+				"example '{}' displays as '{}', not '{}'",
+				example.number, display, example.display
+			));
+		}
+	}
+
+	// Numbers that do not parse have no meaningful position.
+	for pair in parsed.windows(2) {
+		if sort_key(&pair[0].number, defn) > sort_key(&pair[1].number, defn) {
+			problems.push(format!(
+				"example '{}' sorts before '{}' but is listed after it",
+				pair[1].number, pair[0].number
+			));
+		}
+	}
+
+	problems
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::types::SortKey;
+	use crate::types::{CatalogGroup, SortKey};
+
+	#[test]
+	fn reads_schemes_fused_to_numbers() {
+		let koechel: CatalogDefinition = serde_json::from_str(
+			r#"{"name": "Köchel", "input_rules": [{"strip_prefix": ["köchel", "kv.", "kv", "k.", "k"]}], "aliases": ["kv", "köchel"]}"#,
+		)
+		.unwrap();
+		let schemes = [("k".to_string(), koechel)];
+		let resolve = |token: &str| resolve_scheme_token(token, &schemes);
+		assert_eq!(resolve("KV"), Some(("k".into(), None)));
+		assert_eq!(resolve("K331"), Some(("k".into(), Some("331".into()))));
+		assert_eq!(resolve("KV 331"), Some(("k".into(), Some("331".into()))));
+		assert_eq!(resolve("kv.331"), Some(("k".into(), Some("331".into()))));
+		assert_eq!(resolve("kanon"), None);
+	}
 
 	#[test]
 	fn test_parse_roman() {
@@ -668,8 +894,9 @@ mod tests {
 			description: None,
 			canonical_format: None,
 			pattern: Some(r"^(\d+)$".into()),
+			patterns: None,
 			sort_keys: Some(vec![SortKey {
-				group: 1,
+				group: CatalogGroup::Index(1),
 				sort_type: "int".into(),
 				display: None,
 				none_last: None,
@@ -696,10 +923,11 @@ mod tests {
 			description: None,
 			canonical_format: None,
 			pattern: Some(r"^(\d+)(?:/(\d+))?([a-z])?$".into()),
+			patterns: None,
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 2, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 3, sort_type: "str".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(2), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(3), sort_type: "str".into(), display: None, none_last: None },
 			]),
 			group_by: None,
 			aliases: None,
@@ -722,8 +950,8 @@ mod tests {
 			name: "Test".into(),
 			pattern: Some(r"^(\d+)(?:/(\d+))?$".into()),
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 2, sort_type: "int".into(), display: None, none_last: Some(true) },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(2), sort_type: "int".into(), display: None, none_last: Some(true) },
 			]),
 			..Default::default()
 		};
@@ -814,6 +1042,139 @@ mod tests {
 		));
 	}
 
+	fn bwv_with_named_alternatives() -> CatalogDefinition {
+		serde_json::from_str(r#"{
+			"name": "BWV",
+			"canonical_format": "BWV {number}",
+			"patterns": [
+				"^(?<number>\\d+)(?:\\.(?<part>\\d+))?$",
+				"^(?<appendix>anh\\.)\\s*(?<section>[ivx]+)?\\s*(?<number>\\d+)$"
+			],
+			"sort_keys": [
+				{"group": "appendix", "type": "str", "display": "title"},
+				{"group": "section", "type": "roman", "display": "upper"},
+				{"group": "number", "type": "int"},
+				{"group": "part", "type": "int"}
+			],
+			"group_by": ["appendix", "section", "number"],
+			"constraints": [{"group": "number", "max": 1200}]
+		}"#).unwrap()
+	}
+
+	#[test]
+	fn named_groups_resolve_in_whichever_pattern_matched() {
+		let defn = bwv_with_named_alternatives();
+
+		let mut numbers: Vec<String> = ["anh. 116", "1080.2", "anh. iii 141", "1080.1", "812"]
+			.into_iter()
+			.map(String::from)
+			.collect();
+		sort_numbers(&mut numbers, Some(&defn));
+		assert_eq!(numbers, vec!["812", "1080.1", "1080.2", "anh. 116", "anh. iii 141"]);
+
+		// Numeric indices still address the pattern that matched.
+		let parsed = parse_number("anh. iii 141", &defn).unwrap();
+		assert_eq!(parsed.get(&CatalogGroup::Index(3)).map(|m| m.as_str()), Some("141"));
+		assert_eq!(parsed.get(&CatalogGroup::Name("part".into())), None);
+
+		assert!(matches_group("1080.1", "1080", Some(&defn)));
+		assert!(!matches_group("anh. 1080", "1080", Some(&defn)));
+		assert_eq!(group_key("1080.1", &defn), group_key("1080.2", &defn));
+		assert_eq!(group_member_key("812", &defn), None);
+
+		assert!(validate_catalog_domain("anh. 116", &defn).is_ok());
+		assert!(matches!(
+			validate_catalog_domain("anh. iii 1201", &defn),
+			Err(CatalogDomainError::AboveMaximum { group: CatalogGroup::Name(ref name), .. }) if name == "number"
+		));
+		assert_eq!(crate::display::format_catalog("bwv", "anh. iii 141", Some(&defn)), "BWV Anh. III 141");
+	}
+
+	#[test]
+	fn check_examples_reports_pattern_display_and_order() {
+		let defn: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Opus number",
+			"canonical_format": "op. {number}",
+			"part_format": "{main} no. {part}",
+			"pattern": "^(\\d+)(?:/(\\d+))?$",
+			"sort_keys": [{"group": 1, "type": "int"}, {"group": 2, "type": "int"}],
+			"constraints": [{"group": 2, "name": "sub-number", "min": 1}],
+			"examples": [
+				{"number": "2/1", "display": "op. 2 no. 1"},
+				{"number": "10", "display": "Op. 10"},
+				{"number": "3/0", "display": "op. 3 no. 0"},
+				{"number": "81a", "display": "op. 81a"}
+			]
+		}"#).unwrap();
+
+		assert_eq!(check_examples("op", &defn), vec![
+			"example '10' displays as 'op. 10', not 'Op. 10'",
+			"example '3/0' is outside the domain: sub-number 0 is below the minimum 1",
+			"example '81a' does not match the pattern",
+			"example '3/0' sorts before '10' but is listed after it",
+		]);
+
+		let defn = CatalogDefinition { examples: defn.examples.map(|mut e| { e.truncate(1); e }), ..defn };
+		assert!(check_examples("op", &defn).is_empty());
+	}
+
+	#[test]
+	fn input_rules_normalize_typed_numbers() {
+		let opus: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Opus number",
+			"input_rules": [
+				{"strip_prefix": ["opus", "op.", "op"]},
+				{"replace": {"pattern": "\\s*,?\\s*(?:no|nr)\\.?\\s*(\\d)", "with": "/$1"}}
+			]
+		}"#).unwrap();
+		assert_eq!(normalize_input("op.27 no.2", Some(&opus)), "27/2");
+		assert_eq!(normalize_input(" Op. 27, Nr. 2 ", Some(&opus)), "27/2");
+		assert_eq!(normalize_input("27/2", Some(&opus)), "27/2");
+		assert_eq!(normalize_input("Op. 27, Nr. 2", None), "op. 27, nr. 2");
+
+		let hoboken: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Hoboken-Verzeichnis",
+			"categories": {"III": "string quartets", "XVI": "keyboard sonatas"},
+			"input_rules": [
+				{"strip_prefix": ["hob.", "hob"]},
+				{"category": {"arabic": true}}
+			]
+		}"#).unwrap();
+		assert_eq!(normalize_input("Hob. XVI/52", Some(&hoboken)), "xvi:52");
+		assert_eq!(normalize_input("hob 16 52", Some(&hoboken)), "xvi:52");
+		assert_eq!(normalize_input("iii", Some(&hoboken)), "iii");
+		assert_eq!(normalize_input("17/1", Some(&hoboken)), "17/1");
+	}
+
+	#[test]
+	fn check_examples_normalizes_inputs() {
+		let defn: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Köchel-Verzeichnis",
+			"canonical_format": "K. {number}",
+			"input_rules": [{"strip_prefix": ["kv", "k"]}],
+			"examples": [{"number": "331", "display": "K. 331", "input": ["KV 331", "K. 331"]}]
+		}"#).unwrap();
+
+		assert_eq!(check_examples("k", &defn), vec![
+			"example input 'K. 331' normalizes to '. 331', not '331'",
+		]);
+	}
+
+	#[test]
+	fn input_rules_are_checked() {
+		let defn: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Test",
+			"input_rules": [{"replace": {"pattern": "(unclosed", "with": ""}}]
+		}"#).unwrap();
+		assert!(validate_input_rules(&defn).unwrap_err().starts_with("invalid input rule pattern '(unclosed'"));
+
+		let defn: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Test",
+			"input_rules": [{"category": {}}]
+		}"#).unwrap();
+		assert_eq!(validate_input_rules(&defn), Err("category input rule requires categories".to_string()));
+	}
+
 	fn fully_populated_global() -> CatalogDefinition {
 		serde_json::from_str(r#"{
 			"id": "op",
@@ -823,9 +1184,11 @@ mod tests {
 			"part_format": "{main} no. {part}",
 			"allowed_uppercase_suffixes": ["R"],
 			"pattern": "^(\\d+)(?:/(\\d+))?$",
+			"patterns": ["^posth\\. (\\d+)$"],
 			"sort_keys": [{"group": 1, "type": "int"}, {"group": 2, "type": "int"}],
 			"group_by": [1],
 			"examples": [{"number": "2/1", "display": "op. 2 no. 1"}],
+			"input_rules": [{"strip_prefix": ["op."]}],
 			"aliases": ["opus"],
 			"editions": {"1": {"year": 1850, "editor": "Shared"}},
 			"current_edition": "1",
@@ -855,9 +1218,11 @@ mod tests {
 			part_format,
 			allowed_uppercase_suffixes,
 			pattern,
+			patterns,
 			sort_keys,
 			group_by,
 			examples,
+			input_rules,
 			aliases,
 			editions,
 			current_edition,
@@ -876,9 +1241,11 @@ mod tests {
 		assert_eq!(part_format.as_deref(), Some("{main} no. {part}"));
 		assert_eq!(allowed_uppercase_suffixes, Some(vec!["R".to_string()]));
 		assert_eq!(pattern.as_deref(), Some(r"^(\d+)(?:/(\d+))?$"));
+		assert_eq!(patterns, Some(vec![r"^posth\. (\d+)$".to_string()]));
 		assert_eq!(sort_keys.map(|keys| keys.len()), Some(2));
-		assert_eq!(group_by, Some(vec![1]));
+		assert_eq!(group_by, Some(vec![CatalogGroup::Index(1)]));
 		assert_eq!(examples.map(|e| e.len()), Some(1));
+		assert_eq!(input_rules.map(|r| r.len()), Some(1));
 		assert_eq!(aliases, Some(vec!["opus".to_string()]));
 		assert_eq!(editions.map(|e| e.len()), Some(1));
 		assert_eq!(current_edition.as_deref(), Some("1"));
@@ -905,6 +1272,20 @@ mod tests {
 		assert_eq!(merged.description.as_deref(), Some("shared description"));
 	}
 
+	#[test]
+	fn merge_replaces_patterns_as_a_unit() {
+		let global = fully_populated_global();
+		let composer: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "Beethoven opus",
+			"patterns": ["^(?<opus>\\d+)$"]
+		}"#).unwrap();
+
+		let merged = merge_catalog_definitions(&global, &composer);
+
+		assert_eq!(merged.pattern, None);
+		assert_eq!(definition_patterns(&merged).collect::<Vec<_>>(), vec![r"^(?<opus>\d+)$"]);
+	}
+
 	#[test]
 	fn merge_accumulates_constraints_from_both_levels() {
 		let global = fully_populated_global();
@@ -917,8 +1298,8 @@ mod tests {
 		let constraints = merged.constraints.unwrap();
 
 		assert_eq!(constraints.len(), 2);
-		assert!(constraints.iter().any(|c| c.group == 2 && c.min == Some(1)));
-		assert!(constraints.iter().any(|c| c.group == 1 && c.max == Some(138)));
+		assert!(constraints.iter().any(|c| c.group == CatalogGroup::Index(2) && c.min == Some(1)));
+		assert!(constraints.iter().any(|c| c.group == CatalogGroup::Index(1) && c.max == Some(138)));
 	}
 
 	#[test]
@@ -5,8 +5,12 @@ use std::path::{Path, PathBuf};
 
 use crate::add::{commit_composition, prepare_composition_with, AddError, PreparedAdd};
 use crate::validate::Validator;
+use crate::commands::dupes::dataset_compositions;
 use crate::config::Config;
 use crate::display::{expand_title, ExpansionContext};
+use crate::dupes::{find_matches, DEFAULT_THRESHOLD};
+use crate::form::load_form_taxonomy;
+use crate::instrument::load_instrument_vocabulary;
 use crate::index::mark_index_dirty;
 use crate::output::{format_id_header, print};
 
@@ -19,6 +23,9 @@ struct PreflightFailure {
 struct BatchPlan {
 	prepared: Vec<PreparedAdd>,
 	failures: Vec<PreflightFailure>,
+	/// Incoming files that look like records already in the dataset or
+	/// earlier in the batch; these do not stop the add.
+	warnings: Vec<String>,
 	total: usize,
 }
 
@@ -58,13 +65,13 @@ fn current_catalog_keys(prepared: &PreparedAdd) -> Vec<(String, String, String)>
 	let mut seen = HashSet::new();
 	let mut keys = Vec::new();
 	for attribution in &prepared.composition.attribution {
-		let (Some(composer), Some(catalog)) = (&attribution.composer, &attribution.catalog) else {
+		let (Some(composer), Some(catalog)) = (attribution.principal(), &attribution.catalog) else {
 			continue;
 		};
 		for entry in catalog {
-			let scheme_key = (composer.clone(), entry.scheme.clone());
+			let scheme_key = (composer.to_string(), entry.scheme.clone());
 			if seen.insert(scheme_key) {
-				keys.push((composer.clone(), entry.scheme.clone(), entry.number.clone()));
+				keys.push((composer.to_string(), entry.scheme.clone(), entry.number.clone()));
 			}
 		}
 	}
@@ -84,11 +91,15 @@ fn preflight(sources: &[PathBuf], force: bool, data_dir: &Path) -> BatchPlan {
 				message: format!("Dataset integrity errors:\n  {}", integrity_errors.join("\n  ")),
 				already_exists: false,
 			}],
+			warnings: Vec::new(),
 			total: sources.len(),
 		};
 	}
-	let mut prepared = Vec::new();
+	let existing = dataset_compositions(data_dir);
+	let forms = load_form_taxonomy(data_dir).ok().flatten();
+	let mut prepared: Vec<PreparedAdd> = Vec::new();
 	let mut failures = Vec::new();
+	let mut warnings = Vec::new();
 	let mut destinations: HashMap<PathBuf, PathBuf> = HashMap::new();
 	let mut catalog_keys: HashMap<(String, String, String), (String, PathBuf)> = HashMap::new();
 
@@ -130,6 +141,17 @@ fn preflight(sources: &[PathBuf], force: bool, data_dir: &Path) -> BatchPlan {
 					continue;
 				}
 
+				let earlier = prepared.iter().map(|earlier| &earlier.composition);
+				for candidate in find_matches(&plan.composition, existing.iter().chain(earlier), forms.as_deref(), DEFAULT_THRESHOLD) {
+					warnings.push(format!(
+						"{} looks like {} (score {}: {})",
+						source.display(),
+						candidate.second,
+						candidate.score,
+						candidate.reasons.join(", ")
+					));
+				}
+
 				destinations.insert(plan.destination.clone(), source.clone());
 				for key in keys {
 					catalog_keys.insert(key, (plan.id.clone(), source.clone()));
@@ -150,11 +172,26 @@ fn preflight(sources: &[PathBuf], force: bool, data_dir: &Path) -> BatchPlan {
 	BatchPlan {
 		prepared,
 		failures,
+		warnings,
 		total: sources.len(),
 	}
 }
 
 fn summary_rows(prepared: &[PreparedAdd], data_dir: &Path, config: &Config) -> Vec<String> {
+	let forms = match load_form_taxonomy(data_dir) {
+		Ok(forms) => forms,
+		Err(error) => {
+			eprintln!("Error loading form taxonomy: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let instruments = match load_instrument_vocabulary(data_dir) {
+		Ok(instruments) => instruments,
+		Err(error) => {
+			eprintln!("Error loading instrument vocabulary: {}", error);
+			std::process::exit(1);
+		}
+	};
 	let rows: Vec<_> = prepared
 		.iter()
 		.map(|plan| {
@@ -163,6 +200,8 @@ fn summary_rows(prepared: &[PreparedAdd], data_dir: &Path, config: &Config) -> V
 				collection: None,
 				position_in_collection: None,
 				config: &config.display,
+				forms: forms.as_deref(),
+				instruments: instruments.as_deref(),
 			};
 			let title = expand_title(&ctx);
 			let catalog = match format_id_header(&plan.composition, &plan.id, data_dir) {
@@ -254,6 +293,9 @@ pub fn run(
 		}
 	};
 	let plan = preflight(&sources, force, data_dir);
+	for warning in &plan.warnings {
+		eprintln!("warning: {}", warning);
+	}
 
 	if !plan.failures.is_empty() {
 		if directory_input || interactive || dry_run {
@@ -395,6 +437,28 @@ mod tests {
 		assert!(plan.failures[0].message.contains("duplicate composition ID"));
 	}
 
+	#[test]
+	fn preflight_warns_about_likely_duplicates() {
+		let tmp = setup_data_dir();
+		let record = |id: &str| {
+			format!(
+				r#"{{"id":"{}","title":{{"en":"Piano Sonata in A major"}},"form":"sonata","key":"A",
+				"attribution":[{{"composer":"mozart"}}],"movements":[{{"title":"Andante grazioso"}},{{"title":"Rondo alla turca"}}]}}"#,
+				id
+			)
+		};
+		fs::create_dir_all(tmp.path().join("compositions/cd")).unwrap();
+		fs::write(tmp.path().join("compositions/cd/123456.json"), record("cd123456")).unwrap();
+		let input = tmp.path().join("incoming");
+		fs::create_dir(&input).unwrap();
+		fs::write(input.join("a.json"), record("ab123456")).unwrap();
+
+		let plan = preflight(&discover_sources(&input).unwrap(), false, tmp.path());
+		assert_eq!(plan.prepared.len(), 1, "{:?}", plan.failures.iter().map(|f| &f.message).collect::<Vec<_>>());
+		assert_eq!(plan.warnings.len(), 1);
+		assert!(plan.warnings[0].contains("looks like cd123456 (score 70"), "{}", plan.warnings[0]);
+	}
+
 	#[test]
 	fn confirmation_defaults_to_no() {
 		for input in ["\n", "n\n", "no\n"] {
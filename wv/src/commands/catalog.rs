@@ -0,0 +1,322 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use crate::catalog::{definition_patterns, load_catalog_def, matches_group, parse_roman};
+use crate::index::{get_or_build_index, Index};
+use crate::output::print;
+use crate::parse::load_composer;
+use crate::types::{CatalogConstraint, CatalogDefinition, InputRule};
+
+fn index_or_exit(data_dir: &Path) -> Index {
+	match get_or_build_index(data_dir) {
+		Ok(index) => index,
+		Err(error) => {
+			eprintln!("Error loading dataset: {}", error);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn definition_or_exit(data_dir: &Path, scheme: &str, composer: Option<&str>) -> Option<CatalogDefinition> {
+	match load_catalog_def(data_dir, scheme, composer) {
+		Ok(definition) => definition,
+		Err(error) => {
+			eprintln!("Error loading catalog metadata: {}", error);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn json_stems(dir: &Path) -> BTreeSet<String> {
+	let entries = match std::fs::read_dir(dir) {
+		Ok(entries) => entries,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return BTreeSet::new(),
+		Err(error) => {
+			eprintln!("Error reading directory {}: {}", dir.display(), error);
+			std::process::exit(1);
+		}
+	};
+	entries
+		.filter_map(Result::ok)
+		.map(|entry| entry.path())
+		.filter(|path| path.extension().is_some_and(|e| e == "json"))
+		.filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(str::to_string))
+		.collect()
+}
+
+/// Schemes defined in the composer's own file, in name order.
+fn composer_schemes(data_dir: &Path, composer: &str) -> BTreeSet<String> {
+	let path = data_dir.join("composers").join(format!("{}.json", composer));
+	match load_composer(&path) {
+		Ok(composer) => composer.catalogs.unwrap_or_default().into_keys().collect(),
+		Err(error) => {
+			eprintln!("Error loading composer {}: {}", path.display(), error);
+			std::process::exit(1);
+		}
+	}
+}
+
+/// Current record numbers and inventory entries for a scheme, for one
+/// composer or summed over all of them.
+#[derive(Default)]
+struct SchemeCounts {
+	records: Vec<String>,
+	inventory: Vec<String>,
+	has_inventory: bool,
+	complete: bool,
+}
+
+impl SchemeCounts {
+	fn collect(index: &Index, data_dir: &Path, scheme: &str, composer: Option<&str>) -> Self {
+		let composers: BTreeSet<&str> = match composer {
+			Some(composer) => BTreeSet::from([composer]),
+			None => {
+				let indexed = index.catalog.iter().filter(|(_, schemes)| schemes.contains_key(scheme));
+				let inventoried = index.inventory.catalogs.iter().filter(|(_, schemes)| schemes.contains_key(scheme));
+				indexed
+					.map(|(composer, _)| composer.as_str())
+					.chain(inventoried.map(|(composer, _)| composer.as_str()))
+					.collect()
+			}
+		};
+
+		let mut counts = SchemeCounts { complete: true, ..Default::default() };
+		for composer in composers {
+			if let Some(scheme_index) = index.catalog.get(composer).and_then(|schemes| schemes.get(scheme)) {
+				counts.records.extend(scheme_index.current.keys().cloned());
+			}
+			let defn = load_catalog_def(data_dir, scheme, Some(composer)).ok().flatten();
+			if let Some(catalog) = index.inventory.catalog(composer, scheme, None, defn.as_ref()) {
+				counts.has_inventory = true;
+				counts.complete &= catalog.complete;
+				counts.inventory.extend(catalog.entries.iter().cloned());
+			}
+		}
+		counts
+	}
+
+	fn describe(&self, filter: impl Fn(&str) -> bool) -> String {
+		let records = self.records.iter().filter(|number| filter(number)).count();
+		let mut text = format!("{} record{}", records, if records == 1 { "" } else { "s" });
+		if self.has_inventory {
+			let entries = self.inventory.iter().filter(|number| filter(number)).count();
+			text.push_str(&format!(", {} in inventory", entries));
+		}
+		text
+	}
+}
+
+pub fn list(composer: Option<&str>, data_dir: &Path) {
+	let index = index_or_exit(data_dir);
+	let global = json_stems(&data_dir.join("catalogs"));
+
+	if let Some(composer) = composer {
+		if !data_dir.join("composers").join(format!("{}.json", composer)).exists() {
+			eprintln!("Composer not found: {}", composer);
+			std::process::exit(1);
+		}
+		let schemes: BTreeSet<String> = global.union(&composer_schemes(data_dir, composer)).cloned().collect();
+		for scheme in schemes {
+			let Some(defn) = definition_or_exit(data_dir, &scheme, Some(composer)) else {
+				continue;
+			};
+			let counts = SchemeCounts::collect(&index, data_dir, &scheme, Some(composer));
+			print(&format!("{}\t{}\t({})", scheme, defn.name, counts.describe(|_| true)));
+		}
+		return;
+	}
+
+	for scheme in &global {
+		let Some(defn) = definition_or_exit(data_dir, scheme, None) else {
+			continue;
+		};
+		let counts = SchemeCounts::collect(&index, data_dir, scheme, None);
+		print(&format!("{}\tglobal\t{}\t({})", scheme, defn.name, counts.describe(|_| true)));
+	}
+	for composer in json_stems(&data_dir.join("composers")) {
+		for scheme in composer_schemes(data_dir, &composer) {
+			let Some(defn) = definition_or_exit(data_dir, &scheme, Some(&composer)) else {
+				continue;
+			};
+			let counts = SchemeCounts::collect(&index, data_dir, &scheme, Some(&composer));
+			print(&format!("{}\t{}\t{}\t({})", scheme, composer, defn.name, counts.describe(|_| true)));
+		}
+	}
+}
+
+fn describe_input_rule(rule: &InputRule) -> String {
+	match rule {
+		InputRule::StripPrefix(prefixes) => format!("strip prefix {}", prefixes.join(", ")),
+		InputRule::Replace { pattern, with } => format!("replace {} with {}", pattern, with),
+		InputRule::Category { arabic: false } => "join category".into(),
+		InputRule::Category { arabic: true } => "join category, Arabic numerals allowed".into(),
+	}
+}
+
+fn describe_constraint(constraint: &CatalogConstraint) -> String {
+	let mut parts = Vec::new();
+	match (constraint.min, constraint.max) {
+		(Some(min), Some(max)) => parts.push(format!("{}..{}", min, max)),
+		(Some(min), None) => parts.push(format!("at least {}", min)),
+		(None, Some(max)) => parts.push(format!("at most {}", max)),
+		(None, None) => {}
+	}
+	if let Some(ranges) = &constraint.ranges {
+		let ranges: Vec<String> = ranges.iter().map(|range| format!("{}..{}", range.min, range.max)).collect();
+		parts.push(format!("one of {}", ranges.join(", ")));
+	}
+	parts.join(", ")
+}
+
+/// Categories such as Hoboken's are mostly Roman numerals; order those by
+/// value ("IX" before "X") and anything else after them by name.
+fn category_order(category: &str) -> (i64, String) {
+	let numeral: String = category.chars().take_while(|c| "IVXLCDMivxlcdm".contains(*c)).collect();
+	let value = if numeral.is_empty() { i64::MAX } else { parse_roman(&numeral) };
+	(value, category.to_string())
+}
+
+/// The composer whose file defines a scheme that has no global definition.
+/// Exits when several composers define it, since their definitions differ.
+fn defining_composer(data_dir: &Path, scheme: &str) -> Option<String> {
+	if data_dir.join("catalogs").join(format!("{}.json", scheme)).exists() {
+		return None;
+	}
+	let composers: Vec<String> = json_stems(&data_dir.join("composers"))
+		.into_iter()
+		.filter(|composer| composer_schemes(data_dir, composer).contains(scheme))
+		.collect();
+	if composers.len() > 1 {
+		eprintln!("Catalog {} is defined by several composers: {}", scheme, composers.join(", "));
+		eprintln!("Choose one with --composer");
+		std::process::exit(1);
+	}
+	composers.into_iter().next()
+}
+
+pub fn show(scheme: &str, composer: Option<&str>, data_dir: &Path) {
+	let composer = composer.map(str::to_string).or_else(|| defining_composer(data_dir, scheme));
+	let composer = composer.as_deref();
+	let Some(defn) = definition_or_exit(data_dir, scheme, composer) else {
+		match composer {
+			Some(composer) => eprintln!("Catalog not found: {} for {}", scheme, composer),
+			None => eprintln!("Catalog not found: {}", scheme),
+		}
+		std::process::exit(1);
+	};
+	let index = index_or_exit(data_dir);
+	let counts = SchemeCounts::collect(&index, data_dir, scheme, composer);
+
+	print(&format!("{}: {}", scheme, defn.name));
+	if let Some(description) = &defn.description {
+		print(description);
+	}
+	print("");
+
+	if let Some(aliases) = &defn.aliases {
+		print(&format!("Aliases: {}", aliases.join(", ")));
+	}
+	let format = defn
+		.canonical_format
+		.clone()
+		.unwrap_or_else(|| format!("{} {{number}}", scheme.to_uppercase()));
+	print(&format!("Format: {}", format));
+	if let Some(part_format) = &defn.part_format {
+		print(&format!("Parts: {}", part_format));
+	}
+	let patterns: Vec<&str> = definition_patterns(&defn).collect();
+	match patterns.as_slice() {
+		[] => {}
+		[pattern] => print(&format!("Pattern: {}", pattern)),
+		patterns => {
+			print("Patterns:");
+			for pattern in patterns {
+				print(&format!("  {}", pattern));
+			}
+		}
+	}
+	if let Some(rules) = &defn.input_rules {
+		print("Input rules:");
+		for rule in rules {
+			print(&format!("  {}", describe_input_rule(rule)));
+		}
+	}
+	if let Some(sort_keys) = &defn.sort_keys {
+		print("Sort keys:");
+		for key in sort_keys {
+			let mut text = format!("  group {}: {}", key.group, key.sort_type);
+			if let Some(display) = &key.display {
+				text.push_str(&format!(", shown {}", display));
+			}
+			if key.none_last.unwrap_or(false) {
+				text.push_str(", missing last");
+			}
+			print(&text);
+		}
+	}
+	if let Some(group_by) = &defn.group_by {
+		let groups: Vec<String> = group_by.iter().map(ToString::to_string).collect();
+		print(&format!("Group by: {}", groups.join(", ")));
+	}
+	if let Some(constraints) = &defn.constraints {
+		print("Constraints:");
+		for constraint in constraints {
+			let name = constraint.name.as_ref().map_or(String::new(), |name| format!(" ({})", name));
+			print(&format!("  group {}{}: {}", constraint.group, name, describe_constraint(constraint)));
+		}
+	}
+	if let Some(categories) = &defn.categories {
+		let mut names: Vec<&String> = categories.keys().collect();
+		names.sort_by_key(|name| category_order(name));
+		print("Categories:");
+		for name in names {
+			let described = counts.describe(|number| matches_group(number, name, Some(&defn)));
+			print(&format!("  {}\t{}\t({})", name, categories[name], described));
+		}
+	}
+	if let Some(editions) = &defn.editions {
+		let mut labels: Vec<String> = editions.keys().cloned().collect();
+		labels.sort_by(|a, b| editions[a].year.cmp(&editions[b].year).then_with(|| a.cmp(b)));
+		print("Editions:");
+		for label in labels {
+			let edition = &editions[&label];
+			let mut text = format!("  {}\t{}\t{}", label, edition.year, edition.editor);
+			if let Some(entries) = composer
+				.and_then(|composer| index.inventory.catalogs.get(composer))
+				.and_then(|schemes| schemes.get(scheme))
+				.and_then(|scheme_index| scheme_index.editions.get(&label))
+			{
+				text.push_str(&format!("\t({} in inventory)", entries.entries.len()));
+			}
+			if defn.current_edition.as_deref() == Some(label.as_str()) {
+				text.push_str("\tcurrent");
+			}
+			print(&text);
+		}
+	}
+	if let Some(current) = &defn.current_edition {
+		print(&format!("Current edition: {}", current));
+	}
+	if let Some(examples) = &defn.examples {
+		print("Examples:");
+		for example in examples {
+			let mut text = format!("  {}\t{}", example.number, example.display);
+			if !example.input.is_empty() {
+				text.push_str(&format!("\t(typed {})", example.input.join(", ")));
+			}
+			print(&text);
+		}
+	}
+
+	print("");
+	print(&format!("Records: {}", counts.records.len()));
+	if counts.has_inventory {
+		print(&format!(
+			"Inventory: {} entries, {}",
+			counts.inventory.len(),
+			if counts.complete { "complete" } else { "incomplete" }
+		));
+	} else {
+		print("Inventory: none");
+	}
+}
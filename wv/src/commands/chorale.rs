@@ -0,0 +1,148 @@
+use std::fs;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::index::{get_or_build_index, normalize_words};
+use crate::output::{output_pretty, print, OutputContext};
+use crate::parse::load_chorale;
+use crate::types::ChoraleMelody;
+
+pub struct ChoraleArgs {
+	pub name: String,
+	pub terse: bool,
+}
+
+pub fn run(args: ChoraleArgs, data_dir: &Path, config: &Config) {
+	let melodies = load_melodies(data_dir);
+	let matches = find_melodies(&melodies, &args.name);
+	let melody = match matches.as_slice() {
+		[melody] => *melody,
+		[] => {
+			eprintln!("No chorale melody matches '{}'", args.name);
+			std::process::exit(1);
+		}
+		_ => {
+			eprintln!("'{}' matches several chorale melodies:", args.name);
+			for melody in matches {
+				eprintln!("  {} [{}]", melody.label(), melody.id);
+			}
+			std::process::exit(1);
+		}
+	};
+
+	let index = match get_or_build_index(data_dir) {
+		Ok(index) => index,
+		Err(error) => {
+			eprintln!("Error building index: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let results = index.find_by_chorale(&melody.id);
+
+	if args.terse {
+		let mut ids: Vec<&str> = results.iter().map(|result| result.id.as_str()).collect();
+		ids.dedup();
+		for id in ids {
+			print(id);
+		}
+		return;
+	}
+
+	print(&melody.label());
+	if results.is_empty() {
+		print("  (no settings recorded)");
+		return;
+	}
+	let ctx = OutputContext {
+		data_dir,
+		config,
+		scheme: None,
+		catalog_defn: None,
+		text: false,
+	};
+	if let Err(error) = output_pretty(&results, &ctx) {
+		eprintln!("Error producing output: {}", error);
+		std::process::exit(1);
+	}
+}
+
+fn load_melodies(data_dir: &Path) -> Vec<ChoraleMelody> {
+	let mut paths: Vec<_> = match fs::read_dir(data_dir.join("chorales")) {
+		Ok(entries) => entries
+			.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+			.filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+			.collect(),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+		Err(error) => {
+			eprintln!("Error reading chorales/: {}", error);
+			std::process::exit(1);
+		}
+	};
+	paths.sort();
+	paths
+		.iter()
+		.map(|path| match load_chorale(path) {
+			Ok(melody) => melody,
+			Err(error) => {
+				eprintln!("Error loading chorale melody {}: {}", path.display(), error);
+				std::process::exit(1);
+			}
+		})
+		.collect()
+}
+
+/// Melodies named by slug, Zahn number or title. A full title wins over
+/// titles it is only the beginning of.
+fn find_melodies<'a>(melodies: &'a [ChoraleMelody], name: &str) -> Vec<&'a ChoraleMelody> {
+	let zahn = name.trim();
+	let zahn = zahn.strip_prefix("Zahn").or_else(|| zahn.strip_prefix("zahn")).unwrap_or(zahn).trim();
+	let wanted = normalize_words(name);
+	let exact: Vec<&ChoraleMelody> = melodies
+		.iter()
+		.filter(|melody| {
+			melody.id == name || melody.zahn.as_deref() == Some(zahn) || normalize_words(&melody.title) == wanted
+		})
+		.collect();
+	if !exact.is_empty() || wanted.is_empty() {
+		return exact;
+	}
+	let prefix = format!("{} ", wanted);
+	melodies
+		.iter()
+		.filter(|melody| normalize_words(&melody.title).starts_with(&prefix))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn melody(id: &str, title: &str, zahn: &str) -> ChoraleMelody {
+		ChoraleMelody {
+			id: id.into(),
+			title: title.into(),
+			zahn: Some(zahn.into()),
+			origin: None,
+			note: None,
+		}
+	}
+
+	#[test]
+	fn finds_melodies_by_title_slug_or_zahn_number() {
+		let melodies = [
+			melody("jesu-meine-freude", "Jesu, meine Freude", "8032"),
+			melody("was-gott-tut", "Was Gott tut, das ist wohlgetan", "5629"),
+			melody("was-mein-gott-will", "Was mein Gott will, das g'scheh allzeit", "7568"),
+		];
+		let ids = |name: &str| -> Vec<String> {
+			find_melodies(&melodies, name).iter().map(|melody| melody.id.clone()).collect()
+		};
+
+		assert_eq!(ids("jesu meine freude"), ["jesu-meine-freude"]);
+		assert_eq!(ids("was-gott-tut"), ["was-gott-tut"]);
+		assert_eq!(ids("Zahn 5629"), ["was-gott-tut"]);
+		assert_eq!(ids("Was Gott tut"), ["was-gott-tut"]);
+		assert_eq!(ids("Was"), ["was-gott-tut", "was-mein-gott-will"]);
+		assert!(ids("Ein feste Burg").is_empty());
+	}
+}
@@ -1,8 +1,11 @@
 use std::path::Path;
 
-use crate::catalog::load_catalog_def;
+use crate::catalog::{load_catalog_def, normalize_catalog_number, normalize_input, resolve_scheme};
 use crate::config::Config;
 use crate::display::{expand_title, format_catalog, ExpansionContext};
+use crate::duration::{work_duration, DurationValue};
+use crate::form::load_form_taxonomy;
+use crate::instrument::load_instrument_vocabulary;
 use crate::index::get_or_build_index;
 use crate::merge::collection_path_from_id;
 use crate::output::print;
@@ -119,7 +122,7 @@ pub fn show(id: &str, data_dir: &Path, config: &Config) {
 	let composer = collection
 		.attribution
 		.first()
-		.and_then(|a| a.composer.as_deref())
+		.and_then(|a| a.principal())
 		.or(collection.composer.as_deref())
 		.unwrap_or_else(|| id.split_once('-').map(|(c, _)| c).unwrap_or(id));
 
@@ -139,6 +142,22 @@ pub fn show(id: &str, data_dir: &Path, config: &Config) {
 
 	print("");
 
+	let mut total: Option<DurationValue> = None;
+	let mut undurated = 0;
+	let forms = match load_form_taxonomy(data_dir) {
+		Ok(forms) => forms,
+		Err(error) => {
+			eprintln!("Error loading form taxonomy: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let instruments = match load_instrument_vocabulary(data_dir) {
+		Ok(instruments) => instruments,
+		Err(error) => {
+			eprintln!("Error loading instrument vocabulary: {}", error);
+			std::process::exit(1);
+		}
+	};
 	for num in &collection.compositions {
 		let found = match index
 			.query()
@@ -174,13 +193,39 @@ pub fn show(id: &str, data_dir: &Path, config: &Config) {
 				collection: None,
 				position_in_collection: None,
 				config: &config.display,
+				forms: forms.as_deref(),
+				instruments: instruments.as_deref(),
 			};
 			let title = expand_title(&ctx);
-			print(&format!("{}, {}", title, formatted_cat));
+			match work_duration(&comp) {
+				Some(duration) => {
+					total = Some(total.map_or(duration, |total| total + duration));
+					print(&format!("{}, {} ({})", title, formatted_cat, duration));
+				}
+				None => {
+					undurated += 1;
+					print(&format!("{}, {}", title, formatted_cat));
+				}
+			}
 		} else {
+			undurated += 1;
 			print(&format!("{} (not indexed)", formatted_cat));
 		}
 	}
+
+	if let Some(total) = total {
+		print("");
+		if undurated == 0 {
+			print(&format!("Total: {}", total));
+		} else {
+			print(&format!(
+				"Total: at least {} ({} of {} without a duration)",
+				total,
+				undurated,
+				collection.compositions.len()
+			));
+		}
+	}
 }
 
 pub fn find(query: &str, data_dir: &Path) {
@@ -201,6 +246,30 @@ pub fn find(query: &str, data_dir: &Path) {
 			continue;
 		}
 
+		// Members are stored numbers; the query is typed, so its scheme is
+		// resolved ("BWV", "kv") and the number goes through the composer's
+		// input rules for that scheme.
+		let composer = composer_entry.file_name().to_string_lossy().into_owned();
+		let (resolved, fused) = match resolve_scheme(data_dir, &composer, scheme) {
+			Ok(resolved) => resolved.unwrap_or_else(|| (scheme.to_lowercase(), None)),
+			Err(error) => {
+				eprintln!("Error loading catalog metadata: {}", error);
+				std::process::exit(1);
+			}
+		};
+		let defn = match load_catalog_def(data_dir, &resolved, Some(&composer)) {
+			Ok(definition) => definition,
+			Err(error) => {
+				eprintln!("Error loading catalog metadata: {}", error);
+				std::process::exit(1);
+			}
+		};
+		let typed = match fused {
+			Some(fused) => format!("{} {}", fused, number),
+			None => number.to_string(),
+		};
+		let wanted = normalize_input(&typed, defn.as_ref());
+
 		for file_entry in read_dir_or_exit(&composer_entry.path()) {
 			let path = file_entry.path();
 			if path.extension().map_or(true, |e| e != "json") {
@@ -214,7 +283,9 @@ pub fn find(query: &str, data_dir: &Path) {
 					std::process::exit(1);
 				}
 			};
-			if coll.scheme == scheme && coll.compositions.contains(&number.to_string()) {
+			if coll.scheme == resolved
+				&& coll.compositions.iter().any(|member| normalize_catalog_number(member) == wanted)
+			{
 				found.push(coll.id.clone());
 			}
 		}
@@ -268,7 +339,7 @@ pub fn expand(ids: &[String], data_dir: &Path) -> Vec<ExpandedRef> {
 		let composer = collection
 			.attribution
 			.first()
-			.and_then(|a| a.composer.clone())
+			.and_then(|a| a.principal().map(str::to_string))
 			.or_else(|| collection.composer.clone())
 			.unwrap_or_else(|| id.split_once('-').map(|(c, _)| c.to_string()).unwrap_or_default());
 
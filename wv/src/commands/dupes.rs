@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use crate::commands::instrumentation::composition_files;
+use crate::dupes::find_duplicates;
+use crate::form::load_form_taxonomy;
+use crate::output::print;
+use crate::parse::load_composition;
+use crate::types::Composition;
+
+/// List pairs of records that probably describe the same work, best first.
+pub fn run(composer: Option<&str>, threshold: u32, data_dir: &Path) {
+	let forms = match load_form_taxonomy(data_dir) {
+		Ok(forms) => forms,
+		Err(error) => {
+			eprintln!("Error loading form taxonomy: {}", error);
+			std::process::exit(1);
+		}
+	};
+
+	let mut compositions = dataset_compositions(data_dir);
+	if let Some(composer) = composer {
+		compositions.retain(|comp| {
+			comp.attribution.iter().any(|entry| {
+				entry.principal() == Some(composer)
+					|| entry.contributors.iter().flatten().any(|c| c.composer == composer)
+			})
+		});
+	}
+
+	let candidates = find_duplicates(&compositions, forms.as_deref(), threshold);
+	for candidate in &candidates {
+		print(&format!(
+			"{:>3}  {}  {}  {}",
+			candidate.score,
+			candidate.first,
+			candidate.second,
+			candidate.reasons.join(", ")
+		));
+	}
+	eprintln!("{} likely duplicate pair(s) among {} composition(s)", candidates.len(), compositions.len());
+}
+
+/// Every composition in the dataset that parses; the others are reported and
+/// left to `wv validate`.
+pub(crate) fn dataset_compositions(data_dir: &Path) -> Vec<Composition> {
+	composition_files(&[], data_dir)
+		.into_iter()
+		.filter_map(|path| match load_composition(&path) {
+			Ok(comp) => Some(comp),
+			Err(error) => {
+				eprintln!("warning: skipping {}: {}", path.display(), error);
+				None
+			}
+		})
+		.collect()
+}
@@ -0,0 +1,103 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::catalog::load_catalog_def;
+use crate::format::{format_data, FileKind};
+use crate::output::print;
+use crate::types::Inventory;
+
+const DATA_DIRS: [&str; 9] = [
+	"compositions",
+	"composers",
+	"collections",
+	"catalogs",
+	"inventories",
+	"people",
+	"references",
+	"chorales",
+	"taxonomy",
+];
+
+/// Rewrite data files in canonical form. With `check`, only list the files
+/// that are not, and fail if there are any.
+pub fn run(paths: &[PathBuf], check: bool, data_dir: &Path) {
+	let mut files = Vec::new();
+	let mut failed = 0;
+	if paths.is_empty() {
+		for dir in DATA_DIRS {
+			collect_data_files(&data_dir.join(dir), &mut files);
+		}
+	}
+	for path in paths {
+		if path.is_dir() {
+			collect_data_files(path, &mut files);
+		} else if FileKind::of_path(path).is_some() {
+			files.push(path.clone());
+		} else {
+			eprintln!("{}: not a file in one of the data directories", path.display());
+			failed += 1;
+		}
+	}
+
+	let mut changed = 0;
+	for path in &files {
+		let Some(kind) = FileKind::of_path(path) else {
+			continue;
+		};
+		let content = match fs::read_to_string(path) {
+			Ok(content) => content,
+			Err(error) => {
+				eprintln!("{}: {}", path.display(), error);
+				failed += 1;
+				continue;
+			}
+		};
+		let catalog = match kind {
+			FileKind::Inventory => toml::from_str::<Inventory>(&content)
+				.ok()
+				.and_then(|inventory| load_catalog_def(data_dir, &inventory.scheme, Some(&inventory.composer)).ok().flatten()),
+			_ => None,
+		};
+		let formatted = match format_data(kind, &content, catalog.as_ref()) {
+			Ok(formatted) => formatted,
+			Err(error) => {
+				eprintln!("{}: {}", path.display(), error);
+				failed += 1;
+				continue;
+			}
+		};
+		if formatted == content {
+			continue;
+		}
+
+		changed += 1;
+		print(&path.display().to_string());
+		if !check {
+			if let Err(error) = fs::write(path, formatted) {
+				eprintln!("{}: {}", path.display(), error);
+				std::process::exit(1);
+			}
+		}
+	}
+
+	let verb = if check { "would be reformatted" } else { "reformatted" };
+	eprintln!("{} of {} file(s) {}, {} failed", changed, files.len(), verb, failed);
+	if failed > 0 || (check && changed > 0) {
+		std::process::exit(1);
+	}
+}
+
+fn collect_data_files(dir: &Path, files: &mut Vec<PathBuf>) {
+	let Ok(entries) = fs::read_dir(dir) else {
+		return;
+	};
+	let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+	paths.sort();
+	for path in paths {
+		if path.is_dir() {
+			collect_data_files(&path, files);
+		} else if FileKind::of_path(&path).is_some() {
+			files.push(path);
+		}
+	}
+}
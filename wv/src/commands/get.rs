@@ -1,20 +1,33 @@
+use std::collections::BTreeSet;
 use std::io::{self, BufRead};
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::rc::Rc;
 
-use crate::catalog::{load_catalog_def, validate_catalog_domain, CatalogLoadError};
+use crate::catalog::{
+	find_category, load_catalog_def, normalize_input, resolve_scheme, validate_catalog_domain, CatalogLoadError,
+};
 use crate::commands::collection;
 use crate::config::{resolve_editor, Config};
+use crate::date::parse_year_range;
+use crate::duration::{work_duration, DurationValue};
+use crate::form::{load_form_taxonomy, FormTaxonomy};
+use crate::instrument::{load_instrument_vocabulary, InstrumentVocabulary};
+use crate::key::{Key, KeyQuery};
 use crate::display::{expand_title, format_catalog, ExpansionContext};
 use crate::index::{get_or_build_index, mark_index_dirty, Index};
 use crate::inventory::InventoryLookup;
 use crate::output::{
-	id_to_path, output_by_ids, output_json, output_movements, output_pretty, output_terse,
-	print, OutputContext, OutputError,
+	id_to_path, output_by_ids, output_json, output_movements, output_pretty, output_related,
+	output_sources,
+	output_terse, print, OutputContext, OutputError,
 };
 use crate::parse::load_composition;
-use crate::query::QueryError;
-use crate::types::CatalogDefinition;
+use crate::merge::merge_attribution;
+use crate::occasion::occasion_label;
+use crate::query::{QueryError, QueryResult};
+use crate::movement::walk_movements;
+use crate::types::{CatalogDefinition, Composition};
 use crate::xref::{check_duplicates, MbLookup};
 
 fn get_index_or_exit(data_dir: &Path) -> Index {
@@ -37,9 +50,27 @@ fn query_or_exit<T>(result: Result<T, QueryError>) -> T {
 	}
 }
 
-fn catalog_or_exit(
-	result: Result<Option<CatalogDefinition>, CatalogLoadError>,
-) -> Option<CatalogDefinition> {
+fn forms_or_exit(data_dir: &Path) -> Option<Rc<FormTaxonomy>> {
+	match load_form_taxonomy(data_dir) {
+		Ok(forms) => forms,
+		Err(error) => {
+			eprintln!("Error loading form taxonomy: {}", error);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn instruments_or_exit(data_dir: &Path) -> Option<Rc<InstrumentVocabulary>> {
+	match load_instrument_vocabulary(data_dir) {
+		Ok(instruments) => instruments,
+		Err(error) => {
+			eprintln!("Error loading instrument vocabulary: {}", error);
+			std::process::exit(1);
+		}
+	}
+}
+
+fn catalog_or_exit<T>(result: Result<Option<T>, CatalogLoadError>) -> Option<T> {
 	match result {
 		Ok(definition) => definition,
 		Err(error) => {
@@ -56,7 +87,7 @@ fn output_or_exit(result: Result<(), OutputError>) {
 	}
 }
 
-fn composition_or_exit(path: &Path) -> crate::types::Composition {
+fn composition_or_exit(path: &Path) -> Composition {
 	match load_composition(path) {
 		Ok(composition) => composition,
 		Err(error) => {
@@ -82,6 +113,18 @@ pub struct GetArgs {
 	pub strict: bool,
 	pub xref: Option<String>,
 	pub collection: Option<Vec<String>>,
+	pub composed: Option<String>,
+	pub max_duration: Option<String>,
+	pub form: Option<String>,
+	pub key: Option<String>,
+	pub chronological: bool,
+	pub nickname: Option<String>,
+	pub related: bool,
+	pub sources: bool,
+	pub person: Option<String>,
+	pub occasion: Option<String>,
+	pub first_line: Option<String>,
+	pub text: bool,
 }
 
 enum Input {
@@ -131,44 +174,12 @@ fn parse_number_spec(s: &str) -> NumberSpec {
 	NumberSpec::Single(s.to_string())
 }
 
-fn find_category<'a>(value: &str, defn: &'a CatalogDefinition) -> Option<&'a str> {
-	defn.categories
-		.as_ref()?
-		.keys()
-		.find(|category| category.eq_ignore_ascii_case(value))
-		.map(String::as_str)
-}
-
-fn normalize_number_piece(value: &str, defn: Option<&CatalogDefinition>) -> String {
-	let normalized = value.trim().to_lowercase();
-	let Some(defn) = defn else {
-		return normalized;
-	};
-
-	let mut parts = normalized.split_whitespace();
-	let Some(first) = parts.next() else {
-		return normalized;
-	};
-	let rest: Vec<&str> = parts.collect();
-
-	if let Some(category) = find_category(first, defn) {
-		let category = category.to_lowercase();
-		if rest.is_empty() {
-			category
-		} else {
-			format!("{}:{}", category, rest.join(" "))
-		}
-	} else {
-		normalized
-	}
-}
-
 fn normalize_number_spec(spec: &NumberSpec, defn: Option<&CatalogDefinition>) -> NumberSpec {
 	match spec {
-		NumberSpec::Single(number) => NumberSpec::Single(normalize_number_piece(number, defn)),
+		NumberSpec::Single(number) => NumberSpec::Single(normalize_input(number, defn)),
 		NumberSpec::Range { start, end } => {
-			let start = normalize_number_piece(start, defn);
-			let mut end = normalize_number_piece(end, defn);
+			let start = normalize_input(start, defn);
+			let mut end = normalize_input(end, defn);
 			if !end.contains(':') {
 				if let (Some(defn), Some((prefix, _))) = (defn, start.rsplit_once(':')) {
 					if find_category(prefix, defn).is_some() {
@@ -190,6 +201,33 @@ fn category_for_spec(spec: &NumberSpec, defn: &CatalogDefinition) -> Option<Stri
 	find_category(prefix, defn).map(str::to_lowercase)
 }
 
+/// Replace a scheme token that is not one of the composer's schemes with
+/// the scheme it stands for, moving any number fused to it in front of the
+/// number arguments.
+fn resolve_query_scheme(mut query: ComposerQuery, index: &Index, data_dir: &Path) -> ComposerQuery {
+	let Some(token) = query.scheme.clone() else {
+		return query;
+	};
+	let indexed = index.catalog.get(&query.composer).into_iter().flat_map(|schemes| schemes.keys());
+	let inventoried = index.inventory.catalogs.get(&query.composer).into_iter().flat_map(|schemes| schemes.keys());
+	let schemes: BTreeSet<&String> = indexed.chain(inventoried).collect();
+	if schemes.iter().any(|scheme| scheme.eq_ignore_ascii_case(&token)) {
+		return query;
+	}
+	let Some((scheme, fused)) = catalog_or_exit(resolve_scheme(data_dir, &query.composer, &token)) else {
+		return query;
+	};
+	query.scheme = Some(scheme);
+	if let Some(fused) = fused {
+		query.number = Some(match &query.number {
+			None => parse_number_spec(&fused),
+			Some(NumberSpec::Single(rest)) => parse_number_spec(&format!("{} {}", fused, rest)),
+			Some(NumberSpec::Range { start, end }) => parse_number_spec(&format!("{} {}-{}", fused, start, end)),
+		});
+	}
+	query
+}
+
 fn resolve_input(args: &GetArgs) -> Option<Input> {
 	if args.stdin {
 		let mut ids = Vec::new();
@@ -271,6 +309,26 @@ pub fn run(args: GetArgs, data_dir: PathBuf, config: &Config) {
 		return;
 	}
 
+	if let Some(nickname) = &args.nickname {
+		run_nickname(nickname, &args, &data_dir, config);
+		return;
+	}
+
+	if let Some(person) = &args.person {
+		run_person(&person.to_lowercase(), &args, &data_dir, config);
+		return;
+	}
+
+	if let Some(occasion) = &args.occasion {
+		run_occasion(&occasion.to_lowercase(), &args, &data_dir, config);
+		return;
+	}
+
+	if let Some(first_line) = &args.first_line {
+		run_first_line(first_line, &args, &data_dir, config);
+		return;
+	}
+
 	let input = match resolve_input(&args) {
 		Some(i) => i,
 		None => {
@@ -278,6 +336,10 @@ pub fn run(args: GetArgs, data_dir: PathBuf, config: &Config) {
 			eprintln!("       wv get <id> [id...]");
 			eprintln!("       wv get --stdin");
 			eprintln!("       wv get --collection <id>...");
+			eprintln!("       wv get [composer] --nickname <name>");
+			eprintln!("       wv get [composer] --person <slug>");
+			eprintln!("       wv get [composer] --occasion <slug>");
+			eprintln!("       wv get [composer] --first-line <text>");
 			std::process::exit(1);
 		}
 	};
@@ -303,8 +365,13 @@ pub fn run(args: GetArgs, data_dir: PathBuf, config: &Config) {
 			if args.edit {
 				let paths: Vec<PathBuf> = ids.iter().map(|id| id_to_path(&data_dir, id)).collect();
 				open_in_editor(config, &paths, &data_dir);
+			} else if args.related {
+				let index = get_index_or_exit(&data_dir);
+				output_or_exit(output_related(&ids, &index, &data_dir, config));
+			} else if args.sources {
+				output_or_exit(output_sources(&ids, &data_dir, config));
 			} else {
-				output_or_exit(output_by_ids(&ids, &data_dir, config, args.terse, args.movements, args.json));
+				output_or_exit(output_by_ids(&ids, &data_dir, config, args.terse, args.movements, args.json, args.text));
 			}
 		}
 		Input::Query(query) => {
@@ -313,6 +380,95 @@ pub fn run(args: GetArgs, data_dir: PathBuf, config: &Config) {
 	}
 }
 
+fn run_nickname(nickname: &str, args: &GetArgs, data_dir: &Path, config: &Config) {
+	let index = get_index_or_exit(data_dir);
+	let ids = index.find_by_nickname(nickname);
+	output_lookup(ids, &index, args, data_dir, config);
+}
+
+fn run_person(person: &str, args: &GetArgs, data_dir: &Path, config: &Config) {
+	let index = get_index_or_exit(data_dir);
+	let ids = index.people.get(person).cloned().unwrap_or_default();
+	output_lookup(ids, &index, args, data_dir, config);
+}
+
+fn run_occasion(occasion: &str, args: &GetArgs, data_dir: &Path, config: &Config) {
+	if occasion_label(occasion).is_none() {
+		eprintln!("Unknown occasion '{}' (expected e.g. advent-1, trinity+4, easter-monday)", occasion);
+		std::process::exit(1);
+	}
+	let index = get_index_or_exit(data_dir);
+	let ids = index.occasions.get(occasion).cloned().unwrap_or_default();
+	output_lookup(ids, &index, args, data_dir, config);
+}
+
+/// Works and movements whose sung text starts with the given words, listed
+/// at the movement they occur in.
+fn run_first_line(first_line: &str, args: &GetArgs, data_dir: &Path, config: &Config) {
+	let index = get_index_or_exit(data_dir);
+	let mut results = index.find_by_first_line(first_line);
+	if let Some(composer) = &args.target {
+		let by_composer = index.by_composer.get(composer);
+		results.retain(|result| by_composer.is_some_and(|ids| ids.contains(&result.id)));
+	}
+
+	if results.is_empty() {
+		if !args.quiet {
+			eprintln!("No results found.");
+		}
+		return;
+	}
+	if args.edit {
+		let mut paths: Vec<PathBuf> = results.iter().map(|r| id_to_path(data_dir, &r.id)).collect();
+		paths.dedup();
+		open_in_editor(config, &paths, data_dir);
+		return;
+	}
+
+	let ctx = OutputContext {
+		data_dir,
+		config,
+		scheme: None,
+		catalog_defn: None,
+		text: args.text,
+	};
+	if args.json {
+		output_or_exit(output_json(&results, &ctx));
+	} else if args.movements {
+		output_or_exit(output_movements(&results, &ctx));
+	} else if args.terse {
+		output_terse(&results);
+	} else {
+		output_or_exit(output_pretty(&results, &ctx));
+	}
+}
+
+/// Shared tail of the index lookups that bypass catalog queries, optionally
+/// narrowed to the composer given as the target.
+fn output_lookup(mut ids: Vec<String>, index: &Index, args: &GetArgs, data_dir: &Path, config: &Config) {
+	if let Some(composer) = &args.target {
+		let by_composer = index.by_composer.get(composer);
+		ids.retain(|id| by_composer.is_some_and(|ids| ids.contains(id)));
+	}
+
+	if ids.is_empty() {
+		if !args.quiet {
+			eprintln!("No results found.");
+		}
+		return;
+	}
+	if args.edit {
+		let paths: Vec<PathBuf> = ids.iter().map(|id| id_to_path(data_dir, id)).collect();
+		open_in_editor(config, &paths, data_dir);
+	} else if args.related {
+		output_or_exit(output_related(&ids, index, data_dir, config));
+	} else if args.sources {
+		output_or_exit(output_sources(&ids, data_dir, config));
+	} else {
+		output_or_exit(output_by_ids(&ids, data_dir, config, args.terse, args.movements, args.json, args.text));
+	}
+}
+
 fn print_query_examples(
 	query: &ComposerQuery,
 	number_spec: Option<&NumberSpec>,
@@ -605,6 +761,102 @@ fn handle_inventory_miss(
 	}
 }
 
+fn has_filters(args: &GetArgs) -> bool {
+	args.composed.is_some() || args.max_duration.is_some() || args.form.is_some() || args.key.is_some()
+}
+
+/// Whether the work or any of its movements is in a matching key.
+fn has_key(comp: &Composition, query: &KeyQuery) -> bool {
+	comp.key
+		.as_deref()
+		.into_iter()
+		.chain(walk_movements(comp).into_iter().filter_map(|visit| visit.node.key()))
+		.filter_map(|key| key.parse::<Key>().ok())
+		.any(|key| query.matches(&key))
+}
+
+/// Narrow and reorder fetched results by fields that live in the composition
+/// files rather than the index.
+fn filter_results(results: Vec<QueryResult>, args: &GetArgs, data_dir: &Path) -> Vec<QueryResult> {
+	if !has_filters(args) && !args.chronological {
+		return results;
+	}
+	let composed_range = args.composed.as_deref().map(|range| {
+		parse_year_range(range).unwrap_or_else(|error| {
+			eprintln!("Error: --composed: {}", error);
+			std::process::exit(1);
+		})
+	});
+
+	let max_duration = args.max_duration.as_deref().map(|limit| {
+		DurationValue::parse_limit(limit).unwrap_or_else(|error| {
+			eprintln!("Error: --max-duration: {}", error);
+			std::process::exit(1);
+		})
+	});
+
+	let key_query = args.key.as_deref().map(|key| {
+		key.parse::<KeyQuery>().unwrap_or_else(|error| {
+			eprintln!("Error: --key: {}", error);
+			std::process::exit(1);
+		})
+	});
+
+	let forms = forms_or_exit(data_dir);
+	if let (Some(form), Some(forms)) = (&args.form, &forms) {
+		if forms.resolve(form).is_none() {
+			match forms.suggest(form) {
+				Some(suggestion) => eprintln!("Error: --form: unknown form '{}' (did you mean '{}'?)", form, suggestion),
+				None => eprintln!("Error: --form: unknown form '{}'", form),
+			}
+			std::process::exit(1);
+		}
+	}
+
+	let mut kept = Vec::new();
+	for result in results {
+		let comp = composition_or_exit(&id_to_path(data_dir, &result.id));
+		let composed = merge_attribution(&comp.attribution).dates.composed;
+		if let Some((from, to)) = composed_range {
+			if !composed.is_some_and(|date| date.overlaps_years(from, to)) {
+				continue;
+			}
+		}
+		// A work fits if its shortest likely performance does.
+		if let Some(limit) = max_duration {
+			if work_duration(&comp).is_none_or(|duration| duration.min > limit) {
+				continue;
+			}
+		}
+		if let Some(form) = &args.form {
+			let matches = match &forms {
+				Some(forms) => forms.is_a(&comp.form, form),
+				None => comp.form.eq_ignore_ascii_case(form),
+			};
+			if !matches {
+				continue;
+			}
+		}
+		if let Some(query) = &key_query {
+			if !has_key(&comp, query) {
+				continue;
+			}
+		}
+		kept.push((composed, result));
+	}
+
+	if args.chronological {
+		// Undated works go last; the sort is stable, so catalog order breaks ties.
+		kept.sort_by(|(a, _), (b, _)| match (a, b) {
+			(Some(a), Some(b)) => a.cmp(b),
+			(Some(_), None) => std::cmp::Ordering::Less,
+			(None, Some(_)) => std::cmp::Ordering::Greater,
+			(None, None) => std::cmp::Ordering::Equal,
+		});
+	}
+	kept.into_iter().map(|(_, result)| result).collect()
+}
+
 fn run_query(query: ComposerQuery, args: &GetArgs, data_dir: &Path, config: &Config) {
 	if (matches!(&query.number, Some(NumberSpec::Range { .. })) || query.group.is_some())
 		&& query.scheme.is_none()
@@ -615,6 +867,7 @@ fn run_query(query: ComposerQuery, args: &GetArgs, data_dir: &Path, config: &Con
 	}
 
 	let index = get_index_or_exit(data_dir);
+	let query = resolve_query_scheme(query, &index, data_dir);
 	let catalog_defn = match query.scheme.as_ref() {
 		Some(scheme) => catalog_or_exit(load_catalog_def(data_dir, scheme, Some(&query.composer))),
 		None => None,
@@ -713,7 +966,17 @@ fn run_query(query: ComposerQuery, args: &GetArgs, data_dir: &Path, config: &Con
 		return;
 	}
 
+	let results = filter_results(results, args, data_dir);
+	if results.is_empty() {
+		if !args.quiet {
+			eprintln!("No results match the given filters.");
+		}
+		return;
+	}
+
+	// Inventory gaps are meaningless once results have been filtered.
 	let inventory_group_members = match (query.scheme.as_deref(), number_spec.as_ref()) {
+		_ if has_filters(args) => None,
 		(Some(scheme), Some(NumberSpec::Single(number))) => match index.inventory.lookup(
 			&query.composer,
 			scheme,
@@ -773,11 +1036,20 @@ fn run_query(query: ComposerQuery, args: &GetArgs, data_dir: &Path, config: &Con
 		config,
 		scheme: query.scheme.as_deref(),
 		catalog_defn: catalog_defn.as_ref(),
+		text: args.text,
 	};
 
 	if let Some(members) = inventory_group_members.as_ref() {
 		if args.json {
 			output_inventory_group_overlay(&query, members, &results, args, &ctx);
+		} else if args.related {
+			let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+			output_or_exit(output_related(&ids, &index, data_dir, config));
+			warn_inventory_only_entries(inventory_only_count, args);
+		} else if args.sources {
+			let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+			output_or_exit(output_sources(&ids, data_dir, config));
+			warn_inventory_only_entries(inventory_only_count, args);
 		} else if args.movements {
 			output_or_exit(output_movements(&results, &ctx));
 			warn_inventory_only_entries(inventory_only_count, args);
@@ -790,6 +1062,12 @@ fn run_query(query: ComposerQuery, args: &GetArgs, data_dir: &Path, config: &Con
 		}
 	} else if args.json {
 		output_or_exit(output_json(&results, &ctx));
+	} else if args.related {
+		let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+		output_or_exit(output_related(&ids, &index, data_dir, config));
+	} else if args.sources {
+		let ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+		output_or_exit(output_sources(&ids, data_dir, config));
 	} else if args.movements {
 		output_or_exit(output_movements(&results, &ctx));
 	} else if args.terse {
@@ -905,6 +1183,7 @@ fn run_collections(collection_ids: &[String], args: &GetArgs, data_dir: &Path, c
 			config,
 			scheme: None,
 			catalog_defn: None,
+			text: false,
 		};
 		output_or_exit(output_json(&all_results, &ctx));
 		return;
@@ -928,6 +1207,8 @@ fn run_collections(collection_ids: &[String], args: &GetArgs, data_dir: &Path, c
 		open_in_editor(config, &paths, data_dir);
 		return;
 	}
+	let forms = forms_or_exit(data_dir);
+	let instruments = instruments_or_exit(data_dir);
 	for r in &refs {
 		let results = query_or_exit(index
 			.query()
@@ -965,6 +1246,8 @@ fn run_collections(collection_ids: &[String], args: &GetArgs, data_dir: &Path, c
 					collection: None,
 					position_in_collection: None,
 					config: &config.display,
+					forms: forms.as_deref(),
+					instruments: instruments.as_deref(),
 				};
 				let title = expand_title(&expansion_ctx);
 				let formatted_cat = format_catalog(&r.scheme, &r.number, catalog_defn.as_ref());
@@ -979,6 +1262,7 @@ mod tests {
 	use std::collections::HashMap;
 
 	use super::*;
+	use crate::types::InputRule;
 
 	fn test_args(number: &[&str]) -> GetArgs {
 		GetArgs {
@@ -997,6 +1281,18 @@ mod tests {
 			strict: false,
 			xref: None,
 			collection: None,
+			composed: None,
+			max_duration: None,
+			form: None,
+			key: None,
+			chronological: false,
+			nickname: None,
+			related: false,
+			sources: false,
+			person: None,
+			occasion: None,
+			first_line: None,
+			text: false,
 		}
 	}
 
@@ -1006,6 +1302,7 @@ mod tests {
 		CatalogDefinition {
 			name: "Hoboken-Verzeichnis".into(),
 			categories: Some(categories),
+			input_rules: Some(vec![InputRule::Category { arabic: false }]),
 			..Default::default()
 		}
 	}
@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use crate::config::Config;
+use crate::incipit::{
+	contains_intervals, intervals, parse_data, parse_incipit, pitch_sequence, render_svg,
+	render_text, KeySignature,
+};
+use crate::index::get_or_build_index;
+use crate::movement::{format_movement_path, movement_at, parse_movement_path, walk_movements};
+use crate::output::{id_to_path, output_pretty, print, OutputContext};
+use crate::parse::load_composition;
+use crate::query::QueryResult;
+use crate::types::{Composition, Incipit};
+
+pub struct IncipitArgs {
+	pub id: Option<String>,
+	pub movement: Option<String>,
+	pub svg: bool,
+	pub search: Option<String>,
+}
+
+pub fn run(args: IncipitArgs, data_dir: &Path, config: &Config) {
+	if let Some(search) = &args.search {
+		run_search(search, data_dir, config);
+		return;
+	}
+	let Some(id) = &args.id else {
+		eprintln!("Error: give a composition ID, or --search with incipit data");
+		std::process::exit(1);
+	};
+
+	let path = id_to_path(data_dir, id);
+	let comp = match load_composition(&path) {
+		Ok(comp) => comp,
+		Err(error) => {
+			eprintln!("Error loading composition {}: {}", path.display(), error);
+			std::process::exit(1);
+		}
+	};
+
+	let mut incipits = incipits(&comp);
+	if let Some(movement) = &args.movement {
+		let Some(path) = parse_movement_path(movement).filter(|path| movement_at(&comp, path).is_some()) else {
+			eprintln!("Error: movement '{}' not found in {}", movement, comp.id);
+			std::process::exit(1);
+		};
+		incipits.retain(|(incipit_path, _, _)| incipit_path.as_ref() == Some(&path));
+	}
+	if incipits.is_empty() {
+		match &args.movement {
+			Some(movement) => eprintln!("No incipit recorded for movement {} of {}", movement, comp.id),
+			None => eprintln!("No incipit recorded for {}", comp.id),
+		}
+		std::process::exit(1);
+	}
+
+	if args.svg {
+		// One drawing per invocation; without --movement, the first incipit.
+		let (_, _, incipit) = &incipits[0];
+		print(render_svg(&parse_or_exit(incipit, &comp.id)).trim_end());
+		return;
+	}
+
+	let show_labels = incipits.len() > 1 || incipits[0].0.is_some();
+	for (i, (_, label, incipit)) in incipits.iter().enumerate() {
+		if show_labels {
+			if i > 0 {
+				print("");
+			}
+			print(label);
+		}
+		print(&render_text(&parse_or_exit(incipit, &comp.id)));
+	}
+}
+
+fn parse_or_exit(incipit: &Incipit, id: &str) -> crate::incipit::ParsedIncipit {
+	match parse_incipit(incipit) {
+		Ok(parsed) => parsed,
+		Err(error) => {
+			eprintln!("Error in incipit of {}: {}", id, error);
+			std::process::exit(1);
+		}
+	}
+}
+
+/// The work's own incipit, then each movement's, with movement path and label.
+fn incipits(comp: &Composition) -> Vec<(Option<Vec<usize>>, String, &Incipit)> {
+	let mut incipits = Vec::new();
+	if let Some(incipit) = &comp.incipit {
+		incipits.push((None, comp.id.clone(), incipit));
+	}
+	for visit in walk_movements(comp) {
+		if let Some(incipit) = visit.node.incipit() {
+			let label = format!("{}. {}", format_movement_path(&visit.path), visit.node.label());
+			incipits.push((Some(visit.path), label, incipit));
+		}
+	}
+	incipits
+}
+
+/// Find incipits containing the query's interval sequence at any pitch level.
+fn run_search(data: &str, data_dir: &Path, config: &Config) {
+	let query = match parse_data(data, &KeySignature::default()) {
+		Ok(events) => intervals(&pitch_sequence(&events)),
+		Err(error) => {
+			eprintln!("Error in search incipit: {}", error);
+			std::process::exit(1);
+		}
+	};
+	if query.is_empty() {
+		eprintln!("Error: search incipit needs at least two notes");
+		std::process::exit(1);
+	}
+
+	let index = match get_or_build_index(data_dir) {
+		Ok(index) => index,
+		Err(error) => {
+			eprintln!("Error loading dataset: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let mut ids: Vec<&String> = index.by_composer.values().flatten().collect();
+	ids.sort();
+	ids.dedup();
+
+	let mut results = Vec::new();
+	for id in ids {
+		let Ok(comp) = load_composition(id_to_path(data_dir, id)) else {
+			continue;
+		};
+		for (movement, _, incipit) in incipits(&comp) {
+			// Malformed incipits are reported by `wv validate`.
+			let Ok(parsed) = parse_incipit(incipit) else {
+				continue;
+			};
+			if contains_intervals(&intervals(&pitch_sequence(&parsed.events)), &query) {
+				results.push(QueryResult {
+					id: comp.id.clone(),
+					number: None,
+					superseded: false,
+					current_number: None,
+					note: None,
+					movement,
+				});
+			}
+		}
+	}
+
+	if results.is_empty() {
+		eprintln!("No results found.");
+		return;
+	}
+	let ctx = OutputContext {
+		data_dir,
+		config,
+		scheme: None,
+		catalog_defn: None,
+		text: false,
+	};
+	if let Err(error) = output_pretty(&results, &ctx) {
+		eprintln!("Error producing output: {}", error);
+		std::process::exit(1);
+	}
+}
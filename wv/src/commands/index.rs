@@ -14,12 +14,15 @@ pub fn run(data_dir: &Path) {
 		}
 	};
 
-	let mut total_compositions = 0;
 	let mut total_catalog_entries = 0;
 
-	for ids in index.by_composer.values() {
-		total_compositions += ids.len();
-	}
+	// A work credited to several composers is listed under each.
+	let total_compositions = index
+		.by_composer
+		.values()
+		.flatten()
+		.collect::<std::collections::HashSet<_>>()
+		.len();
 
 	for schemes in index.catalog.values() {
 		for scheme_index in schemes.values() {
@@ -57,6 +60,30 @@ pub fn run(data_dir: &Path) {
 		"Wrote {}",
 		indexes_dir.join("inventory-index.json").display()
 	);
+	eprintln!(
+		"Wrote {}",
+		indexes_dir.join("nickname-index.json").display()
+	);
+	eprintln!(
+		"Wrote {}",
+		indexes_dir.join("relation-index.json").display()
+	);
+	eprintln!(
+		"Wrote {}",
+		indexes_dir.join("person-index.json").display()
+	);
+	eprintln!(
+		"Wrote {}",
+		indexes_dir.join("first-line-index.json").display()
+	);
+	eprintln!(
+		"Wrote {}",
+		indexes_dir.join("occasion-index.json").display()
+	);
+	eprintln!(
+		"Wrote {}",
+		indexes_dir.join("chorale-index.json").display()
+	);
 	if !index.editions.is_empty() {
 		eprintln!(
 			"Wrote edition indexes to {}",
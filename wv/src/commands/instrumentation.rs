@@ -0,0 +1,158 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::ser::{PrettyFormatter, Serializer};
+
+use crate::instrument::{format_instrumentation, instrument_vocabulary_path, load_instrument_vocabulary, parse_instrumentation};
+use crate::output::print;
+use crate::types::{InstrumentPart, Instrumentation};
+
+/// Convert free-text instrumentation to structured parts. Without `write`
+/// this only reports what would change.
+pub fn run_migrate(paths: &[PathBuf], write: bool, data_dir: &Path) {
+	let vocabulary = match load_instrument_vocabulary(data_dir) {
+		Ok(Some(vocabulary)) => vocabulary,
+		Ok(None) => {
+			eprintln!("No instrument vocabulary at {}", instrument_vocabulary_path(data_dir).display());
+			std::process::exit(1);
+		}
+		Err(error) => {
+			eprintln!("Error loading instrument vocabulary: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let files = composition_files(paths, data_dir);
+
+	let mut migrated = 0;
+	let mut skipped = 0;
+	for path in files {
+		let content = match fs::read_to_string(&path) {
+			Ok(content) => content,
+			Err(error) => {
+				eprintln!("{}: {}", path.display(), error);
+				skipped += 1;
+				continue;
+			}
+		};
+		let value: serde_json::Value = match serde_json::from_str(&content) {
+			Ok(value) => value,
+			Err(error) => {
+				eprintln!("{}: {}", path.display(), error);
+				skipped += 1;
+				continue;
+			}
+		};
+		let Some(text) = value.get("instrumentation").and_then(|v| v.as_str()) else {
+			continue;
+		};
+
+		let parsed = parse_instrumentation(text, &vocabulary);
+		if !parsed.unrecognized.is_empty() || parsed.parts.is_empty() {
+			eprintln!(
+				"{}: unrecognized: {}",
+				path.display(),
+				parsed.unrecognized.join(" | ")
+			);
+			skipped += 1;
+			continue;
+		}
+
+		let parts = parsed.parts;
+		let formatted = format_instrumentation(&Instrumentation::Parts(parts.clone()), Some(&vocabulary));
+		print(&format!("{}\t{}", path.display(), formatted));
+		migrated += 1;
+
+		if write {
+			match replace_instrumentation(&content, text, &parts) {
+				Some(updated) => {
+					if let Err(error) = fs::write(&path, updated) {
+						eprintln!("{}: {}", path.display(), error);
+						std::process::exit(1);
+					}
+				}
+				None => {
+					eprintln!("{}: could not locate instrumentation line", path.display());
+					migrated -= 1;
+					skipped += 1;
+				}
+			}
+		}
+	}
+
+	eprintln!("migrated: {}, skipped: {}", migrated, skipped);
+	if !write && migrated > 0 {
+		eprintln!("Run with --write to update files.");
+	}
+}
+
+/// Swap the instrumentation string in place so the rest of the file keeps
+/// its key order and formatting.
+fn replace_instrumentation(content: &str, text: &str, parts: &[InstrumentPart]) -> Option<String> {
+	let needle = format!("\"instrumentation\": {}", serde_json::to_string(text).ok()?);
+	let start = content.find(&needle)?;
+	let line_start = content[..start].rfind('\n').map_or(0, |i| i + 1);
+	let indent = &content[line_start..start];
+
+	let mut buffer = Vec::new();
+	let mut serializer = Serializer::with_formatter(&mut buffer, PrettyFormatter::with_indent(b"\t"));
+	parts.serialize(&mut serializer).ok()?;
+	let array = String::from_utf8(buffer).ok()?;
+	let array = array.replace('\n', &format!("\n{}", indent));
+
+	Some(format!(
+		"{}\"instrumentation\": {}{}",
+		&content[..start],
+		array,
+		&content[start + needle.len()..]
+	))
+}
+
+/// JSON files under the given paths, or every composition when none are given.
+pub(crate) fn composition_files(paths: &[PathBuf], data_dir: &Path) -> Vec<PathBuf> {
+	let mut files = Vec::new();
+	if paths.is_empty() {
+		collect_json_files(&data_dir.join("compositions"), &mut files);
+	}
+	for path in paths {
+		if path.is_dir() {
+			collect_json_files(path, &mut files);
+		} else {
+			files.push(path.clone());
+		}
+	}
+	files
+}
+
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) {
+	let Ok(entries) = fs::read_dir(dir) else {
+		eprintln!("Failed to read directory: {}", dir.display());
+		return;
+	};
+	let mut paths: Vec<PathBuf> = entries.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+	paths.sort();
+	for path in paths {
+		if path.is_dir() {
+			collect_json_files(&path, files);
+		} else if path.extension().is_some_and(|ext| ext == "json") {
+			files.push(path);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn replaces_only_the_instrumentation_value() {
+		let content = "{\n\t\"id\": \"12345678\",\n\t\"instrumentation\": \"2 violins, continuo\",\n\t\"form\": \"sonata\"\n}\n";
+		let vocabulary = load_instrument_vocabulary(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap().unwrap();
+		let parts = parse_instrumentation("2 violins, continuo", &vocabulary).parts;
+		let updated = replace_instrumentation(content, "2 violins, continuo", &parts).unwrap();
+		assert_eq!(
+			updated,
+			"{\n\t\"id\": \"12345678\",\n\t\"instrumentation\": [\n\t\t{\n\t\t\t\"instrument\": \"violin\",\n\t\t\t\"count\": 2\n\t\t},\n\t\t{\n\t\t\t\"instrument\": \"continuo\"\n\t\t}\n\t],\n\t\"form\": \"sonata\"\n}\n"
+		);
+	}
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::instrumentation::composition_files;
+use crate::config::DisplayConfig;
+use crate::index::mark_index_dirty;
+use crate::lint::{apply_fixes, Linter, Severity};
+use crate::output::print;
+use crate::types::Composition;
+
+/// Report style problems in compositions; with `fix`, rewrite the ones that
+/// have a safe fix and report only what is left.
+pub fn run(paths: &[PathBuf], fix: bool, data_dir: &Path, display: &DisplayConfig) {
+	let linter = match Linter::new(data_dir, display) {
+		Ok(linter) => linter,
+		Err(error) => {
+			eprintln!("Error: {}", error);
+			std::process::exit(1);
+		}
+	};
+
+	let mut warnings = 0;
+	let mut errors = 0;
+	let mut fixed = 0;
+	for path in composition_files(paths, data_dir) {
+		let path_str = path.display().to_string();
+		let content = match fs::read_to_string(&path) {
+			Ok(content) => content,
+			Err(error) => {
+				eprintln!("{}: {}", path_str, error);
+				errors += 1;
+				continue;
+			}
+		};
+		let comp: Composition = match serde_json::from_str(&content) {
+			Ok(comp) => comp,
+			Err(error) => {
+				eprintln!("{}: {}", path_str, error);
+				errors += 1;
+				continue;
+			}
+		};
+
+		let mut diagnostics = linter.lint_composition(&comp, &path_str);
+		if fix && diagnostics.iter().any(|d| d.fix.is_some()) {
+			let (updated, applied) = apply_fixes(&content, &diagnostics);
+			if applied > 0 {
+				if let Err(error) = fs::write(&path, &updated) {
+					eprintln!("{}: {}", path_str, error);
+					std::process::exit(1);
+				}
+				fixed += applied;
+				// Lint the rewritten file so only what is left gets reported.
+				if let Ok(comp) = serde_json::from_str::<Composition>(&updated) {
+					diagnostics = linter.lint_composition(&comp, &path_str);
+				}
+			}
+		}
+
+		for diagnostic in diagnostics {
+			match diagnostic.severity {
+				Severity::Error => errors += 1,
+				_ => warnings += 1,
+			}
+			print(&diagnostic.to_string());
+		}
+	}
+
+	if fixed > 0 {
+		if let Err(error) = mark_index_dirty(data_dir) {
+			eprintln!("warning: failed to mark index stale: {}", error);
+		}
+	}
+
+	eprintln!("{} error(s), {} warning(s), {} fixed", errors, warnings, fixed);
+	if errors > 0 {
+		std::process::exit(1);
+	}
+}
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use crate::display::format_credits;
 use crate::merge::merge_attribution;
 use crate::output::print;
 use crate::parse::load_composition;
@@ -22,8 +23,9 @@ pub fn run(path: &Path, _data_dir: &Path) {
 	}
 	print("");
 	print("Merged attribution:");
-	if let Some(composer) = &merged.composer {
-		print(&format!("  Composer: {}", composer));
+	let credits = format_credits(merged.composer.as_deref(), &merged.contributors);
+	if !credits.is_empty() {
+		print(&format!("  Composer: {}", credits));
 	}
 	if let Some(composed) = merged.dates.composed {
 		print(&format!("  Composed: {}", composed));
@@ -31,6 +33,12 @@ pub fn run(path: &Path, _data_dir: &Path) {
 	if let Some(published) = merged.dates.published {
 		print(&format!("  Published: {}", published));
 	}
+	if let Some(premiered) = merged.dates.premiered {
+		print(&format!("  Premiered: {}", premiered));
+	}
+	if let Some(revised) = merged.dates.revised {
+		print(&format!("  Revised: {}", revised));
+	}
 	if let Some(status) = &merged.status {
 		print(&format!("  Status: {:?}", status));
 	}
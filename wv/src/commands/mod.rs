@@ -1,11 +1,19 @@
 pub mod add;
+pub mod catalog;
+pub mod chorale;
 pub mod collection;
+pub mod fmt;
 pub mod coverage;
+pub mod dupes;
 pub mod render;
 pub mod get;
+pub mod incipit;
 pub mod index;
+pub mod instrumentation;
+pub mod lint;
 pub mod merge;
 pub mod new;
+pub mod occasion;
 pub mod parse;
 pub mod set;
 pub mod sort;
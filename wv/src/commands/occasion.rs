@@ -0,0 +1,70 @@
+use std::path::Path;
+
+use crate::config::Config;
+use crate::date::PartialDate;
+use crate::index::get_or_build_index;
+use crate::occasion::{occasion_label, occasions_on, previous_sunday};
+use crate::output::{output_by_ids, print};
+
+pub struct OccasionArgs {
+	pub date: String,
+	pub terse: bool,
+}
+
+/// Names the church-year occasions of a date and lists works written for
+/// them. A weekday with no feast of its own takes the preceding Sunday.
+pub fn run(args: OccasionArgs, data_dir: &Path, config: &Config) {
+	let date = match args.date.parse::<PartialDate>() {
+		Ok(PartialDate { year, month: Some(month), day: Some(day) }) => (year, month, day),
+		Ok(_) => {
+			eprintln!("Error: give a full date as YYYY-MM-DD");
+			std::process::exit(1);
+		}
+		Err(error) => {
+			eprintln!("Error: invalid date '{}': {}", args.date, error);
+			std::process::exit(1);
+		}
+	};
+
+	let (year, month, day) = date;
+	let mut heading = args.date.clone();
+	let mut occasions = occasions_on(year, month, day);
+	if occasions.is_empty() {
+		let sunday = previous_sunday(year, month, day);
+		occasions = occasions_on(sunday.year, sunday.month.unwrap_or(1), sunday.day.unwrap_or(1));
+		heading = format!("{} (week of {})", args.date, sunday);
+	}
+	if occasions.is_empty() {
+		eprintln!("No church-year occasion falls on {}", heading);
+		return;
+	}
+
+	let index = match get_or_build_index(data_dir) {
+		Ok(index) => index,
+		Err(error) => {
+			eprintln!("Error building index: {}", error);
+			std::process::exit(1);
+		}
+	};
+
+	for (i, occasion) in occasions.iter().enumerate() {
+		let label = occasion_label(occasion).unwrap_or_else(|| occasion.clone());
+		let ids = index.occasions.get(occasion).cloned().unwrap_or_default();
+		if !args.terse {
+			if i > 0 {
+				print("");
+			}
+			print(&format!("{}: {} [{}]", heading, label, occasion));
+		}
+		if ids.is_empty() {
+			if !args.terse {
+				print("  (no works recorded)");
+			}
+			continue;
+		}
+		if let Err(error) = output_by_ids(&ids, data_dir, config, args.terse, false, false, false) {
+			eprintln!("Error producing output: {}", error);
+			std::process::exit(1);
+		}
+	}
+}
@@ -4,6 +4,8 @@ use std::path::Path;
 use crate::catalog::load_catalog_def;
 use crate::config::Config;
 use crate::display::{expand_title, format_catalog, ExpansionContext};
+use crate::form::load_form_taxonomy;
+use crate::instrument::load_instrument_vocabulary;
 use crate::output::print;
 use crate::types::Composition;
 
@@ -28,12 +30,28 @@ pub fn run(data_dir: &Path, config: &Config) {
 			std::process::exit(1);
 		};
 
+	let forms = match load_form_taxonomy(data_dir) {
+		Ok(forms) => forms,
+		Err(error) => {
+			eprintln!("Error loading form taxonomy: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let instruments = match load_instrument_vocabulary(data_dir) {
+		Ok(instruments) => instruments,
+		Err(error) => {
+			eprintln!("Error loading instrument vocabulary: {}", error);
+			std::process::exit(1);
+		}
+	};
 	for comp in &compositions {
 		let ctx = ExpansionContext {
 			composition: comp,
 			collection: None,
 			position_in_collection: None,
 			config: &config.display,
+			forms: forms.as_deref(),
+			instruments: instruments.as_deref(),
 		};
 		let title = expand_title(&ctx);
 
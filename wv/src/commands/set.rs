@@ -1,7 +1,7 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::catalog::load_catalog_def;
+use crate::catalog::{load_catalog_def, resolve_scheme};
 use crate::config::Config;
 use crate::index::{get_or_build_index, mark_index_dirty};
 use crate::output::{id_to_path, print};
@@ -26,7 +26,7 @@ pub fn run(args: SetArgs, data_dir: PathBuf, config: &Config) {
 		std::process::exit(1);
 	}
 
-	let Some(scheme) = &args.scheme else {
+	let Some(token) = &args.scheme else {
 		eprintln!("Error: set --xref requires a catalog scheme");
 		std::process::exit(1);
 	};
@@ -58,6 +58,20 @@ pub fn run(args: SetArgs, data_dir: PathBuf, config: &Config) {
 	};
 	let composer = &args.target;
 
+	// "BWV", "kv" and "K331" all name a scheme; the number may come fused to it.
+	let (scheme, fused) = match resolve_scheme(&data_dir, composer, token) {
+		Ok(resolved) => resolved.unwrap_or_else(|| (token.to_lowercase(), None)),
+		Err(error) => {
+			eprintln!("Error loading catalog metadata: {}", error);
+			std::process::exit(1);
+		}
+	};
+	let scheme = scheme.as_str();
+	let with_fused = |n: &str| match &fused {
+		Some(fused) => format!("{} {}", fused, n),
+		None => n.to_string(),
+	};
+
 	let catalog_defn = match load_catalog_def(&data_dir, scheme, Some(composer)) {
 		Ok(definition) => definition,
 		Err(error) => {
@@ -70,10 +84,12 @@ pub fn run(args: SetArgs, data_dir: PathBuf, config: &Config) {
 
 	if let Some(n) = &args.number {
 		if let Some((start, end)) = n.split_once('-').or_else(|| n.split_once("..")) {
-			builder = builder.range(start, end).sorted(&data_dir);
+			builder = builder.range(&with_fused(start), &with_fused(end)).sorted(&data_dir);
 		} else {
-			builder = builder.number(n);
+			builder = builder.number(&with_fused(n));
 		}
+	} else if let Some(fused) = &fused {
+		builder = builder.number(fused);
 	}
 
 	let results = match builder.fetch() {
@@ -1,8 +1,21 @@
 use std::path::Path;
 
-use crate::validate::{validate_all, validate_file};
+use serde_json::{json, Value};
 
-pub fn run(path: Option<&Path>, data_dir: &Path) {
+use crate::validate::{validate_all, validate_file, ErrorCode, Level, ValidationError};
+
+/// Exit status when there are warnings but no errors; 2 is clap's usage error.
+const EXIT_WARNINGS: i32 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+	Text,
+	Json,
+	/// SARIF 2.1.0, for code-scanning tools.
+	Sarif,
+}
+
+pub fn run(path: Option<&Path>, format: ReportFormat, data_dir: &Path) {
 	let errors = if let Some(p) = path {
 		validate_file(p, data_dir)
 	} else {
@@ -10,13 +23,100 @@ pub fn run(path: Option<&Path>, data_dir: &Path) {
 		validate_all(data_dir)
 	};
 
+	match format {
+		ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&json_report(&errors)).unwrap()),
+		ReportFormat::Sarif => println!("{}", serde_json::to_string_pretty(&sarif_report(&errors)).unwrap()),
+		ReportFormat::Text => print_text(&errors),
+	}
+
+	if errors.iter().any(|e| e.severity == Level::Error) {
+		std::process::exit(1);
+	}
+	if !errors.is_empty() {
+		std::process::exit(EXIT_WARNINGS);
+	}
+}
+
+fn print_text(errors: &[ValidationError]) {
 	if errors.is_empty() {
 		eprintln!("No validation errors found.");
-	} else {
+		return;
+	}
+	let warnings = errors.iter().filter(|e| e.severity == Level::Warning).count();
+	if warnings == 0 {
 		eprintln!("Found {} validation error(s):", errors.len());
-		for err in &errors {
+	} else {
+		eprintln!("Found {} validation error(s) and {} warning(s):", errors.len() - warnings, warnings);
+	}
+	for err in errors {
+		if err.severity == Level::Warning {
+			eprintln!("  warning: {}", err);
+		} else {
 			eprintln!("  {}", err);
 		}
-		std::process::exit(1);
+	}
+}
+
+fn json_report(errors: &[ValidationError]) -> Value {
+	errors
+		.iter()
+		.map(|e| {
+			json!({
+				"file": e.path,
+				"pointer": e.pointer,
+				"code": e.code,
+				"severity": e.severity,
+				"message": e.message,
+			})
+		})
+		.collect()
+}
+
+/// SARIF 2.1.0, for code-scanning tools.
+fn sarif_report(errors: &[ValidationError]) -> Value {
+	let mut codes: Vec<ErrorCode> = errors.iter().map(|e| e.code).collect();
+	codes.sort();
+	codes.dedup();
+	let rules: Vec<Value> = codes.iter().map(|code| json!({ "id": code.to_string() })).collect();
+
+	let results: Vec<Value> = errors
+		.iter()
+		.map(|e| {
+			let mut location = json!({
+				"physicalLocation": { "artifactLocation": { "uri": e.path } },
+			});
+			if !e.pointer.is_empty() {
+				location["logicalLocations"] = json!([{ "fullyQualifiedName": e.pointer, "kind": "object" }]);
+			}
+			json!({
+				"ruleId": e.code.to_string(),
+				"ruleIndex": codes.binary_search(&e.code).unwrap_or_default(),
+				"level": sarif_level(e.severity),
+				"message": { "text": e.message },
+				"locations": [location],
+			})
+		})
+		.collect();
+
+	json!({
+		"$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+		"version": "2.1.0",
+		"runs": [{
+			"tool": {
+				"driver": {
+					"name": "wv",
+					"version": env!("CARGO_PKG_VERSION"),
+					"rules": rules,
+				}
+			},
+			"results": results,
+		}],
+	})
+}
+
+fn sarif_level(severity: Level) -> &'static str {
+	match severity {
+		Level::Error => "error",
+		Level::Warning => "warning",
 	}
 }
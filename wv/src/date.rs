@@ -0,0 +1,406 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+#[error("invalid date '{input}': {message}")]
+pub struct DateError {
+	pub input: String,
+	pub message: String,
+}
+
+/// A year, year-month, or full date. Missing parts sort first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PartialDate {
+	pub year: i32,
+	pub month: Option<u8>,
+	pub day: Option<u8>,
+}
+
+impl PartialDate {
+	pub fn year(year: i32) -> Self {
+		Self { year, month: None, day: None }
+	}
+}
+
+impl fmt::Display for PartialDate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.year)?;
+		if let Some(month) = self.month {
+			write!(f, "-{:02}", month)?;
+		}
+		if let Some(day) = self.day {
+			write!(f, "-{:02}", day)?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for PartialDate {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let mut parts = s.split('-');
+		let year = parts
+			.next()
+			.filter(|y| y.len() == 4 && y.bytes().all(|b| b.is_ascii_digit()))
+			.ok_or("expected a four-digit year")?
+			.parse()
+			.map_err(|_| "expected a four-digit year")?;
+		let mut part = |name: &str, max: u8| -> Result<Option<u8>, String> {
+			match parts.next() {
+				None => Ok(None),
+				Some(p) if p.len() == 2 => match p.parse::<u8>() {
+					Ok(n) if (1..=max).contains(&n) => Ok(Some(n)),
+					_ => Err(format!("{} out of range", name)),
+				},
+				Some(_) => Err(format!("expected a two-digit {}", name)),
+			}
+		};
+		let month = part("month", 12)?;
+		let day = part("day", days_in_month(year, month.unwrap_or(1)))?;
+		if parts.next().is_some() {
+			return Err("too many date components".into());
+		}
+		Ok(Self { year, month, day })
+	}
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+	match month {
+		2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// A date as sources give it: exact, approximate (`c. 1720`), a range
+/// (`1723..1724`), or open-ended (`before 1750`, `after 1720`). Written as a
+/// bare integer when it is a plain year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateValue {
+	pub earliest: Option<PartialDate>,
+	pub latest: Option<PartialDate>,
+	pub circa: bool,
+}
+
+impl DateValue {
+	pub fn year(year: i32) -> Self {
+		Self::exact(PartialDate::year(year))
+	}
+
+	pub fn exact(date: PartialDate) -> Self {
+		Self {
+			earliest: Some(date),
+			latest: Some(date),
+			circa: false,
+		}
+	}
+
+	/// The bound used for chronological sorting: the earliest possible date,
+	/// or the latest for `before` dates.
+	pub fn sort_key(&self) -> PartialDate {
+		self.earliest.or(self.latest).expect("date has at least one bound")
+	}
+
+	pub fn earliest_year(&self) -> Option<i32> {
+		self.earliest.map(|d| d.year)
+	}
+
+	pub fn latest_year(&self) -> Option<i32> {
+		self.latest.map(|d| d.year)
+	}
+
+	pub fn is_range(&self) -> bool {
+		self.earliest != self.latest
+	}
+
+	/// False when a range ends before it starts.
+	pub fn is_ordered(&self) -> bool {
+		match (self.earliest, self.latest) {
+			(Some(start), Some(end)) => {
+				// Compare only at the precision both ends share, so that
+				// "1723-05..1723" reads as "May to some time in 1723".
+				let month = start.month.zip(end.month);
+				let day = start.day.zip(end.day);
+				(start.year, month.map(|m| m.0), day.map(|d| d.0))
+					<= (end.year, month.map(|m| m.1), day.map(|d| d.1))
+			}
+			_ => true,
+		}
+	}
+
+	/// Whether the date could fall within the given years (inclusive).
+	pub fn overlaps_years(&self, from: Option<i32>, to: Option<i32>) -> bool {
+		let starts_in_time = match (to, self.earliest_year()) {
+			(Some(to), Some(earliest)) => earliest <= to,
+			_ => true,
+		};
+		let ends_in_time = match (from, self.latest_year()) {
+			(Some(from), Some(latest)) => latest >= from,
+			_ => true,
+		};
+		starts_in_time && ends_in_time
+	}
+
+	fn plain_year(&self) -> Option<i32> {
+		match (self.earliest, self.latest) {
+			(Some(start), Some(end))
+				if start == end && !self.circa && start.month.is_none() =>
+			{
+				Some(start.year)
+			}
+			_ => None,
+		}
+	}
+}
+
+impl PartialOrd for DateValue {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for DateValue {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.sort_key()
+			.cmp(&other.sort_key())
+			.then_with(|| self.earliest.cmp(&other.earliest))
+			.then_with(|| self.latest.cmp(&other.latest))
+			.then_with(|| self.circa.cmp(&other.circa))
+	}
+}
+
+impl fmt::Display for DateValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.circa {
+			write!(f, "c. ")?;
+		}
+		match (self.earliest, self.latest) {
+			(Some(start), Some(end)) if start == end => write!(f, "{}", start),
+			(Some(start), Some(end)) => write!(f, "{}–{}", start, end),
+			(None, Some(end)) => write!(f, "before {}", end),
+			(Some(start), None) => write!(f, "after {}", start),
+			(None, None) => Ok(()),
+		}
+	}
+}
+
+impl FromStr for DateValue {
+	type Err = DateError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let error = |message: String| DateError {
+			input: s.to_string(),
+			message,
+		};
+		let (circa, rest) = match s.trim().strip_prefix("c. ") {
+			Some(rest) => (true, rest.trim()),
+			None => (false, s.trim()),
+		};
+
+		let (earliest, latest) = if let Some(end) = rest.strip_prefix("before ") {
+			(None, Some(end.parse().map_err(error)?))
+		} else if let Some(start) = rest.strip_prefix("after ") {
+			(Some(start.parse().map_err(error)?), None)
+		} else if let Some((start, end)) = rest.split_once("..") {
+			(
+				Some(start.parse().map_err(error)?),
+				Some(end.parse().map_err(error)?),
+			)
+		} else if let Some((start, end)) = rest.split_once('–') {
+			let start: PartialDate = start.trim().parse().map_err(error)?;
+			(Some(start), Some(parse_range_end(start, end.trim()).map_err(error)?))
+		} else if let Some((start, end)) = hyphenated_years(rest) {
+			(Some(PartialDate::year(start)), Some(PartialDate::year(end)))
+		} else {
+			let date = rest.parse().map_err(error)?;
+			(Some(date), Some(date))
+		};
+
+		Ok(Self { earliest, latest, circa })
+	}
+}
+
+/// The end of an en-dash range, where a two-digit year is read within the
+/// start's century, or the next one: "1723–24", "1799–00".
+fn parse_range_end(start: PartialDate, end: &str) -> Result<PartialDate, String> {
+	if end.len() == 2 && end.bytes().all(|b| b.is_ascii_digit()) {
+		let short: i32 = end.parse().map_err(|_| "expected a year")?;
+		let mut year = start.year - start.year % 100 + short;
+		if year < start.year {
+			year += 100;
+		}
+		return Ok(PartialDate::year(year));
+	}
+	end.parse()
+}
+
+/// "1723-1724". A hyphen followed by two digits is a month ("1723-05"), so
+/// only full years are read as a range here.
+fn hyphenated_years(s: &str) -> Option<(i32, i32)> {
+	let (start, end) = s.split_once('-')?;
+	let year = |part: &str| {
+		(part.len() == 4 && part.bytes().all(|b| b.is_ascii_digit())).then(|| part.parse().ok()).flatten()
+	};
+	Some((year(start)?, year(end)?))
+}
+
+/// A year filter such as `1720`, `1720..1730`, `..1750` or `1720..`.
+pub fn parse_year_range(s: &str) -> Result<(Option<i32>, Option<i32>), DateError> {
+	let error = || DateError {
+		input: s.to_string(),
+		message: "expected YEAR, YEAR..YEAR, ..YEAR or YEAR..".into(),
+	};
+	let bound = |part: &str| -> Result<Option<i32>, DateError> {
+		let part = part.trim();
+		if part.is_empty() {
+			Ok(None)
+		} else {
+			part.parse().map(Some).map_err(|_| error())
+		}
+	};
+	let (from, to) = match s.split_once("..") {
+		Some((from, to)) => (bound(from)?, bound(to)?),
+		None => {
+			let year = bound(s)?.ok_or_else(error)?;
+			(Some(year), Some(year))
+		}
+	};
+	if from.zip(to).is_some_and(|(from, to)| from > to) {
+		return Err(error());
+	}
+	Ok((from, to))
+}
+
+impl Serialize for DateValue {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self.plain_year() {
+			Some(year) => serializer.serialize_i32(year),
+			None => serializer.collect_str(&SourceForm(self)),
+		}
+	}
+}
+
+/// The stored string form, which spells ranges with `..` rather than a dash.
+struct SourceForm<'a>(&'a DateValue);
+
+impl fmt::Display for SourceForm<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let date = self.0;
+		match (date.earliest, date.latest) {
+			(Some(start), Some(end)) if start != end => {
+				if date.circa {
+					write!(f, "c. ")?;
+				}
+				write!(f, "{}..{}", start, end)
+			}
+			_ => write!(f, "{}", date),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for DateValue {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct DateVisitor;
+
+		impl Visitor<'_> for DateVisitor {
+			type Value = DateValue;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("a year or a date string such as \"c. 1720\" or \"1723..1724\"")
+			}
+
+			fn visit_i64<E: de::Error>(self, v: i64) -> Result<DateValue, E> {
+				i32::try_from(v)
+					.map(DateValue::year)
+					.map_err(|_| E::custom(format!("year out of range: {}", v)))
+			}
+
+			fn visit_u64<E: de::Error>(self, v: u64) -> Result<DateValue, E> {
+				i32::try_from(v)
+					.map(DateValue::year)
+					.map_err(|_| E::custom(format!("year out of range: {}", v)))
+			}
+
+			fn visit_str<E: de::Error>(self, v: &str) -> Result<DateValue, E> {
+				v.parse().map_err(E::custom)
+			}
+		}
+
+		deserializer.deserialize_any(DateVisitor)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn date(s: &str) -> DateValue {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn parses_and_displays_forms() {
+		assert_eq!(date("1720").to_string(), "1720");
+		assert_eq!(date("c. 1720").to_string(), "c. 1720");
+		assert_eq!(date("1723..1724").to_string(), "1723–1724");
+		assert_eq!(date("before 1750").to_string(), "before 1750");
+		assert_eq!(date("after 1720-05").to_string(), "after 1720-05");
+		assert_eq!(date("1724-04-07").to_string(), "1724-04-07");
+	}
+
+	#[test]
+	fn parses_dashed_year_ranges() {
+		assert_eq!(date("1723–24"), date("1723..1724"));
+		assert_eq!(date("1723–1724"), date("1723..1724"));
+		assert_eq!(date("1723-1724"), date("1723..1724"));
+		assert_eq!(date("c. 1799–00"), date("c. 1799..1800"));
+		assert_eq!(date("1723-05–1724-02"), date("1723-05..1724-02"));
+		assert_eq!(date("1723-05"), DateValue::exact(PartialDate { year: 1723, month: Some(5), day: None }));
+		assert!("1723–2".parse::<DateValue>().is_err());
+		assert!("1723–x".parse::<DateValue>().is_err());
+	}
+
+	#[test]
+	fn rejects_malformed_dates() {
+		assert!("172".parse::<DateValue>().is_err());
+		assert!("1723-13".parse::<DateValue>().is_err());
+		assert!("1723-02-30".parse::<DateValue>().is_err());
+		assert!("1724-02-29".parse::<DateValue>().is_ok());
+		assert!("circa 1720".parse::<DateValue>().is_err());
+	}
+
+	#[test]
+	fn bare_integers_round_trip() {
+		let value: DateValue = serde_json::from_str("1783").unwrap();
+		assert_eq!(value, DateValue::year(1783));
+		assert_eq!(serde_json::to_string(&value).unwrap(), "1783");
+
+		let value: DateValue = serde_json::from_str(r#""c. 1723..1724""#).unwrap();
+		assert!(value.circa && value.is_range());
+		assert_eq!(serde_json::to_string(&value).unwrap(), r#""c. 1723..1724""#);
+	}
+
+	#[test]
+	fn orders_and_filters_by_bounds() {
+		let mut dates = [date("1750"), date("before 1720"), date("c. 1720"), date("1720-03")];
+		dates.sort();
+		let sorted: Vec<String> = dates.iter().map(ToString::to_string).collect();
+		assert_eq!(sorted, ["before 1720", "c. 1720", "1720-03", "1750"]);
+
+		assert!(date("1723..1724").overlaps_years(Some(1724), None));
+		assert!(!date("1723..1724").overlaps_years(Some(1725), None));
+		assert!(date("before 1750").overlaps_years(None, Some(1700)));
+		assert!(!date("1724..1723").is_ordered());
+		assert!(date("1723-05..1723").is_ordered());
+
+		assert_eq!(parse_year_range("1720").unwrap(), (Some(1720), Some(1720)));
+		assert_eq!(parse_year_range("..1750").unwrap(), (None, Some(1750)));
+		assert!(parse_year_range("1750..1720").is_err());
+	}
+}
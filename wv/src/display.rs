@@ -3,9 +3,12 @@ use std::sync::OnceLock;
 
 use serde::Deserialize;
 
-use crate::catalog::cached_regex;
+use crate::catalog::parse_number;
 use crate::config::{DisplayConfig, KeySymbols};
-use crate::types::{CatalogDefinition, Collection, Composition};
+use crate::form::FormTaxonomy;
+use crate::instrument::{format_instrumentation, InstrumentVocabulary};
+use crate::key::{Key, Mode};
+use crate::types::{CatalogDefinition, Collection, Composition, Contributor, ContributorRole};
 
 #[derive(Debug, Deserialize)]
 struct KeyLanguageProfile {
@@ -39,28 +42,29 @@ pub fn expand_key(code: &str, config: &DisplayConfig) -> String {
 		return expanded.clone();
 	}
 
-	let Some((is_minor, note, accidental, mode_suffix)) = parse_key_code(code) else {
+	let Ok(key) = code.parse::<Key>() else {
 		return code.to_string();
 	};
 	let profile = key_language_profile(&config.language);
-	let canonical_note = format!("{}{}", note, accidental);
+	let note = key.tonic.to_string();
+	let accidental = key.accidental_code();
 	let note_str = profile
 		.notes
-		.get(&canonical_note)
+		.get(&key.tonic_name())
 		.cloned()
 		.unwrap_or_else(|| match config.key_symbols {
-			KeySymbols::Unicode => format_note_unicode(&note, &accidental),
-			KeySymbols::Ascii => format_note_ascii(&note, &accidental),
+			KeySymbols::Unicode => format_note_unicode(&note, accidental),
+			KeySymbols::Ascii => format_note_ascii(&note, accidental),
 		});
 
-	if let Some(mode_suffix) = mode_suffix {
-		let Some(mode) = profile.modes.get(&mode_suffix) else {
+	if let Some(mode_suffix) = key.mode.suffix() {
+		let Some(mode) = profile.modes.get(mode_suffix) else {
 			return code.to_string();
 		};
 		return apply_key_template(&profile.mode, &note_str, Some(mode));
 	}
 
-	let template = if is_minor { &profile.minor } else { &profile.major };
+	let template = if key.mode == Mode::Minor { &profile.minor } else { &profile.major };
 	apply_key_template(template, &note_str, None)
 }
 
@@ -74,42 +78,12 @@ fn apply_key_template(template: &str, note: &str, mode: Option<&str>) -> String
 	result
 }
 
-fn parse_key_code(code: &str) -> Option<(bool, String, String, Option<String>)> {
-	let code = code.trim();
-	let (main, mode) = match code.split_once('.') {
-		Some((main, mode)) if !mode.is_empty() && !mode.contains('.') => {
-			(main, Some(mode.to_lowercase()))
-		}
-		Some(_) => return None,
-		None => (code, None),
-	};
-
-	let mut chars = main.chars();
-	let first = chars.next()?;
-	if !matches!(first.to_ascii_uppercase(), 'A'..='G') {
-		return None;
-	}
-	let accidental = chars.collect::<String>();
-	let accidental = match accidental.as_str() {
-		"" | "#" | "b" | "bb" | "##" | "x" => accidental,
-		"X" => "x".to_string(),
-		_ => return None,
-	};
-
-	Some((
-		first.is_ascii_lowercase(),
-		first.to_ascii_uppercase().to_string(),
-		accidental,
-		mode,
-	))
-}
-
 fn format_note_unicode(note: &str, accidental: &str) -> String {
 	let acc = match accidental {
 		"#" => "♯",
 		"b" => "♭",
 		"bb" => "𝄫",
-		"##" | "x" => "𝄪",
+		"##" => "𝄪",
 		_ => "",
 	};
 	format!("{}{}", note, acc)
@@ -120,13 +94,56 @@ fn format_note_ascii(note: &str, accidental: &str) -> String {
 		"#" => "#",
 		"b" => "b",
 		"bb" => "bb",
-		"##" | "x" => "##",
+		"##" => "##",
 		_ => "",
 	};
 	format!("{}{}", note, acc)
 }
 
-pub fn format_form(form: &str) -> String {
+/// Combined credit, e.g. "mozart, completed by sussmayr" or "haydn and
+/// pleyel". Empty when nobody is credited.
+pub fn format_credits(composer: Option<&str>, contributors: &[Contributor]) -> String {
+	let mut composers: Vec<&str> = composer.into_iter().collect();
+	let mut completers = Vec::new();
+	let mut arrangers = Vec::new();
+	for contributor in contributors {
+		let names = match contributor.role {
+			ContributorRole::Composer | ContributorRole::CoComposer => &mut composers,
+			ContributorRole::Completer => &mut completers,
+			ContributorRole::Arranger => &mut arrangers,
+		};
+		if !names.contains(&contributor.composer.as_str()) {
+			names.push(&contributor.composer);
+		}
+	}
+
+	let mut parts = Vec::new();
+	if !composers.is_empty() {
+		parts.push(join_names(&composers));
+	}
+	if !completers.is_empty() {
+		parts.push(format!("completed by {}", join_names(&completers)));
+	}
+	if !arrangers.is_empty() {
+		parts.push(format!("arranged by {}", join_names(&arrangers)));
+	}
+	parts.join(", ")
+}
+
+fn join_names(names: &[&str]) -> String {
+	match names {
+		[] => String::new(),
+		[name] => name.to_string(),
+		[rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+	}
+}
+
+/// The taxonomy's preferred label for a form (so synonyms display alike), or
+/// the form name in title case.
+pub fn format_form(form: &str, forms: Option<&FormTaxonomy>, language: &str) -> String {
+	if let Some(label) = forms.and_then(|forms| forms.label(form, language)) {
+		return label.to_string();
+	}
 	form.split_whitespace()
 		.map(|word| {
 			let mut chars = word.chars();
@@ -163,21 +180,12 @@ pub fn format_number_for_display(number: &str, defn: Option<&CatalogDefinition>)
 		None => return number.to_string(),
 	};
 
-	let pattern = match &defn.pattern {
-		Some(p) => p,
-		None => return number.to_string(),
-	};
-
 	let sort_keys = match &defn.sort_keys {
 		Some(sks) => sks,
 		None => return number.to_string(),
 	};
 
-	let Some(re) = cached_regex(pattern) else {
-		return number.to_string();
-	};
-
-	let caps = match re.captures(number) {
+	let caps = match parse_number(number, defn) {
 		Some(c) => c,
 		None => return number.to_string(),
 	};
@@ -186,7 +194,7 @@ pub fn format_number_for_display(number: &str, defn: Option<&CatalogDefinition>)
 
 	for sk in sort_keys {
 		if let Some(display) = &sk.display {
-			if let Some(m) = caps.get(sk.group) {
+			if let Some(m) = caps.get(&sk.group) {
 				transforms.push((m.start(), m.end(), display.as_str()));
 			}
 		}
@@ -249,6 +257,8 @@ pub struct ExpansionContext<'a> {
 	pub collection: Option<&'a Collection>,
 	pub position_in_collection: Option<usize>,
 	pub config: &'a DisplayConfig,
+	pub forms: Option<&'a FormTaxonomy>,
+	pub instruments: Option<&'a InstrumentVocabulary>,
 }
 
 pub fn expand_title(ctx: &ExpansionContext) -> String {
@@ -295,7 +305,7 @@ fn expand_pattern(pattern: &str, ctx: &ExpansionContext) -> String {
 	let comp = ctx.composition;
 	let config = ctx.config;
 
-	let form = format_form(&comp.form);
+	let form = format_form(&comp.form, ctx.forms, &config.language);
 	let key = comp
 		.key
 		.as_ref()
@@ -315,15 +325,66 @@ fn expand_pattern(pattern: &str, ctx: &ExpansionContext) -> String {
 	let instrumentation = comp
 		.instrumentation
 		.as_ref()
-		.map(|i| truncate_instrumentation(i, config.patterns.instrumentation_max_chars))
+		.map(|i| {
+			truncate_instrumentation(
+				&format_instrumentation(i, ctx.instruments),
+				config.patterns.instrumentation_max_chars,
+			)
+		})
+		.unwrap_or_default();
+
+	let nickname = comp
+		.nicknames
+		.as_ref()
+		.and_then(|nicknames| nicknames.first())
+		.and_then(|nickname| localized(&nickname.name, &config.language))
 		.unwrap_or_default();
 
-	pattern
-		.replace("{form}", &form)
-		.replace("{key}", &key)
-		.replace("{num}", &num)
-		.replace("{catalog}", &catalog)
-		.replace("{instrumentation}", &instrumentation)
+	let values = [
+		("{form}", form),
+		("{key}", key),
+		("{num}", num),
+		("{catalog}", catalog),
+		("{instrumentation}", instrumentation),
+		("{nickname}", nickname),
+	];
+
+	// A `[...]` segment is dropped unless every placeholder inside it has a
+	// value, e.g. "{form} in {key}[ ('{nickname}')]".
+	let mut result = String::new();
+	let mut rest = pattern;
+	while let Some(open) = rest.find('[') {
+		let Some(close) = rest[open..].find(']').map(|i| open + i) else {
+			break;
+		};
+		result.push_str(&fill_placeholders(&rest[..open], &values));
+		let segment = &rest[open + 1..close];
+		let complete = values
+			.iter()
+			.all(|(placeholder, value)| !segment.contains(placeholder) || !value.is_empty());
+		if complete {
+			result.push_str(&fill_placeholders(segment, &values));
+		}
+		rest = &rest[close + 1..];
+	}
+	result.push_str(&fill_placeholders(rest, &values));
+	result
+}
+
+fn fill_placeholders(text: &str, values: &[(&str, String)]) -> String {
+	values
+		.iter()
+		.fold(text.to_string(), |text, (placeholder, value)| text.replace(placeholder, value))
+}
+
+/// The entry for `language`, falling back to English and then to the first
+/// language alphabetically.
+fn localized(names: &HashMap<String, String>, language: &str) -> Option<String> {
+	names
+		.get(language)
+		.or_else(|| names.get("en"))
+		.or_else(|| names.iter().min_by_key(|(lang, _)| *lang).map(|(_, name)| name))
+		.cloned()
 }
 
 #[cfg(test)]
@@ -335,6 +396,7 @@ mod tests {
 		let comp = Composition {
 			id: "12345678".into(),
 			title: None,
+			nicknames: None,
 			form: "variations".into(),
 			key: None,
 			instrumentation: None,
@@ -342,6 +404,13 @@ mod tests {
 			attribution: Vec::new(),
 			movements: None,
 			sections: None,
+			relations: None,
+			text: None,
+			chorale: None,
+			incipit: None,
+			duration: None,
+			occasion: None,
+			people: None,
 			xref: None,
 		};
 		let config = DisplayConfig::default();
@@ -350,11 +419,70 @@ mod tests {
 			collection: None,
 			position_in_collection: None,
 			config: &config,
+			forms: None,
+			instruments: None,
 		};
 
 		assert_eq!(expand_title(&ctx), "Variations");
 	}
 
+	#[test]
+	fn expand_title_formats_structured_instrumentation() {
+		let comp: Composition = serde_json::from_str(r#"{
+			"id": "12345678",
+			"form": "sonata",
+			"instrumentation": [
+				{"instrument": "violin", "alternatives": ["flute"]},
+				{"instrument": "harpsichord", "role": "obbligato"}
+			],
+			"attribution": [{"composer": "bach"}]
+		}"#).unwrap();
+		let mut config = DisplayConfig::default();
+		config.patterns.generic_no_key = "{form} for {instrumentation}".into();
+		let ctx = ExpansionContext {
+			composition: &comp,
+			collection: None,
+			position_in_collection: None,
+			config: &config,
+			forms: None,
+			instruments: None,
+		};
+
+		assert_eq!(expand_title(&ctx), "Sonata for violin or flute, harpsichord obbligato");
+	}
+
+	#[test]
+	fn expand_title_appends_nickname_through_optional_segment() {
+		let mut comp: Composition = serde_json::from_str(r#"{
+			"id": "12345678",
+			"form": "sonata",
+			"key": "c#",
+			"nicknames": [{"name": {"en": "Moonlight", "de": "Mondschein"}, "provenance": "Ludwig Rellstab"}],
+			"attribution": [{"composer": "beethoven"}]
+		}"#).unwrap();
+		let mut config = DisplayConfig::default();
+		config.patterns.generic = "{form} in {key}[ ('{nickname}')]".into();
+
+		let title = |comp: &Composition, config: &DisplayConfig| {
+			expand_title(&ExpansionContext {
+				composition: comp,
+				collection: None,
+				position_in_collection: None,
+				config,
+				forms: None,
+				instruments: None,
+			})
+		};
+		assert_eq!(title(&comp, &config), "Sonata in c♯ minor ('Moonlight')");
+
+		config.language = "de".into();
+		assert!(title(&comp, &config).ends_with("('Mondschein')"));
+
+		comp.nicknames = None;
+		config.language = "en".into();
+		assert_eq!(title(&comp, &config), "Sonata in c♯ minor");
+	}
+
 	#[test]
 	fn truncate_instrumentation_counts_characters_not_bytes() {
 		let inst = "violoncello e cembalo obbligato";
@@ -435,9 +563,33 @@ mod tests {
 
 	#[test]
 	fn test_format_form() {
-		assert_eq!(format_form("sonata"), "Sonata");
-		assert_eq!(format_form("character piece"), "Character Piece");
-		assert_eq!(format_form("FUGUE"), "Fugue");
+		assert_eq!(format_form("sonata", None, "en"), "Sonata");
+		assert_eq!(format_form("character piece", None, "en"), "Character Piece");
+		assert_eq!(format_form("FUGUE", None, "en"), "Fugue");
+
+		let forms: FormTaxonomy = serde_json::from_str(r#"{
+			"sacred cantata": {"synonyms": ["church cantata"], "labels": {"en": "Sacred Cantata", "de": "Kirchenkantate"}}
+		}"#).unwrap();
+		assert_eq!(format_form("church cantata", Some(&forms), "en"), "Sacred Cantata");
+		assert_eq!(format_form("church cantata", Some(&forms), "de"), "Kirchenkantate");
+		assert_eq!(format_form("sonata", Some(&forms), "en"), "Sonata");
+	}
+
+	#[test]
+	fn test_format_credits() {
+		let contributors: Vec<Contributor> = serde_json::from_str(r#"[
+			{"composer": "sussmayr", "role": "completer"},
+			{"composer": "eybler", "role": "completer"},
+			{"composer": "mozart", "role": "composer"},
+			{"composer": "czerny", "role": "arranger"}
+		]"#).unwrap();
+		assert_eq!(format_credits(Some("mozart"), &contributors[..2]), "mozart, completed by sussmayr and eybler");
+		assert_eq!(
+			format_credits(Some("mozart"), &contributors),
+			"mozart, completed by sussmayr and eybler, arranged by czerny"
+		);
+		assert_eq!(format_credits(None, &contributors[3..]), "arranged by czerny");
+		assert_eq!(format_credits(None, &[]), "");
 	}
 
 	#[test]
@@ -480,16 +632,17 @@ mod tests {
 
 	#[test]
 	fn test_format_number_for_display() {
-		use crate::types::{CatalogDefinition, SortKey};
+		use crate::types::{CatalogDefinition, CatalogGroup, SortKey};
 
 		let hob_defn = CatalogDefinition {
 			name: "Hoboken".into(),
 			description: None,
 			canonical_format: Some("Hob. {number}".into()),
 			pattern: Some(r"^([ivxlcdm]+):(\d+)$".into()),
+			patterns: None,
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "roman".into(), display: Some("upper".into()), none_last: None },
-				SortKey { group: 2, sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "roman".into(), display: Some("upper".into()), none_last: None },
+				SortKey { group: CatalogGroup::Index(2), sort_type: "int".into(), display: None, none_last: None },
 			]),
 			group_by: None,
 			aliases: None,
@@ -504,19 +657,20 @@ mod tests {
 
 	#[test]
 	fn test_format_number_bwv_anhang() {
-		use crate::types::{CatalogDefinition, SortKey};
+		use crate::types::{CatalogDefinition, CatalogGroup, SortKey};
 
 		let bwv_defn = CatalogDefinition {
 			name: "BWV".into(),
 			description: None,
 			canonical_format: Some("BWV {number}".into()),
 			pattern: Some(r"^(anh\.|app\.)?(\s*)([ivxlcdm]+|[a-d])?(\s*)(\d+)(?:\.(\d+))?([a-z]|r)?$".into()),
+			patterns: None,
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "str".into(), display: Some("title".into()), none_last: None },
-				SortKey { group: 3, sort_type: "roman".into(), display: Some("upper".into()), none_last: None },
-				SortKey { group: 5, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 6, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 7, sort_type: "str".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "str".into(), display: Some("title".into()), none_last: None },
+				SortKey { group: CatalogGroup::Index(3), sort_type: "roman".into(), display: Some("upper".into()), none_last: None },
+				SortKey { group: CatalogGroup::Index(5), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(6), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(7), sort_type: "str".into(), display: None, none_last: None },
 			]),
 			group_by: None,
 			aliases: None,
@@ -532,16 +686,17 @@ mod tests {
 
 	#[test]
 	fn test_format_catalog_hoboken() {
-		use crate::types::{CatalogDefinition, SortKey};
+		use crate::types::{CatalogDefinition, CatalogGroup, SortKey};
 
 		let hob_defn = CatalogDefinition {
 			name: "Hoboken".into(),
 			description: None,
 			canonical_format: Some("Hob. {number}".into()),
 			pattern: Some(r"^([ivxlcdm]+):(\d+)$".into()),
+			patterns: None,
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "roman".into(), display: Some("upper".into()), none_last: None },
-				SortKey { group: 2, sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "roman".into(), display: Some("upper".into()), none_last: None },
+				SortKey { group: CatalogGroup::Index(2), sort_type: "int".into(), display: None, none_last: None },
 			]),
 			group_by: None,
 			aliases: None,
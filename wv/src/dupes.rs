@@ -0,0 +1,370 @@
+//! Spotting records that describe the same work under different IDs.
+//!
+//! Pairs are scored from 0 to 100 on what they share: composer, form, key,
+//! catalog numbers in any scheme or edition, title words and movement titles,
+//! the last weighing more the more movements are shared.
+//! Different keys or different current numbers in the same catalog count
+//! against a match. Works linked by `relations` are never reported, since an
+//! arrangement is expected to look like its original.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::catalog::normalize_catalog_number;
+use crate::form::FormTaxonomy;
+use crate::index::normalize_words;
+use crate::key::Key;
+use crate::merge::current_composer;
+use crate::movement::walk_movements;
+use crate::types::Composition;
+
+/// Score at which a pair is reported by default.
+pub const DEFAULT_THRESHOLD: u32 = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateCandidate {
+	pub first: String,
+	pub second: String,
+	pub score: u32,
+	/// What the score is made of, e.g. "shared catalog number bwv 565".
+	pub reasons: Vec<String>,
+}
+
+/// What a composition is compared on, worked out once per record.
+struct Features<'a> {
+	id: &'a str,
+	composer: Option<&'a str>,
+	composers: HashSet<&'a str>,
+	form: String,
+	key: Option<Key>,
+	/// Every `(composer, scheme, number)` the work has ever had.
+	catalog: HashSet<(String, String, String)>,
+	/// The current number per `(composer, scheme)`.
+	current: HashMap<(String, String), String>,
+	/// Title words by language.
+	titles: HashMap<&'a str, HashSet<String>>,
+	movement_titles: HashSet<String>,
+	related: HashSet<&'a str>,
+}
+
+impl<'a> Features<'a> {
+	fn of(comp: &'a Composition, forms: Option<&FormTaxonomy>) -> Self {
+		let composer = current_composer(&comp.attribution);
+		let mut composers = HashSet::new();
+		let mut catalog = HashSet::new();
+		let mut current = HashMap::new();
+		for entry in &comp.attribution {
+			composers.extend(entry.principal());
+			composers.extend(entry.contributors.iter().flatten().map(|c| c.composer.as_str()));
+			let Some(principal) = entry.principal() else {
+				continue;
+			};
+			for cat in entry.catalog.iter().flatten() {
+				let number = normalize_catalog_number(&cat.number);
+				catalog.insert((principal.to_string(), cat.scheme.clone(), number.clone()));
+				current.entry((principal.to_string(), cat.scheme.clone())).or_insert(number);
+			}
+		}
+
+		let visits = walk_movements(comp);
+		if let Some(composer) = composer {
+			for visit in &visits {
+				for cat in visit.node.catalog() {
+					let number = normalize_catalog_number(&cat.number);
+					catalog.insert((composer.to_string(), cat.scheme.clone(), number));
+				}
+			}
+		}
+
+		let form = forms
+			.and_then(|forms| forms.resolve(&comp.form))
+			.map_or_else(|| comp.form.trim().to_lowercase(), str::to_string);
+
+		Self {
+			id: &comp.id,
+			composer,
+			composers,
+			form,
+			key: comp.key.as_deref().and_then(|key| key.parse().ok()),
+			catalog,
+			current,
+			titles: comp
+				.title
+				.iter()
+				.flatten()
+				.map(|(language, title)| (language.as_str(), words(title)))
+				.collect(),
+			movement_titles: visits
+				.iter()
+				.filter_map(|visit| visit.node.title())
+				.map(normalize_words)
+				.filter(|title| !title.is_empty())
+				.collect(),
+			related: comp
+				.relations
+				.iter()
+				.flatten()
+				.map(|relation| relation.target.as_str())
+				.collect(),
+		}
+	}
+
+	fn score(&self, other: &Features) -> (u32, Vec<String>) {
+		let mut score: i32 = 0;
+		let mut reasons = Vec::new();
+
+		if self.composer.is_some() && self.composer == other.composer {
+			score += 20;
+			reasons.push("same composer".to_string());
+		} else if !self.composers.is_disjoint(&other.composers) {
+			score += 10;
+			reasons.push("shared composer in attribution".to_string());
+		}
+
+		if !self.form.is_empty() && self.form == other.form {
+			score += 10;
+			reasons.push("same form".to_string());
+		}
+
+		if let (Some(key), Some(other_key)) = (self.key, other.key) {
+			if key.enharmonic(&other_key) {
+				score += 10;
+				reasons.push("same key".to_string());
+			} else {
+				score -= 20;
+				reasons.push("different keys".to_string());
+			}
+		}
+
+		let shared: BTreeSet<_> = self.catalog.intersection(&other.catalog).collect();
+		if let Some((_, scheme, number)) = shared.first() {
+			score += 40;
+			reasons.push(format!("shared catalog number {} {}", scheme, number));
+		} else {
+			let conflict = self
+				.current
+				.iter()
+				.filter_map(|(key, number)| Some((key, number, other.current.get(key)?)))
+				.filter(|(_, number, other_number)| number != other_number)
+				.min();
+			if let Some(((_, scheme), number, other_number)) = conflict {
+				score -= 30;
+				reasons.push(format!("different {} numbers ({}, {})", scheme, number, other_number));
+			}
+		}
+
+		if let Some(similarity) = self.title_similarity(other) {
+			score += (20.0 * similarity).round() as i32;
+			if similarity >= 0.5 {
+				reasons.push(format!("titles {}% alike", (100.0 * similarity).round()));
+			}
+		}
+
+		if let Some(overlap) = jaccard(&self.movement_titles, &other.movement_titles) {
+			// A long run of identical movement titles says more than a
+			// shared "Kyrie" and "Gloria", and may be all a copy that lost
+			// its catalog number still has in common with the original.
+			let shared = self.movement_titles.intersection(&other.movement_titles).count() as i32;
+			let weight = (5 * shared).clamp(10, 30);
+			score += (weight as f64 * overlap).round() as i32;
+			if overlap >= 0.5 {
+				reasons.push(format!("{}% of movement titles shared", (100.0 * overlap).round()));
+			}
+		}
+
+		(score.clamp(0, 100) as u32, reasons)
+	}
+
+	/// Best match among the languages both titles are given in, or of all
+	/// the words if they share none.
+	fn title_similarity(&self, other: &Features) -> Option<f64> {
+		let shared = self
+			.titles
+			.iter()
+			.filter_map(|(language, words)| jaccard(words, other.titles.get(language)?))
+			.max_by(f64::total_cmp);
+		shared.or_else(|| {
+			let all = |titles: &HashMap<&str, HashSet<String>>| titles.values().flatten().cloned().collect();
+			jaccard(&all(&self.titles), &all(&other.titles))
+		})
+	}
+
+	fn is_related(&self, other: &Features) -> bool {
+		self.related.contains(other.id) || other.related.contains(self.id)
+	}
+}
+
+fn words(text: &str) -> HashSet<String> {
+	normalize_words(text).split(' ').filter(|word| !word.is_empty()).map(str::to_string).collect()
+}
+
+/// Shared share of the union; `None` when either side has nothing to compare.
+fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> Option<f64> {
+	if a.is_empty() || b.is_empty() {
+		return None;
+	}
+	let shared = a.intersection(b).count();
+	Some(shared as f64 / (a.len() + b.len() - shared) as f64)
+}
+
+/// Score one pair of records.
+pub fn score_pair(a: &Composition, b: &Composition, forms: Option<&FormTaxonomy>) -> (u32, Vec<String>) {
+	Features::of(a, forms).score(&Features::of(b, forms))
+}
+
+/// Pairs scoring at least `threshold`, highest first. Only records sharing a
+/// composer or a catalog number are compared.
+pub fn find_duplicates(
+	compositions: &[Composition],
+	forms: Option<&FormTaxonomy>,
+	threshold: u32,
+) -> Vec<DuplicateCandidate> {
+	let features: Vec<Features> = compositions.iter().map(|comp| Features::of(comp, forms)).collect();
+
+	let mut buckets: HashMap<String, Vec<usize>> = HashMap::new();
+	for (i, feature) in features.iter().enumerate() {
+		let mut keys: HashSet<String> = feature.composers.iter().map(|c| format!("composer:{}", c)).collect();
+		keys.extend(feature.catalog.iter().map(|(c, s, n)| format!("catalog:{}:{}:{}", c, s, n)));
+		for key in keys {
+			buckets.entry(key).or_default().push(i);
+		}
+	}
+
+	let mut pairs = BTreeSet::new();
+	for members in buckets.values() {
+		for (n, &i) in members.iter().enumerate() {
+			for &j in &members[n + 1..] {
+				pairs.insert((i.min(j), i.max(j)));
+			}
+		}
+	}
+
+	let mut candidates: Vec<DuplicateCandidate> = pairs
+		.into_iter()
+		.filter(|&(i, j)| features[i].id != features[j].id && !features[i].is_related(&features[j]))
+		.filter_map(|(i, j)| {
+			let (score, reasons) = features[i].score(&features[j]);
+			(score >= threshold).then(|| {
+				let (first, second) = if features[i].id <= features[j].id { (i, j) } else { (j, i) };
+				DuplicateCandidate {
+					first: features[first].id.to_string(),
+					second: features[second].id.to_string(),
+					score,
+					reasons,
+				}
+			})
+		})
+		.collect();
+	candidates.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| (&a.first, &a.second).cmp(&(&b.first, &b.second))));
+	candidates
+}
+
+/// Existing records `incoming` looks like, highest score first. A record with
+/// the same ID is the one being replaced, not a duplicate.
+pub fn find_matches<'a>(
+	incoming: &Composition,
+	existing: impl IntoIterator<Item = &'a Composition>,
+	forms: Option<&FormTaxonomy>,
+	threshold: u32,
+) -> Vec<DuplicateCandidate> {
+	let features = Features::of(incoming, forms);
+	let mut matches: Vec<DuplicateCandidate> = existing
+		.into_iter()
+		.filter(|comp| comp.id != incoming.id)
+		.filter_map(|comp| {
+			let other = Features::of(comp, forms);
+			if features.is_related(&other) {
+				return None;
+			}
+			let (score, reasons) = features.score(&other);
+			(score >= threshold).then(|| DuplicateCandidate {
+				first: incoming.id.clone(),
+				second: comp.id.clone(),
+				score,
+				reasons,
+			})
+		})
+		.collect();
+	matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.second.cmp(&b.second)));
+	matches
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn composition(json: &str) -> Composition {
+		serde_json::from_str(json).unwrap()
+	}
+
+	#[test]
+	fn same_work_under_new_id_scores_high() {
+		let a = composition(r#"{"id": "aa000001", "title": {"en": "Toccata and Fugue"}, "form": "toccata and fugue", "key": "d",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "565"}]}]}"#);
+		let b = composition(r#"{"id": "bb000002", "title": {"en": "Toccata & Fugue"}, "form": "Toccata and Fugue", "key": "d",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "565"}]}]}"#);
+
+		let (score, reasons) = score_pair(&a, &b, None);
+		assert_eq!(score, 93);
+		assert!(reasons.contains(&"shared catalog number bwv 565".to_string()), "{:?}", reasons);
+	}
+
+	#[test]
+	fn different_numbers_and_keys_count_against() {
+		let a = composition(r#"{"id": "aa000001", "title": {"en": "Prelude and Fugue"}, "form": "prelude and fugue", "key": "C",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "846"}]}]}"#);
+		let b = composition(r#"{"id": "bb000002", "title": {"en": "Prelude and Fugue"}, "form": "prelude and fugue", "key": "c",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "847"}]}]}"#);
+
+		let (score, reasons) = score_pair(&a, &b, None);
+		assert_eq!(score, 0);
+		assert!(reasons.contains(&"different keys".to_string()));
+		assert!(reasons.contains(&"different bwv numbers (846, 847)".to_string()));
+	}
+
+	#[test]
+	fn finds_pairs_above_threshold_but_not_related_works() {
+		let compositions = vec![
+			composition(r#"{"id": "aa000001", "title": {"en": "Mass in B minor"}, "form": "mass", "key": "b",
+				"attribution": [{"composer": "bach"}], "movements": [{"title": "Kyrie"}, {"title": "Gloria"}]}"#),
+			composition(r#"{"id": "bb000002", "title": {"de": "Messe in h-Moll", "en": "Mass in B minor"}, "form": "mass", "key": "b",
+				"attribution": [{"composer": "bach"}], "movements": [{"title": "Kyrie"}, {"title": "Gloria"}]}"#),
+			composition(r#"{"id": "cc000003", "title": {"en": "Mass in B minor"}, "form": "mass", "key": "b",
+				"attribution": [{"composer": "bach"}], "relations": [{"type": "version-of", "target": "aa000001"}]}"#),
+			composition(r#"{"id": "dd000004", "title": {"en": "Mass in B minor"}, "form": "mass", "key": "b",
+				"attribution": [{"composer": "mozart"}]}"#),
+		];
+
+		let candidates = find_duplicates(&compositions, None, DEFAULT_THRESHOLD);
+		let pairs: Vec<(&str, &str)> = candidates.iter().map(|c| (c.first.as_str(), c.second.as_str())).collect();
+		assert_eq!(pairs, [("aa000001", "bb000002"), ("bb000002", "cc000003")]);
+		assert!(candidates[0].score > candidates[1].score);
+
+		let matches = find_matches(&compositions[1], &compositions, None, DEFAULT_THRESHOLD);
+		assert_eq!(matches.iter().map(|m| m.second.as_str()).collect::<Vec<_>>(), ["aa000001", "cc000003"]);
+	}
+
+	#[test]
+	fn copy_without_catalog_number_is_found_by_its_movements() {
+		let original = r#"{"id": "aa000001", "title": {"de": "Herr Jesu Christ, du höchstes Gut"}, "form": "chorale cantata",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "113"}]}],
+			"movements": [
+				{"title": "Herr Jesu Christ, du höchstes Gut"},
+				{"title": "Erbarm dich mein in solcher Last"},
+				{"title": "Fürwahr, wenn mir das kömmet ein"},
+				{"title": "Jedoch dein heilsam Wort, das macht"},
+				{"title": "Jesus nimmt die Sünder an"},
+				{"title": "Der Heiland nimmt die Sünder an"},
+				{"title": "Ach Herr, mein Gott, vergib mirs doch"},
+				{"title": "Stärk mich mit deinem Freudengeist"},
+				{"title": "Wohl dem, der sich mit Glauben kann"}
+			]}"#;
+		let copy = original
+			.replace("aa000001", "bb000002")
+			.replace(r#", "catalog": [{"scheme": "bwv", "number": "113"}]"#, "")
+			.replace("höchstes", "hoechstes");
+
+		let (score, reasons) = score_pair(&composition(original), &composition(&copy), None);
+		assert!(score >= DEFAULT_THRESHOLD, "{} {:?}", score, reasons);
+		assert!(reasons.contains(&"80% of movement titles shared".to_string()), "{:?}", reasons);
+	}
+}
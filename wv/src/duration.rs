@@ -0,0 +1,324 @@
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use crate::movement::MovementNode;
+use crate::types::{Composition, Movement, Section};
+
+#[derive(Debug, Error, PartialEq)]
+#[error("invalid duration '{input}': {message}")]
+pub struct DurationError {
+	pub input: String,
+	pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Notation {
+	Seconds,
+	Iso,
+}
+
+/// A performance duration in seconds, possibly a range. Stored either as a
+/// bare number of seconds or as ISO 8601 (`PT25M`, `PT20M..PT25M`), and
+/// written back the way it was read.
+#[derive(Debug, Clone, Copy)]
+pub struct DurationValue {
+	pub min: u32,
+	pub max: u32,
+	notation: Notation,
+}
+
+impl DurationValue {
+	pub fn seconds(seconds: u32) -> Self {
+		Self { min: seconds, max: seconds, notation: Notation::Seconds }
+	}
+
+	pub fn range(min: u32, max: u32) -> Self {
+		Self { min, max, notation: Notation::Iso }
+	}
+
+	pub fn is_range(&self) -> bool {
+		self.min != self.max
+	}
+
+	/// A command-line limit: seconds, ISO 8601, or `m:ss` / `h:mm:ss`.
+	pub fn parse_limit(s: &str) -> Result<u32, DurationError> {
+		let s = s.trim();
+		if let Ok(seconds) = s.parse() {
+			return Ok(seconds);
+		}
+		if s.contains(':') {
+			let parts: Option<Vec<u32>> = s.split(':').map(|p| p.parse().ok()).collect();
+			if let Some(parts) = parts.filter(|p| p.len() <= 3) {
+				return parts
+					.iter()
+					.try_fold(0u32, |total, &part| total.checked_mul(60)?.checked_add(part))
+					.ok_or_else(|| DurationError {
+						input: s.to_string(),
+						message: "duration out of range".into(),
+					});
+			}
+		}
+		parse_iso(s).map_err(|message| DurationError {
+			input: s.to_string(),
+			message: format!("{}; expected seconds, m:ss, or ISO 8601 such as PT20M", message),
+		})
+	}
+}
+
+impl PartialEq for DurationValue {
+	fn eq(&self, other: &Self) -> bool {
+		(self.min, self.max) == (other.min, other.max)
+	}
+}
+
+impl Eq for DurationValue {}
+
+impl Add for DurationValue {
+	type Output = Self;
+
+	fn add(self, other: Self) -> Self {
+		let notation = if self.notation == other.notation {
+			self.notation
+		} else {
+			Notation::Iso
+		};
+		Self {
+			min: self.min + other.min,
+			max: self.max + other.max,
+			notation,
+		}
+	}
+}
+
+fn format_clock(seconds: u32) -> String {
+	let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+	if hours > 0 {
+		format!("{}:{:02}:{:02}", hours, minutes, seconds)
+	} else {
+		format!("{}:{:02}", minutes, seconds)
+	}
+}
+
+fn format_iso(seconds: u32) -> String {
+	let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+	let mut text = "PT".to_string();
+	if hours > 0 {
+		text.push_str(&format!("{}H", hours));
+	}
+	if minutes > 0 {
+		text.push_str(&format!("{}M", minutes));
+	}
+	if seconds > 0 || (hours == 0 && minutes == 0) {
+		text.push_str(&format!("{}S", seconds));
+	}
+	text
+}
+
+/// The time part of an ISO 8601 duration: `PT1H5M30S` and its subsets.
+fn parse_iso(s: &str) -> Result<u32, String> {
+	let rest = s.strip_prefix("PT").ok_or("expected an ISO 8601 duration starting with PT")?;
+	if rest.is_empty() {
+		return Err("duration has no components".into());
+	}
+	let mut total: u32 = 0;
+	let mut number = String::new();
+	let mut last_unit = 0;
+	for c in rest.chars() {
+		if c.is_ascii_digit() {
+			number.push(c);
+			continue;
+		}
+		let (rank, factor) = match c {
+			'H' => (1, 3600),
+			'M' => (2, 60),
+			'S' => (3, 1),
+			_ => return Err(format!("unexpected '{}'", c)),
+		};
+		if number.is_empty() || rank <= last_unit {
+			return Err("components must be numbers in H, M, S order".into());
+		}
+		let value: u32 = number.parse().map_err(|_| "component out of range")?;
+		total = value
+			.checked_mul(factor)
+			.and_then(|seconds| total.checked_add(seconds))
+			.ok_or("duration out of range")?;
+		number.clear();
+		last_unit = rank;
+	}
+	if !number.is_empty() {
+		return Err("trailing number without a unit".into());
+	}
+	Ok(total)
+}
+
+impl fmt::Display for DurationValue {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.is_range() {
+			write!(f, "{}–{}", format_clock(self.min), format_clock(self.max))
+		} else {
+			write!(f, "{}", format_clock(self.min))
+		}
+	}
+}
+
+impl FromStr for DurationValue {
+	type Err = DurationError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let error = |message: String| DurationError {
+			input: s.to_string(),
+			message,
+		};
+		let (min, max) = match s.trim().split_once("..") {
+			Some((min, max)) => (parse_iso(min).map_err(error)?, parse_iso(max).map_err(error)?),
+			None => {
+				let seconds = parse_iso(s.trim()).map_err(error)?;
+				(seconds, seconds)
+			}
+		};
+		if min > max {
+			return Err(error("range ends before it starts".into()));
+		}
+		Ok(Self::range(min, max))
+	}
+}
+
+impl Serialize for DurationValue {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		match self.notation {
+			Notation::Seconds if !self.is_range() => serializer.serialize_u32(self.min),
+			_ if self.is_range() => {
+				serializer.collect_str(&format_args!("{}..{}", format_iso(self.min), format_iso(self.max)))
+			}
+			_ => serializer.serialize_str(&format_iso(self.min)),
+		}
+	}
+}
+
+impl<'de> Deserialize<'de> for DurationValue {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		struct DurationVisitor;
+
+		impl Visitor<'_> for DurationVisitor {
+			type Value = DurationValue;
+
+			fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				f.write_str("seconds or an ISO 8601 duration such as \"PT25M\" or \"PT20M..PT25M\"")
+			}
+
+			fn visit_u64<E: de::Error>(self, v: u64) -> Result<DurationValue, E> {
+				u32::try_from(v)
+					.map(DurationValue::seconds)
+					.map_err(|_| E::custom(format!("duration out of range: {}", v)))
+			}
+
+			fn visit_i64<E: de::Error>(self, v: i64) -> Result<DurationValue, E> {
+				u32::try_from(v)
+					.map(DurationValue::seconds)
+					.map_err(|_| E::custom(format!("duration out of range: {}", v)))
+			}
+
+			fn visit_str<E: de::Error>(self, v: &str) -> Result<DurationValue, E> {
+				v.parse().map_err(E::custom)
+			}
+		}
+
+		deserializer.deserialize_any(DurationVisitor)
+	}
+}
+
+/// Sum of the given movements and sections, or `None` unless every one of
+/// them has a duration of its own or through its children.
+fn sum_children(movements: Option<&[Movement]>, sections: Option<&[Section]>) -> Option<DurationValue> {
+	let nodes = movements
+		.unwrap_or_default()
+		.iter()
+		.map(MovementNode::Movement)
+		.chain(sections.unwrap_or_default().iter().map(MovementNode::Section));
+	let mut total: Option<DurationValue> = None;
+	for node in nodes {
+		let duration = node_duration(node)?;
+		total = Some(match total {
+			Some(total) => total + duration,
+			None => duration,
+		});
+	}
+	total
+}
+
+/// A movement's own duration, or the total of its subsections.
+pub fn node_duration(node: MovementNode) -> Option<DurationValue> {
+	node.duration().or_else(|| {
+		let (movements, sections) = node.children();
+		sum_children(movements, sections)
+	})
+}
+
+/// The work's duration, rolled up from its movements when not given.
+pub fn work_duration(comp: &Composition) -> Option<DurationValue> {
+	comp.duration
+		.or_else(|| sum_children(comp.movements.as_deref(), comp.sections.as_deref()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn duration(s: &str) -> DurationValue {
+		s.parse().unwrap()
+	}
+
+	#[test]
+	fn parses_iso_and_ranges() {
+		assert_eq!(duration("PT25M"), DurationValue::seconds(1500));
+		assert_eq!(duration("PT1H5M30S"), DurationValue::seconds(3930));
+		assert_eq!(duration("PT20M..PT25M"), DurationValue::range(1200, 1500));
+		assert!("PT".parse::<DurationValue>().is_err());
+		assert!("PT5S3M".parse::<DurationValue>().is_err());
+		assert!("PT25M..PT20M".parse::<DurationValue>().is_err());
+		assert!("25 minutes".parse::<DurationValue>().is_err());
+	}
+
+	#[test]
+	fn round_trips_in_original_notation() {
+		for json in ["1500", r#""PT25M""#, r#""PT20M..PT1H""#] {
+			let value: DurationValue = serde_json::from_str(json).unwrap();
+			assert_eq!(serde_json::to_string(&value).unwrap(), json);
+		}
+	}
+
+	#[test]
+	fn displays_as_clock_time() {
+		assert_eq!(DurationValue::seconds(330).to_string(), "5:30");
+		assert_eq!(DurationValue::seconds(3930).to_string(), "1:05:30");
+		assert_eq!(DurationValue::range(1200, 1500).to_string(), "20:00–25:00");
+		assert_eq!(DurationValue::parse_limit("20:00").unwrap(), 1200);
+		assert_eq!(DurationValue::parse_limit("PT20M").unwrap(), 1200);
+		assert_eq!(DurationValue::parse_limit("90").unwrap(), 90);
+		assert!(DurationValue::parse_limit("4294967295:00").is_err());
+	}
+
+	#[test]
+	fn rolls_up_movement_durations() {
+		let comp: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "suite",
+			"attribution": [{"composer": "bach"}],
+			"movements": [
+				{"form": "allemande", "duration": 240},
+				{"form": "courante", "duration": "PT2M..PT3M"},
+				{"form": "menuets", "sections": [{"duration": 60}, {"duration": 90}]}
+			]
+		}"#).unwrap();
+		assert_eq!(work_duration(&comp), Some(DurationValue::range(510, 570)));
+
+		let mut incomplete = comp.clone();
+		incomplete.movements.as_mut().unwrap()[0].duration = None;
+		assert_eq!(work_duration(&incomplete), None);
+	}
+}
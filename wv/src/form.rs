@@ -0,0 +1,281 @@
+//! The form vocabulary: each form's parent, synonyms and display labels,
+//! read from `taxonomy/forms.json` in the data directory.
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use thiserror::Error;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormDefinition {
+	/// The broader form this one is a kind of, e.g. "cantata" for "chorale cantata".
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub parent: Option<String>,
+	/// Other spellings found in the data, shown under the preferred label.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub synonyms: Vec<String>,
+	/// Display label by language code.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty", serialize_with = "crate::types::sorted")]
+	pub labels: HashMap<String, String>,
+}
+
+/// Forms by canonical name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FormTaxonomy {
+	pub forms: BTreeMap<String, FormDefinition>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FormTaxonomyError {
+	#[error("failed to read {path}: {message}")]
+	Io { path: PathBuf, message: String },
+	#[error("invalid form taxonomy {path}: {message}")]
+	Parse { path: PathBuf, message: String },
+}
+
+/// Joiners in compound movement forms such as "recitative and chorale" or
+/// "aria e coro".
+const COMPOUND_SEPARATORS: [&str; 5] = [", ", " / ", " and ", " e ", " with "];
+
+impl FormTaxonomy {
+	/// Canonical name for a form or one of its synonyms, ignoring case.
+	pub fn resolve(&self, form: &str) -> Option<&str> {
+		let form = form.trim().to_lowercase();
+		self.forms
+			.iter()
+			.find(|(name, definition)| {
+				name.to_lowercase() == form
+					|| definition.synonyms.iter().any(|synonym| synonym.to_lowercase() == form)
+			})
+			.map(|(name, _)| name.as_str())
+	}
+
+	/// The forms a compound is made of. A form known as a whole, like
+	/// "prelude and fugue", is a single part.
+	pub fn parts<'a>(&self, form: &'a str) -> Vec<&'a str> {
+		let mut parts = Vec::new();
+		self.collect_parts(form, &mut parts);
+		parts
+	}
+
+	fn collect_parts<'a>(&self, form: &'a str, parts: &mut Vec<&'a str>) {
+		let form = form.trim();
+		if self.resolve(form).is_none() {
+			if let Some(separator) = COMPOUND_SEPARATORS.iter().find(|separator| form.contains(*separator)) {
+				for part in form.split(separator) {
+					self.collect_parts(part, parts);
+				}
+				return;
+			}
+		}
+		if !form.is_empty() {
+			parts.push(form);
+		}
+	}
+
+	/// Whether `form` is `ancestor` or one of its descendants. A compound form
+	/// matches if any of its parts does.
+	pub fn is_a(&self, form: &str, ancestor: &str) -> bool {
+		let Some(ancestor) = self.resolve(ancestor) else {
+			return false;
+		};
+		self.parts(form).into_iter().any(|part| {
+			let mut current = self.resolve(part);
+			// Bounded, so a cyclic taxonomy cannot hang a query.
+			for _ in 0..=self.forms.len() {
+				let Some(name) = current else {
+					break;
+				};
+				if name == ancestor {
+					return true;
+				}
+				current = self.forms[name].parent.as_deref().and_then(|parent| self.resolve(parent));
+			}
+			false
+		})
+	}
+
+	/// Preferred label in `language`, falling back to English.
+	pub fn label(&self, form: &str, language: &str) -> Option<&str> {
+		let definition = &self.forms[self.resolve(form)?];
+		definition
+			.labels
+			.get(language)
+			.or_else(|| definition.labels.get("en"))
+			.map(String::as_str)
+	}
+
+	/// Parts of `form` missing from the vocabulary.
+	pub fn unknown_parts<'a>(&self, form: &'a str) -> Vec<&'a str> {
+		self.parts(form)
+			.into_iter()
+			.filter(|part| self.resolve(part).is_none())
+			.collect()
+	}
+
+	/// The known name or synonym nearest to `form`, if close enough to be a
+	/// likely misspelling.
+	pub fn suggest(&self, form: &str) -> Option<&str> {
+		let form = form.trim().to_lowercase();
+		let limit = (form.chars().count() / 3).max(2);
+		self.forms
+			.iter()
+			.flat_map(|(name, definition)| std::iter::once(name).chain(&definition.synonyms))
+			.map(|candidate| (edit_distance(&form, &candidate.to_lowercase()), candidate))
+			.filter(|(distance, _)| *distance <= limit)
+			.min_by_key(|(distance, _)| *distance)
+			.map(|(_, candidate)| candidate.as_str())
+	}
+
+	/// Parents missing from the vocabulary, parent chains that loop, and
+	/// spellings claimed by more than one form.
+	pub fn check(&self) -> Vec<String> {
+		let mut problems = Vec::new();
+		let mut owners: HashMap<String, &str> = HashMap::new();
+		for (name, definition) in &self.forms {
+			for spelling in std::iter::once(name).chain(&definition.synonyms) {
+				if let Some(owner) = owners.insert(spelling.to_lowercase(), name) {
+					problems.push(format!("'{}' is listed under both '{}' and '{}'", spelling, owner, name));
+				}
+			}
+		}
+		for (name, definition) in &self.forms {
+			let Some(parent) = &definition.parent else {
+				continue;
+			};
+			if !self.forms.contains_key(parent) {
+				problems.push(format!("{}: parent '{}' is not a form", name, parent));
+				continue;
+			}
+			let mut current = Some(parent.as_str());
+			for _ in 0..self.forms.len() {
+				match current {
+					Some(ancestor) if ancestor == name => {
+						problems.push(format!("{}: parent chain loops back to itself", name));
+						break;
+					}
+					Some(ancestor) => current = self.forms.get(ancestor).and_then(|d| d.parent.as_deref()),
+					None => break,
+				}
+			}
+		}
+		problems
+	}
+}
+
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+	let b: Vec<char> = b.chars().collect();
+	let mut row: Vec<usize> = (0..=b.len()).collect();
+	for (i, ca) in a.chars().enumerate() {
+		let mut diagonal = row[0];
+		row[0] = i + 1;
+		for (j, cb) in b.iter().enumerate() {
+			let substitution = diagonal + usize::from(ca != *cb);
+			diagonal = row[j + 1];
+			row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+		}
+	}
+	row[b.len()]
+}
+
+pub fn form_taxonomy_path<P: AsRef<Path>>(data_dir: P) -> PathBuf {
+	data_dir.as_ref().join("taxonomy").join("forms.json")
+}
+
+type TaxonomyResult = Result<Option<Rc<FormTaxonomy>>, FormTaxonomyError>;
+
+thread_local! {
+	static TAXONOMY_CACHE: RefCell<HashMap<PathBuf, TaxonomyResult>> = RefCell::new(HashMap::new());
+}
+
+/// The dataset's form taxonomy, or `None` for datasets without one. Read once
+/// per process, since every displayed title consults it.
+pub fn load_form_taxonomy<P: AsRef<Path>>(data_dir: P) -> TaxonomyResult {
+	let path = form_taxonomy_path(data_dir);
+	if let Some(cached) = TAXONOMY_CACHE.with(|cache| cache.borrow().get(&path).cloned()) {
+		return cached;
+	}
+	let taxonomy = read_form_taxonomy(&path);
+	TAXONOMY_CACHE.with(|cache| {
+		cache.borrow_mut().insert(path, taxonomy.clone());
+	});
+	taxonomy
+}
+
+fn read_form_taxonomy(path: &Path) -> TaxonomyResult {
+	let content = match fs::read_to_string(path) {
+		Ok(content) => content,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+		Err(error) => {
+			return Err(FormTaxonomyError::Io { path: path.to_path_buf(), message: error.to_string() })
+		}
+	};
+	serde_json::from_str(&content)
+		.map(|taxonomy| Some(Rc::new(taxonomy)))
+		.map_err(|error| FormTaxonomyError::Parse { path: path.to_path_buf(), message: error.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn taxonomy() -> FormTaxonomy {
+		serde_json::from_str(r#"{
+			"cantata": {"labels": {"en": "Cantata", "de": "Kantate"}},
+			"sacred cantata": {"parent": "cantata", "synonyms": ["church cantata"], "labels": {"en": "Sacred Cantata"}},
+			"chorale cantata": {"parent": "sacred cantata"},
+			"sonata": {},
+			"prelude and fugue": {},
+			"recitative": {},
+			"chorale": {"synonyms": ["choral"]}
+		}"#).unwrap()
+	}
+
+	#[test]
+	fn resolves_synonyms_and_matches_descendants() {
+		let forms = taxonomy();
+		assert_eq!(forms.resolve("Church Cantata"), Some("sacred cantata"));
+		assert!(forms.is_a("chorale cantata", "cantata"));
+		assert!(forms.is_a("church cantata", "sacred cantata"));
+		assert!(!forms.is_a("cantata", "sacred cantata"));
+		assert!(forms.is_a("recitative and choral", "chorale"));
+		assert_eq!(forms.label("church cantata", "de"), Some("Sacred Cantata"));
+		assert_eq!(forms.label("cantata", "de"), Some("Kantate"));
+	}
+
+	#[test]
+	fn splits_compounds_unless_known_whole() {
+		let forms = taxonomy();
+		assert_eq!(forms.parts("prelude and fugue"), ["prelude and fugue"]);
+		assert_eq!(forms.parts("recitative e chorale, aria"), ["recitative", "chorale", "aria"]);
+		assert_eq!(forms.unknown_parts("recitative e chorale, aria"), ["aria"]);
+	}
+
+	#[test]
+	fn suggests_near_spellings_only() {
+		let forms = taxonomy();
+		assert_eq!(forms.suggest("sonnata"), Some("sonata"));
+		assert_eq!(forms.suggest("church cantatas"), Some("church cantata"));
+		assert_eq!(forms.suggest("symphony"), None);
+	}
+
+	#[test]
+	fn checks_parents_cycles_and_duplicate_spellings() {
+		let forms: FormTaxonomy = serde_json::from_str(r#"{
+			"a": {"parent": "b"},
+			"b": {"parent": "a"},
+			"c": {"parent": "d", "synonyms": ["a"]}
+		}"#).unwrap();
+		assert_eq!(forms.check(), [
+			"'a' is listed under both 'a' and 'c'",
+			"a: parent chain loops back to itself",
+			"b: parent chain loops back to itself",
+			"c: parent 'd' is not a form",
+		]);
+	}
+}
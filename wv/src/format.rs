@@ -0,0 +1,311 @@
+//! Canonical layout for data files, so that diffs show only real changes.
+//!
+//! JSON files are read into their type and written back out: fields in
+//! struct order, maps sorted by key, tab indentation, a trailing newline.
+//! Inventories keep their hand-made layout and comments; only the order of
+//! entry lines, and of entries within a line, is put into catalog order.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::ser::{PrettyFormatter, Serializer};
+use thiserror::Error;
+
+use crate::catalog::{has_pattern, parse_number, sort_numbers};
+use crate::form::FormTaxonomy;
+use crate::types::{CatalogDefinition, ChoraleMelody, Collection, Composer, Composition, Inventory, Person, Reference};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+	Composition,
+	Composer,
+	Collection,
+	Catalog,
+	Inventory,
+	Person,
+	Reference,
+	Chorale,
+	FormTaxonomy,
+}
+
+impl FileKind {
+	/// Kind from the data directory a file sits under, e.g.
+	/// `compositions/ab/cdef12.json` or `inventories/beethoven/op.toml`.
+	pub fn of_path(path: &Path) -> Option<FileKind> {
+		let extension = path.extension()?.to_str()?;
+		let parents: Vec<&str> = path
+			.parent()?
+			.components()
+			.filter_map(|component| component.as_os_str().to_str())
+			.collect();
+		let kind = parents.iter().rev().find_map(|dir| match *dir {
+			"compositions" => Some(FileKind::Composition),
+			"composers" => Some(FileKind::Composer),
+			"collections" => Some(FileKind::Collection),
+			"catalogs" => Some(FileKind::Catalog),
+			"inventories" => Some(FileKind::Inventory),
+			"people" => Some(FileKind::Person),
+			"references" => Some(FileKind::Reference),
+			"chorales" => Some(FileKind::Chorale),
+			"taxonomy" => Some(FileKind::FormTaxonomy),
+			_ => None,
+		})?;
+		let expected = if kind == FileKind::Inventory { "toml" } else { "json" };
+		(extension == expected).then_some(kind)
+	}
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum FormatError {
+	#[error("cannot parse: {0}")]
+	Parse(String),
+	#[error("formatting would change the data: {0}")]
+	Lossy(String),
+	#[error("entries line {line}: {message}")]
+	Entries { line: usize, message: String },
+}
+
+/// Canonical text for a data file of the given kind. Inventories are only
+/// sorted when every entry matches their catalog's pattern.
+pub fn format_data(kind: FileKind, content: &str, catalog: Option<&CatalogDefinition>) -> Result<String, FormatError> {
+	match kind {
+		FileKind::Composition => format_json::<Composition>(content),
+		FileKind::Composer => format_json::<Composer>(content),
+		FileKind::Collection => format_json::<Collection>(content),
+		FileKind::Catalog => format_json::<CatalogDefinition>(content),
+		FileKind::Inventory => format_inventory(content, catalog),
+		FileKind::Person => format_json::<Person>(content),
+		FileKind::Reference => format_json::<Reference>(content),
+		FileKind::Chorale => format_json::<ChoraleMelody>(content),
+		FileKind::FormTaxonomy => format_json::<FormTaxonomy>(content),
+	}
+}
+
+/// Read `content` as `T` and write it back in canonical form. Refuses
+/// rather than drop or alter anything the type does not round-trip.
+pub fn format_json<T: Serialize + DeserializeOwned>(content: &str) -> Result<String, FormatError> {
+	let original: serde_json::Value = serde_json::from_str(content).map_err(|e| FormatError::Parse(e.to_string()))?;
+	let typed: T = serde_json::from_value(original.clone()).map_err(|e| FormatError::Parse(e.to_string()))?;
+
+	let mut buffer = Vec::new();
+	let mut serializer = Serializer::with_formatter(&mut buffer, PrettyFormatter::with_indent(b"\t"));
+	typed.serialize(&mut serializer).map_err(|e| FormatError::Parse(e.to_string()))?;
+	let mut formatted = String::from_utf8(buffer).map_err(|e| FormatError::Parse(e.to_string()))?;
+	formatted.push('\n');
+
+	let reread: serde_json::Value = serde_json::from_str(&formatted).map_err(|e| FormatError::Parse(e.to_string()))?;
+	if reread != original {
+		return Err(FormatError::Lossy(first_difference(&original, &reread, "")));
+	}
+	Ok(formatted)
+}
+
+fn first_difference(before: &serde_json::Value, after: &serde_json::Value, location: &str) -> String {
+	use serde_json::Value;
+	let at = |key: &str| if location.is_empty() { key.to_string() } else { format!("{}.{}", location, key) };
+	match (before, after) {
+		(Value::Object(before), Value::Object(after)) => {
+			for (key, value) in before {
+				match after.get(key) {
+					Some(other) if other != value => return first_difference(value, other, &at(key)),
+					Some(_) => {}
+					None => return format!("{} would be dropped", at(key)),
+				}
+			}
+			match after.keys().find(|key| !before.contains_key(*key)) {
+				Some(key) => format!("{} would be added", at(key)),
+				None => format!("{} would change", location),
+			}
+		}
+		(Value::Array(before), Value::Array(after)) if before.len() == after.len() => before
+			.iter()
+			.zip(after)
+			.enumerate()
+			.find(|(_, (a, b))| a != b)
+			.map(|(i, (a, b))| first_difference(a, b, &format!("{}[{}]", location, i)))
+			.unwrap_or_else(|| format!("{} would change", location)),
+		_ => format!("{}: {} would become {}", if location.is_empty() { "value" } else { location }, before, after),
+	}
+}
+
+/// One line of an inventory's `entries` array, with the blank and comment
+/// lines above it.
+struct EntryLine {
+	above: Vec<String>,
+	text: String,
+	original: Vec<String>,
+	/// Whether the last entry already has its comma.
+	terminated: bool,
+	entries: Vec<String>,
+	comment: Option<String>,
+}
+
+/// Sort an inventory's entries into catalog order without disturbing its
+/// grouping: a line keeps its entries and trailing comment, and comment
+/// lines travel with the entry line below them.
+pub fn format_inventory(content: &str, catalog: Option<&CatalogDefinition>) -> Result<String, FormatError> {
+	let original: Inventory = toml::from_str(content).map_err(|e| FormatError::Parse(e.to_string()))?;
+	let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+	let start = lines.iter().position(|line| line.trim() == "entries = [");
+	let end = start.and_then(|start| lines[start..].iter().position(|line| line.trim() == "]").map(|i| start + i));
+	let (Some(start), Some(end)) = (start, end) else {
+		return Err(FormatError::Entries {
+			line: 0,
+			message: "expected `entries = [` and `]` on lines of their own".into(),
+		});
+	};
+
+	let mut entry_lines = Vec::new();
+	let mut above = Vec::new();
+	for (offset, line) in lines[start + 1..end].iter().enumerate() {
+		let trimmed = line.trim();
+		if trimmed.is_empty() {
+			above.push(String::new());
+		} else if trimmed.starts_with('#') {
+			above.push(format!("\t{}", trimmed));
+		} else {
+			let (entries, comment, terminated) = parse_entry_line(trimmed).map_err(|message| FormatError::Entries {
+				line: start + offset + 2,
+				message,
+			})?;
+			entry_lines.push(EntryLine {
+				above: std::mem::take(&mut above),
+				text: trimmed.to_string(),
+				original: entries.clone(),
+				terminated,
+				entries,
+				comment,
+			});
+		}
+	}
+
+	// Catalog order needs every entry to match the catalog's pattern; string
+	// order would scramble "2" and "10", so otherwise the order stays.
+	let sortable = catalog.filter(|catalog| {
+		has_pattern(catalog) && original.entries.iter().all(|entry| parse_number(entry, catalog).is_some())
+	});
+	if let Some(catalog) = sortable {
+		let mut sorted = original.entries.clone();
+		sort_numbers(&mut sorted, Some(catalog));
+		let mut rank: HashMap<&str, usize> = HashMap::new();
+		for (i, number) in sorted.iter().enumerate() {
+			rank.entry(number).or_insert(i);
+		}
+		let rank_of = |number: &String| rank.get(number.as_str()).copied().unwrap_or(usize::MAX);
+		for line in &mut entry_lines {
+			line.entries.sort_by_key(rank_of);
+		}
+		entry_lines.sort_by_key(|line| line.entries.first().map_or(usize::MAX, rank_of));
+	}
+
+	let mut output: Vec<String> = lines[..=start].iter().map(|line| line.to_string()).collect();
+	for line in entry_lines {
+		output.extend(line.above);
+		// A line already in order keeps its own spacing before the comment.
+		if line.entries == line.original && line.terminated {
+			output.push(format!("\t{}", line.text));
+			continue;
+		}
+		let quoted: Vec<String> = line.entries.iter().map(|entry| serde_json::Value::from(entry.as_str()).to_string()).collect();
+		output.push(match line.comment {
+			Some(comment) => format!("\t{}, {}", quoted.join(", "), comment),
+			None => format!("\t{},", quoted.join(", ")),
+		});
+	}
+	output.extend(above);
+	output.extend(lines[end..].iter().map(|line| line.to_string()));
+	while output.last().is_some_and(|line| line.is_empty()) {
+		output.pop();
+	}
+	let formatted = output.join("\n") + "\n";
+
+	let reread: Inventory = toml::from_str(&formatted).map_err(|e| FormatError::Parse(e.to_string()))?;
+	let mut before = original.entries;
+	let mut after = reread.entries;
+	before.sort();
+	after.sort();
+	if before != after {
+		return Err(FormatError::Lossy("entries would change".into()));
+	}
+	Ok(formatted)
+}
+
+/// `"1/1", "1/2",  # comment` into its entries, its comment, and whether
+/// the last entry is followed by a comma.
+fn parse_entry_line(line: &str) -> Result<(Vec<String>, Option<String>, bool), String> {
+	let mut entries = Vec::new();
+	let mut rest = line;
+	let mut terminated = false;
+	loop {
+		rest = rest.trim_start();
+		if rest.is_empty() {
+			return Ok((entries, None, terminated));
+		}
+		if rest.starts_with('#') {
+			return Ok((entries, Some(rest.to_string()), terminated));
+		}
+		let Some(quoted) = rest.strip_prefix('"') else {
+			return Err(format!("expected a quoted catalog number at '{}'", rest));
+		};
+		let close = quoted.find('"').ok_or("unterminated string")?;
+		let entry = &quoted[..close];
+		if entry.contains('\\') {
+			return Err(format!("escapes are not supported in '{}'", entry));
+		}
+		entries.push(entry.to_string());
+		rest = quoted[close + 1..].trim_start();
+		terminated = rest.starts_with(',');
+		rest = rest.strip_prefix(',').unwrap_or(rest);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	#[test]
+	fn kinds_follow_data_directories() {
+		let kind = |path: &str| FileKind::of_path(&PathBuf::from(path));
+		assert_eq!(kind("compositions/ab/cdef12.json"), Some(FileKind::Composition));
+		assert_eq!(kind("/data/catalogs/mozart/k.json"), Some(FileKind::Catalog));
+		assert_eq!(kind("inventories/beethoven/op.toml"), Some(FileKind::Inventory));
+		assert_eq!(kind("taxonomy/forms.json"), Some(FileKind::FormTaxonomy));
+		assert_eq!(kind("inventories/README.md"), None);
+		assert_eq!(kind("schemas/composition.schema.json"), None);
+	}
+
+	#[test]
+	fn composition_fields_follow_struct_order() {
+		let content = r#"{"attribution": [{"composer": "bach"}], "form": "sonata", "title": {"en": "Sonata", "de": "Sonate"}, "id": "ab123456"}"#;
+		assert_eq!(
+			format_json::<Composition>(content).unwrap(),
+			"{\n\t\"id\": \"ab123456\",\n\t\"title\": {\n\t\t\"de\": \"Sonate\",\n\t\t\"en\": \"Sonata\"\n\t},\n\t\"attribution\": [\n\t\t{\n\t\t\t\"composer\": \"bach\"\n\t\t}\n\t],\n\t\"form\": \"sonata\"\n}\n"
+		);
+		let formatted = format_json::<Composition>(content).unwrap();
+		assert_eq!(format_json::<Composition>(&formatted).unwrap(), formatted);
+	}
+
+	#[test]
+	fn refuses_lossy_rewrites() {
+		let content = r#"{"id": "ab123456", "form": "sonata", "attribution": [], "movements": [{"title": "Adagio", "tempo": "slow"}]}"#;
+		assert!(matches!(format_json::<Composition>(content), Err(FormatError::Lossy(_)) | Err(FormatError::Parse(_))));
+	}
+
+	#[test]
+	fn inventory_entries_sort_with_their_comments() {
+		let catalog: CatalogDefinition = serde_json::from_value(serde_json::json!({
+			"name": "Opus",
+			"pattern": "^(\\d+)(?:/(\\d+))?$",
+			"sort_keys": [{"group": 1, "type": "int"}, {"group": 2, "type": "int"}]
+		}))
+		.unwrap();
+		let content = "composer = \"beethoven\"\nscheme = \"op\"\ncomplete = false\n\n# source\n\nentries = [\n\t# Trios\n\t\"1/3\", \"1/1\",\"1/2\",\n\t\"10\", # ten\n\t\"2\",  # two\n]\n\n";
+		assert_eq!(
+			format_inventory(content, Some(&catalog)).unwrap(),
+			"composer = \"beethoven\"\nscheme = \"op\"\ncomplete = false\n\n# source\n\nentries = [\n\t# Trios\n\t\"1/1\", \"1/2\", \"1/3\",\n\t\"2\",  # two\n\t\"10\", # ten\n]\n"
+		);
+	}
+}
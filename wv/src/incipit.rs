@@ -0,0 +1,916 @@
+//! Plaine & Easie Code (PAE) incipits, as used by RISM: parsing and
+//! validation, pitch extraction for interval search, and plain text and SVG
+//! rendering.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use thiserror::Error;
+
+use crate::types::Incipit;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum PaeError {
+	#[error("invalid clef '{0}' (expected e.g. G-2, F-4, C-3)")]
+	Clef(String),
+	#[error("invalid key signature '{0}' (expected e.g. xFC or bBEA)")]
+	Key(String),
+	#[error("invalid time signature '{0}' (expected e.g. 4/4, 3/8, c, c/)")]
+	Time(String),
+	#[error("data, position {position}: {message}")]
+	Data { position: usize, message: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clef {
+	/// 'G', 'C' or 'F'.
+	pub shape: char,
+	/// Staff line the clef sits on, counted from the bottom.
+	pub line: u8,
+	pub mensural: bool,
+}
+
+impl Clef {
+	/// Diatonic number (octave * 7 + step) of the note on the clef's line.
+	fn diatonic(self) -> i32 {
+		match self.shape {
+			'G' => diatonic('G', 4),
+			'C' => diatonic('C', 4),
+			_ => diatonic('F', 3),
+		}
+	}
+}
+
+impl std::str::FromStr for Clef {
+	type Err = PaeError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let error = || PaeError::Clef(s.to_string());
+		let chars: Vec<char> = s.chars().collect();
+		let [shape, sign, line] = chars[..] else {
+			return Err(error());
+		};
+		if !"GCFgcf".contains(shape) || !"-+".contains(sign) {
+			return Err(error());
+		}
+		let line = line.to_digit(10).filter(|l| (1..=5).contains(l)).ok_or_else(error)?;
+		Ok(Self {
+			shape: shape.to_ascii_uppercase(),
+			line: line as u8,
+			mensural: sign == '+' || shape.is_ascii_lowercase(),
+		})
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeySignature {
+	/// +1 for sharps, -1 for flats, 0 when empty.
+	pub alter: i8,
+	pub steps: Vec<char>,
+}
+
+impl KeySignature {
+	fn alter_for(&self, step: char) -> i8 {
+		if self.steps.contains(&step) {
+			self.alter
+		} else {
+			0
+		}
+	}
+}
+
+impl std::str::FromStr for KeySignature {
+	type Err = PaeError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let error = || PaeError::Key(s.to_string());
+		let mut chars = s.chars();
+		let alter = match chars.next() {
+			None => return Ok(Self::default()),
+			Some('x') => 1,
+			Some('b') => -1,
+			Some('n') => 0,
+			Some(_) => return Err(error()),
+		};
+		let mut steps = Vec::new();
+		for c in chars.filter(|c| !matches!(c, '[' | ']')) {
+			if !('A'..='G').contains(&c) || steps.contains(&c) {
+				return Err(error());
+			}
+			steps.push(c);
+		}
+		if steps.is_empty() && alter != 0 {
+			return Err(error());
+		}
+		Ok(Self { alter, steps })
+	}
+}
+
+fn parse_time(s: &str) -> Result<String, PaeError> {
+	let valid_piece = |piece: &str| {
+		let rest = piece.strip_prefix('c').unwrap_or(piece);
+		let rest = match piece {
+			"c" | "c/" | "o" | "o." => return true,
+			_ => rest,
+		};
+		let mut parts = rest.split('/');
+		let numerator = parts.next().unwrap_or_default();
+		let denominator = parts.next();
+		!numerator.is_empty()
+			&& numerator.bytes().all(|b| b.is_ascii_digit())
+			&& denominator.is_none_or(|d| !d.is_empty() && d.bytes().all(|b| b.is_ascii_digit()))
+			&& parts.next().is_none()
+	};
+	if !s.is_empty() && s.split_whitespace().all(valid_piece) {
+		Ok(s.to_string())
+	} else {
+		Err(PaeError::Time(s.to_string()))
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Duration {
+	/// PAE code: 0 longa, 9 breve, 1 whole, 2 half, 4 quarter, 8 eighth,
+	/// 6 sixteenth, 3 thirty-second, 5 sixty-fourth, 7 128th.
+	pub code: char,
+	pub dots: u8,
+}
+
+impl Duration {
+	fn label(self) -> String {
+		let base = match self.code {
+			'0' => "longa",
+			'9' => "breve",
+			'6' => "16",
+			'3' => "32",
+			'5' => "64",
+			'7' => "128",
+			code => return format!("{}{}", code, ".".repeat(self.dots as usize)),
+		};
+		format!("{}{}", base, ".".repeat(self.dots as usize))
+	}
+
+	fn is_filled(self) -> bool {
+		!matches!(self.code, '0' | '9' | '1' | '2')
+	}
+
+	fn has_stem(self) -> bool {
+		!matches!(self.code, '9' | '1')
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaeNote {
+	pub step: char,
+	pub octave: i8,
+	/// Sounding alteration after key signature and earlier accidentals.
+	pub alter: i8,
+	/// Accidental written before this note, if any.
+	pub accidental: Option<i8>,
+	pub duration: Duration,
+	pub tied: bool,
+	pub grace: bool,
+	/// Sounds with the previous note as a chord.
+	pub chord: bool,
+	pub fermata: bool,
+	pub trill: bool,
+}
+
+impl PaeNote {
+	pub fn midi(&self) -> i32 {
+		let semitone = match self.step {
+			'C' => 0,
+			'D' => 2,
+			'E' => 4,
+			'F' => 5,
+			'G' => 7,
+			'A' => 9,
+			_ => 11,
+		};
+		(self.octave as i32 + 1) * 12 + semitone + self.alter as i32
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaeEvent {
+	Note(PaeNote),
+	Rest { duration: Duration, fermata: bool },
+	MeasureRest(u32),
+	Barline(String),
+	Clef(Clef),
+	Key(KeySignature),
+	Time(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedIncipit {
+	pub clef: Clef,
+	pub key: KeySignature,
+	pub time: Option<String>,
+	pub events: Vec<PaeEvent>,
+}
+
+pub fn parse_incipit(incipit: &Incipit) -> Result<ParsedIncipit, PaeError> {
+	let clef = incipit.clef.parse()?;
+	let key: KeySignature = incipit.key.as_deref().unwrap_or("").parse()?;
+	let time = incipit.time.as_deref().map(parse_time).transpose()?;
+	let events = parse_data(&incipit.data, &key)?;
+	Ok(ParsedIncipit { clef, key, time, events })
+}
+
+fn diatonic(step: char, octave: i8) -> i32 {
+	let index = "CDEFGAB".find(step).unwrap_or(0) as i32;
+	octave as i32 * 7 + index
+}
+
+struct DataParser<'a> {
+	chars: Vec<char>,
+	pos: usize,
+	key: &'a KeySignature,
+	events: Vec<PaeEvent>,
+	octave: i8,
+	rhythm: Vec<Duration>,
+	rhythm_next: usize,
+	/// Whether the last token was a duration, so the next one extends the
+	/// rhythmic pattern instead of replacing it.
+	in_rhythm: bool,
+	measure_accidentals: HashMap<(char, i8), i8>,
+	grace_next: bool,
+	grace_group: bool,
+	chord_next: bool,
+	/// Event index at each open parenthesis.
+	groups: Vec<usize>,
+}
+
+impl DataParser<'_> {
+	fn error(&self, message: impl Into<String>) -> PaeError {
+		PaeError::Data {
+			position: self.pos + 1,
+			message: message.into(),
+		}
+	}
+
+	fn peek(&self) -> Option<char> {
+		self.chars.get(self.pos).copied()
+	}
+
+	fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> String {
+		let start = self.pos;
+		while self.peek().is_some_and(&predicate) {
+			self.pos += 1;
+		}
+		self.chars[start..self.pos].iter().collect()
+	}
+
+	fn next_duration(&mut self) -> Duration {
+		self.in_rhythm = false;
+		if self.rhythm.is_empty() {
+			return Duration { code: '4', dots: 0 };
+		}
+		let duration = self.rhythm[self.rhythm_next % self.rhythm.len()];
+		self.rhythm_next += 1;
+		duration
+	}
+
+	fn last_note(&mut self) -> Option<&mut PaeNote> {
+		match self.events.last_mut() {
+			Some(PaeEvent::Note(note)) => Some(note),
+			_ => None,
+		}
+	}
+
+	fn parse(mut self) -> Result<Vec<PaeEvent>, PaeError> {
+		while let Some(c) = self.peek() {
+			match c {
+				' ' | '\t' | '\n' | '{' | '}' | '!' | 'f' | 'i' => self.pos += 1,
+				'\'' | ',' => {
+					let marks = self.take_while(|m| m == c);
+					let count = marks.chars().count() as i8;
+					self.octave = if c == '\'' { 3 + count } else { 4 - count };
+				}
+				'0'..='9' => {
+					let code = c;
+					self.pos += 1;
+					let dots = self.take_while(|d| d == '.').len() as u8;
+					if !self.in_rhythm {
+						self.rhythm.clear();
+						self.rhythm_next = 0;
+					}
+					self.rhythm.push(Duration { code, dots });
+					self.in_rhythm = true;
+				}
+				'x' | 'b' | 'n' => self.parse_note()?,
+				'A'..='G' => self.parse_note()?,
+				'-' => {
+					self.pos += 1;
+					let duration = self.next_duration();
+					self.events.push(PaeEvent::Rest { duration, fermata: false });
+				}
+				'=' => {
+					self.pos += 1;
+					let count = self.take_while(|d| d.is_ascii_digit());
+					let count = if count.is_empty() {
+						1
+					} else {
+						count.parse().map_err(|_| self.error("measure rest count out of range"))?
+					};
+					self.events.push(PaeEvent::MeasureRest(count));
+				}
+				'/' | ':' => {
+					let bar = self.take_while(|b| b == '/' || b == ':');
+					if !matches!(bar.as_str(), "/" | "//" | "://" | "//:" | "://:") {
+						return Err(self.error(format!("unknown barline '{}'", bar)));
+					}
+					self.measure_accidentals.clear();
+					self.events.push(PaeEvent::Barline(bar));
+				}
+				'+' => {
+					self.pos += 1;
+					let error = self.error("tie must follow a note");
+					self.last_note().ok_or(error)?.tied = true;
+				}
+				't' => {
+					self.pos += 1;
+					let error = self.error("trill must follow a note");
+					self.last_note().ok_or(error)?.trill = true;
+				}
+				'^' => {
+					self.pos += 1;
+					if self.last_note().is_none() {
+						return Err(self.error("chord mark must follow a note"));
+					}
+					self.chord_next = true;
+				}
+				'g' => {
+					self.pos += 1;
+					self.grace_next = true;
+				}
+				'q' => {
+					self.pos += 1;
+					if self.peek() == Some('q') {
+						self.pos += 1;
+						self.grace_group = true;
+					} else {
+						self.grace_next = true;
+					}
+				}
+				'r' => {
+					if !self.grace_group {
+						return Err(self.error("'r' closes a grace group that was not opened with 'qq'"));
+					}
+					self.pos += 1;
+					self.grace_group = false;
+				}
+				'(' => {
+					self.pos += 1;
+					self.groups.push(self.events.len());
+				}
+				';' => {
+					if self.groups.is_empty() {
+						return Err(self.error("';' outside a tuplet group"));
+					}
+					self.pos += 1;
+					if self.take_while(|d| d.is_ascii_digit()).is_empty() {
+						return Err(self.error("expected a tuplet count after ';'"));
+					}
+					// A counted group is a tuplet, never a fermata.
+					if let Some(start) = self.groups.last_mut() {
+						*start = usize::MAX;
+					}
+				}
+				')' => {
+					let start = self.groups.pop().ok_or_else(|| self.error("unmatched ')'"))?;
+					self.pos += 1;
+					if start != usize::MAX && self.events.len() == start + 1 {
+						match &mut self.events[start] {
+							PaeEvent::Note(note) => note.fermata = true,
+							PaeEvent::Rest { fermata, .. } => *fermata = true,
+							_ => {}
+						}
+					}
+				}
+				'%' => {
+					self.pos += 1;
+					let spec: String = self.chars.iter().skip(self.pos).take(3).collect();
+					let clef = spec.parse().map_err(|_| self.error(format!("invalid clef change '{}'", spec)))?;
+					self.pos += spec.chars().count();
+					self.events.push(PaeEvent::Clef(clef));
+				}
+				'$' => {
+					self.pos += 1;
+					let spec = self.take_while(|k| !k.is_whitespace());
+					let key: KeySignature = spec
+						.parse()
+						.map_err(|_| self.error(format!("invalid key change '{}'", spec)))?;
+					self.events.push(PaeEvent::Key(key));
+				}
+				'@' => {
+					self.pos += 1;
+					let spec = self.take_while(|t| !t.is_whitespace());
+					let time = parse_time(&spec).map_err(|_| self.error(format!("invalid time change '{}'", spec)))?;
+					self.events.push(PaeEvent::Time(time));
+				}
+				other => return Err(self.error(format!("unexpected '{}'", other))),
+			}
+		}
+
+		if !self.groups.is_empty() {
+			return Err(self.error("unclosed '('"));
+		}
+		if self.grace_group {
+			return Err(self.error("grace group opened with 'qq' is not closed with 'r'"));
+		}
+		if self.grace_next || self.chord_next {
+			return Err(self.error("data ends before the note it announces"));
+		}
+		Ok(self.events)
+	}
+
+	fn parse_note(&mut self) -> Result<(), PaeError> {
+		let accidental = match self.take_while(|c| matches!(c, 'x' | 'b' | 'n')).as_str() {
+			"" => None,
+			"x" => Some(1),
+			"xx" => Some(2),
+			"b" => Some(-1),
+			"bb" => Some(-2),
+			"n" => Some(0),
+			other => return Err(self.error(format!("unknown accidental '{}'", other))),
+		};
+		let step = match self.peek() {
+			Some(step @ 'A'..='G') => step,
+			_ => return Err(self.error("accidental must be followed by a note")),
+		};
+		self.pos += 1;
+
+		// Key changes written inline apply from here on.
+		let key = self
+			.events
+			.iter()
+			.rev()
+			.find_map(|event| match event {
+				PaeEvent::Key(key) => Some(key),
+				_ => None,
+			})
+			.unwrap_or(self.key);
+		let octave = self.octave;
+		let alter = match accidental {
+			Some(alter) => {
+				self.measure_accidentals.insert((step, octave), alter);
+				alter
+			}
+			None => self
+				.measure_accidentals
+				.get(&(step, octave))
+				.copied()
+				.unwrap_or_else(|| key.alter_for(step)),
+		};
+
+		let duration = self.next_duration();
+		let grace = self.grace_next || self.grace_group;
+		let chord = self.chord_next;
+		self.grace_next = false;
+		self.chord_next = false;
+		self.events.push(PaeEvent::Note(PaeNote {
+			step,
+			octave,
+			alter,
+			accidental,
+			duration,
+			tied: false,
+			grace,
+			chord,
+			fermata: false,
+			trill: false,
+		}));
+		Ok(())
+	}
+}
+
+/// Parse the `data` part of an incipit against its key signature.
+pub fn parse_data(data: &str, key: &KeySignature) -> Result<Vec<PaeEvent>, PaeError> {
+	DataParser {
+		chars: data.chars().collect(),
+		pos: 0,
+		key,
+		events: Vec::new(),
+		octave: 4,
+		rhythm: Vec::new(),
+		rhythm_next: 0,
+		in_rhythm: false,
+		measure_accidentals: HashMap::new(),
+		grace_next: false,
+		grace_group: false,
+		chord_next: false,
+		groups: Vec::new(),
+	}
+	.parse()
+}
+
+/// MIDI pitches of the melody: grace notes and lower chord notes are
+/// dropped, and tied notes count once.
+pub fn pitch_sequence(events: &[PaeEvent]) -> Vec<i32> {
+	let mut pitches = Vec::new();
+	let mut tied_from: Option<i32> = None;
+	for event in events {
+		let PaeEvent::Note(note) = event else {
+			continue;
+		};
+		if note.grace || note.chord {
+			continue;
+		}
+		let pitch = note.midi();
+		if tied_from != Some(pitch) {
+			pitches.push(pitch);
+		}
+		tied_from = note.tied.then_some(pitch);
+	}
+	pitches
+}
+
+/// Successive intervals in semitones, which do not change under transposition.
+pub fn intervals(pitches: &[i32]) -> Vec<i32> {
+	pitches.windows(2).map(|pair| pair[1] - pair[0]).collect()
+}
+
+pub fn contains_intervals(haystack: &[i32], needle: &[i32]) -> bool {
+	!needle.is_empty() && haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+fn accidental_sign(alter: i8) -> &'static str {
+	match alter {
+		2 => "𝄪",
+		1 => "♯",
+		-1 => "♭",
+		-2 => "𝄫",
+		_ => "♮",
+	}
+}
+
+fn describe_key(key: &KeySignature) -> String {
+	if key.steps.is_empty() {
+		return "no accidentals".into();
+	}
+	key.steps
+		.iter()
+		.map(|step| format!("{}{}", step, accidental_sign(key.alter)))
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+fn describe_clef(clef: Clef) -> String {
+	format!("{}-clef on line {}", clef.shape, clef.line)
+}
+
+/// A readable transcription: a header line, then the notes with scientific
+/// pitch names and PAE duration codes (`D5/4.` is a dotted quarter).
+pub fn render_text(parsed: &ParsedIncipit) -> String {
+	let mut header = vec![describe_clef(parsed.clef), describe_key(&parsed.key)];
+	if let Some(time) = &parsed.time {
+		header.push(time.clone());
+	}
+
+	let mut tokens = Vec::new();
+	for event in &parsed.events {
+		let token = match event {
+			PaeEvent::Note(note) => {
+				let mut token = String::new();
+				if note.grace {
+					token.push_str("grace ");
+				}
+				if note.chord {
+					token.push('+');
+				}
+				token.push(note.step);
+				if note.alter != 0 {
+					token.push_str(accidental_sign(note.alter));
+				}
+				let _ = write!(token, "{}/{}", note.octave, note.duration.label());
+				if note.trill {
+					token.push_str(" tr");
+				}
+				if note.fermata {
+					token.push_str(" 𝄐");
+				}
+				if note.tied {
+					token.push_str(" ~");
+				}
+				token
+			}
+			PaeEvent::Rest { duration, fermata } => {
+				format!("rest/{}{}", duration.label(), if *fermata { " 𝄐" } else { "" })
+			}
+			PaeEvent::MeasureRest(1) => "[1 bar rest]".into(),
+			PaeEvent::MeasureRest(count) => format!("[{} bars rest]", count),
+			PaeEvent::Barline(bar) => match bar.as_str() {
+				"//" => "||".into(),
+				"://" => ":||".into(),
+				"//:" => "||:".into(),
+				"://:" => ":||:".into(),
+				_ => "|".into(),
+			},
+			PaeEvent::Clef(clef) => format!("[{}]", describe_clef(*clef)),
+			PaeEvent::Key(key) => format!("[key: {}]", describe_key(key)),
+			PaeEvent::Time(time) => format!("[time: {}]", time),
+		};
+		tokens.push(token);
+	}
+	format!("{}\n{}", header.join(", "), tokens.join(" "))
+}
+
+const STAFF_TOP: f32 = 30.0;
+const LINE_GAP: f32 = 10.0;
+const NOTE_GAP: f32 = 26.0;
+
+/// Staff position in half line-gaps above the bottom line.
+fn staff_position(clef: Clef, step: char, octave: i8) -> i32 {
+	diatonic(step, octave) - clef.diatonic() + (clef.line as i32 - 1) * 2
+}
+
+fn position_y(position: i32) -> f32 {
+	STAFF_TOP + 4.0 * LINE_GAP - position as f32 * LINE_GAP / 2.0
+}
+
+/// Staff positions for the key signature, in the conventional zigzag.
+fn key_positions(clef: Clef, key: &KeySignature) -> Vec<(char, i32)> {
+	// Lowest position each accidental may take, as on treble, alto and bass staves.
+	let floor = match (clef.shape, key.alter > 0) {
+		('G', true) => 3,
+		('C', true) => 2,
+		(_, true) => 1,
+		('G', false) => 1,
+		('C', false) => 0,
+		(_, false) => -1,
+	};
+	key.steps
+		.iter()
+		.map(|&step| {
+			let base = staff_position(clef, step, 0);
+			let position = floor + (base - floor).rem_euclid(7);
+			(step, position)
+		})
+		.collect()
+}
+
+/// A minimal engraving: staff, clef letter, key signature, noteheads with
+/// stems, accidentals, ledger lines, rests and barlines.
+pub fn render_svg(parsed: &ParsedIncipit) -> String {
+	let mut body = String::new();
+	let mut clef = parsed.clef;
+	let mut x = 14.0;
+
+	let draw_clef = |body: &mut String, clef: Clef, x: f32| {
+		let y = position_y((clef.line as i32 - 1) * 2) + 6.0;
+		let _ = writeln!(
+			body,
+			r#"<text x="{x}" y="{y}" font-family="serif" font-size="20" font-weight="bold">{}</text>"#,
+			clef.shape
+		);
+	};
+	let draw_key = |body: &mut String, clef: Clef, key: &KeySignature, x: &mut f32| {
+		let sign = if key.alter > 0 { "♯" } else { "♭" };
+		for (_, position) in key_positions(clef, key) {
+			let y = position_y(position) + 4.0;
+			let _ = writeln!(body, r#"<text x="{}" y="{y}" font-size="14">{sign}</text>"#, *x);
+			*x += 9.0;
+		}
+	};
+
+	draw_clef(&mut body, clef, x);
+	x += 22.0;
+	draw_key(&mut body, clef, &parsed.key, &mut x);
+	if let Some(time) = &parsed.time {
+		let _ = writeln!(
+			body,
+			r#"<text x="{x}" y="{}" font-family="serif" font-size="14" font-weight="bold">{time}</text>"#,
+			STAFF_TOP + 2.0 * LINE_GAP + 5.0
+		);
+		x += 8.0 * time.len() as f32 + 8.0;
+	}
+	x += 10.0;
+
+	for event in &parsed.events {
+		match event {
+			PaeEvent::Note(note) => {
+				if note.chord {
+					x -= NOTE_GAP;
+				}
+				let position = staff_position(clef, note.step, note.octave);
+				let y = position_y(position);
+				let scale = if note.grace { 0.7 } else { 1.0 };
+
+				let ledgers = (position <= -2).then(|| (position..=-2).rev().collect::<Vec<_>>())
+					.or_else(|| (position >= 10).then(|| (10..=position).collect()))
+					.unwrap_or_default();
+				for ledger in ledgers.into_iter().filter(|p| p % 2 == 0) {
+					let ly = position_y(ledger);
+					let _ = writeln!(
+						body,
+						r#"<line x1="{}" y1="{ly}" x2="{}" y2="{ly}" stroke="black"/>"#,
+						x - 9.0,
+						x + 9.0
+					);
+				}
+				if let Some(accidental) = note.accidental {
+					let _ = writeln!(
+						body,
+						r#"<text x="{}" y="{}" font-size="14">{}</text>"#,
+						x - 18.0,
+						y + 4.0,
+						accidental_sign(accidental)
+					);
+				}
+				let fill = if note.duration.is_filled() { "black" } else { "none" };
+				let _ = writeln!(
+					body,
+					r#"<ellipse cx="{x}" cy="{y}" rx="{}" ry="{}" fill="{fill}" stroke="black" stroke-width="1.5" transform="rotate(-20 {x} {y})"/>"#,
+					5.5 * scale,
+					4.0 * scale
+				);
+				if note.duration.has_stem() && !note.chord {
+					let (stem_x, stem_end) = if position < 4 {
+						(x + 5.0 * scale, y - 32.0 * scale)
+					} else {
+						(x - 5.0 * scale, y + 32.0 * scale)
+					};
+					let _ = writeln!(
+						body,
+						r#"<line x1="{stem_x}" y1="{y}" x2="{stem_x}" y2="{stem_end}" stroke="black" stroke-width="1.2"/>"#
+					);
+				}
+				for dot in 0..note.duration.dots {
+					let _ = writeln!(
+						body,
+						r#"<circle cx="{}" cy="{}" r="1.6"/>"#,
+						x + 9.0 + 5.0 * dot as f32,
+						if position % 2 == 0 { y - LINE_GAP / 2.0 } else { y }
+					);
+				}
+				if note.fermata {
+					let _ = writeln!(body, r#"<text x="{}" y="{}" font-size="14">𝄐</text>"#, x - 6.0, STAFF_TOP - 8.0);
+				}
+				x += NOTE_GAP * scale;
+			}
+			PaeEvent::Rest { .. } => {
+				let y = position_y(4);
+				let _ = writeln!(
+					body,
+					r#"<rect x="{}" y="{}" width="6" height="14" fill="black"/>"#,
+					x - 3.0,
+					y - 7.0
+				);
+				x += NOTE_GAP;
+			}
+			PaeEvent::MeasureRest(count) => {
+				let y = position_y(5);
+				let _ = writeln!(
+					body,
+					r#"<rect x="{}" y="{y}" width="16" height="5" fill="black"/>"#,
+					x - 8.0
+				);
+				if *count > 1 {
+					let _ = writeln!(
+						body,
+						r#"<text x="{}" y="{}" font-family="serif" font-size="12" font-weight="bold">{count}</text>"#,
+						x - 4.0,
+						STAFF_TOP - 6.0
+					);
+				}
+				x += NOTE_GAP * 1.5;
+			}
+			PaeEvent::Barline(bar) => {
+				x -= NOTE_GAP / 3.0;
+				let lines = if bar == "/" { 1 } else { 2 };
+				for i in 0..lines {
+					let bx = x + i as f32 * 4.0;
+					let _ = writeln!(
+						body,
+						r#"<line x1="{bx}" y1="{STAFF_TOP}" x2="{bx}" y2="{}" stroke="black"/>"#,
+						STAFF_TOP + 4.0 * LINE_GAP
+					);
+				}
+				x += NOTE_GAP * 2.0 / 3.0 + 4.0;
+			}
+			PaeEvent::Clef(new_clef) => {
+				clef = *new_clef;
+				draw_clef(&mut body, clef, x - 6.0);
+				x += NOTE_GAP;
+			}
+			PaeEvent::Key(key) => {
+				draw_key(&mut body, clef, key, &mut x);
+				x += 8.0;
+			}
+			PaeEvent::Time(time) => {
+				let _ = writeln!(
+					body,
+					r#"<text x="{x}" y="{}" font-family="serif" font-size="14" font-weight="bold">{time}</text>"#,
+					STAFF_TOP + 2.0 * LINE_GAP + 5.0
+				);
+				x += NOTE_GAP;
+			}
+		}
+	}
+
+	let width = (x + 10.0).ceil();
+	let height = STAFF_TOP * 2.0 + 4.0 * LINE_GAP;
+	let mut svg = format!(
+		r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+	);
+	svg.push('\n');
+	for line in 0..5 {
+		let y = STAFF_TOP + line as f32 * LINE_GAP;
+		let _ = writeln!(svg, r#"<line x1="4" y1="{y}" x2="{}" y2="{y}" stroke="black"/>"#, width - 4.0);
+	}
+	svg.push_str(&body);
+	svg.push_str("</svg>\n");
+	svg
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn incipit(clef: &str, key: &str, time: &str, data: &str) -> Incipit {
+		Incipit {
+			clef: clef.into(),
+			key: (!key.is_empty()).then(|| key.into()),
+			time: (!time.is_empty()).then(|| time.into()),
+			data: data.into(),
+		}
+	}
+
+	#[test]
+	fn applies_key_and_measure_accidentals() {
+		let parsed = parse_incipit(&incipit("G-2", "xF", "3/4", "'4FnF8F/4F")).unwrap();
+		let pitches = pitch_sequence(&parsed.events);
+		// F♯, F♮, F♮ (carried through the bar), then F♯ again after the barline.
+		assert_eq!(pitches, [66, 65, 65, 66]);
+	}
+
+	#[test]
+	fn rhythmic_patterns_repeat_until_replaced() {
+		let parsed = parse_incipit(&incipit("G-2", "", "", "4.8CDEF2G")).unwrap();
+		let codes: Vec<(char, u8)> = parsed
+			.events
+			.iter()
+			.filter_map(|event| match event {
+				PaeEvent::Note(note) => Some((note.duration.code, note.duration.dots)),
+				_ => None,
+			})
+			.collect();
+		assert_eq!(codes, [('4', 1), ('8', 0), ('4', 1), ('8', 0), ('2', 0)]);
+	}
+
+	#[test]
+	fn reports_syntax_errors_with_position() {
+		assert_eq!("G-6".parse::<Clef>(), Err(PaeError::Clef("G-6".into())));
+		assert!("xFF".parse::<KeySignature>().is_err());
+		assert!(parse_time("4/").is_err());
+		assert_eq!(
+			parse_data("4CDZ", &KeySignature::default()),
+			Err(PaeError::Data { position: 4, message: "unexpected 'Z'".into() })
+		);
+		assert!(parse_data("4C(DE", &KeySignature::default()).is_err());
+		assert!(parse_data("+C", &KeySignature::default()).is_err());
+		assert!(parse_data("4xx", &KeySignature::default()).is_err());
+	}
+
+	#[test]
+	fn fermatas_ties_and_grace_notes() {
+		let parsed = parse_incipit(&incipit("G-2", "", "", "'4C+CgD(E)6(3FGA;3)")).unwrap();
+		let notes: Vec<&PaeNote> = parsed
+			.events
+			.iter()
+			.filter_map(|event| match event {
+				PaeEvent::Note(note) => Some(note),
+				_ => None,
+			})
+			.collect();
+		assert!(notes[0].tied && notes[2].grace && notes[3].fermata);
+		assert!(!notes[4].fermata);
+		// The tie and grace note drop out of the melody.
+		assert_eq!(pitch_sequence(&parsed.events), [60, 64, 65, 67, 69]);
+	}
+
+	#[test]
+	fn interval_search_ignores_transposition() {
+		let theme = parse_data("''4CDEC", &KeySignature::default()).unwrap();
+		let transposed = parse_data("'4GABG", &KeySignature::default()).unwrap();
+		let query = parse_data("'8DEF", &KeySignature::default()).unwrap();
+		let theme = intervals(&pitch_sequence(&theme));
+		assert_eq!(theme, intervals(&pitch_sequence(&transposed)));
+		assert!(contains_intervals(&theme, &[2, 2]));
+		assert!(!contains_intervals(&theme, &intervals(&pitch_sequence(&query))));
+	}
+
+	#[test]
+	fn renders_text_and_svg() {
+		let parsed = parse_incipit(&incipit("G-2", "bB", "c", "'4.B8A4G/2F")).unwrap();
+		assert_eq!(
+			render_text(&parsed),
+			"G-clef on line 2, B♭, c\nB♭4/4. A4/8 G4/4 | F4/2"
+		);
+		let svg = render_svg(&parsed);
+		assert!(svg.starts_with("<svg"));
+		assert_eq!(svg.matches("<ellipse").count(), 4);
+	}
+}
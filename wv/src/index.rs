@@ -7,8 +7,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::catalog::normalize_catalog_number;
 use crate::inventory::{build_inventory_index, InventoryError, InventoryIndex};
+use crate::merge::current_composer;
+use crate::movement::{format_movement_path, walk_movements};
 use crate::parse::{load_composition, ParseError};
-use crate::types::CatalogEntry;
+use crate::types::{CatalogEntry, RelationKind};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexEntry {
@@ -17,10 +19,44 @@ pub struct IndexEntry {
 	pub note: Option<String>,
 }
 
+/// A catalog number that identifies one movement or section of a record.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovementIndexEntry {
+	pub id: String,
+	pub path: Vec<usize>,
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub superseded: bool,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+/// A relation declared by `id`, filed under its target for inverse lookup.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelationIndexEntry {
+	pub id: String,
+	#[serde(rename = "type")]
+	pub kind: RelationKind,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub movement: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub target_movement: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct FirstLineIndexEntry {
+	pub id: String,
+	/// Movement path such as "1.3"; absent for a first line set on the work.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub movement: Option<String>,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SchemeIndex {
 	pub current: HashMap<String, IndexEntry>,
 	pub superseded: HashMap<String, IndexEntry>,
+	/// Kept apart from `current` so listings by scheme show each record once.
+	#[serde(default, skip_serializing_if = "HashMap::is_empty")]
+	pub movements: HashMap<String, MovementIndexEntry>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -29,6 +65,18 @@ pub struct Index {
 	pub catalog: HashMap<String, HashMap<String, SchemeIndex>>,
 	pub editions: HashMap<String, HashMap<String, HashMap<String, String>>>,
 	pub inventory: InventoryIndex,
+	/// Normalized nickname (see `normalize_words`) to composition IDs.
+	pub nicknames: HashMap<String, Vec<String>>,
+	/// Target composition ID to the relations pointing at it.
+	pub relations: HashMap<String, Vec<RelationIndexEntry>>,
+	/// Person slug to the compositions that credit them in any role.
+	pub people: HashMap<String, Vec<String>>,
+	/// Normalized first line of sung text to where it occurs.
+	pub first_lines: HashMap<String, Vec<FirstLineIndexEntry>>,
+	/// Occasion slug to the compositions written for it.
+	pub occasions: HashMap<String, Vec<String>>,
+	/// Chorale melody slug to the works and movements that set it.
+	pub chorales: HashMap<String, Vec<FirstLineIndexEntry>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -73,7 +121,7 @@ pub enum EditionIndexError {
 	},
 }
 
-const INDEX_FORMAT_VERSION: u32 = 2;
+const INDEX_FORMAT_VERSION: u32 = 9;
 const INDEX_TTL_SECS: u64 = 24 * 60 * 60;
 
 /// Directories whose contents feed the index, with the extension that matters.
@@ -137,15 +185,18 @@ pub fn build_index<P: AsRef<Path>>(data_dir: P) -> Result<Index, IndexError> {
 			let mut scheme_first_seen: HashMap<(String, String), bool> = HashMap::new();
 
 			for attr in comp.attribution.iter() {
-				if let Some(composer) = &attr.composer {
-					if composers_seen.insert(composer.clone()) {
+				for credited in attr.credited() {
+					if composers_seen.insert(credited.to_string()) {
 						index
 							.by_composer
-							.entry(composer.clone())
+							.entry(credited.to_string())
 							.or_default()
 							.push(comp.id.clone());
 					}
+				}
 
+				if let Some(composer) = attr.principal() {
+					let composer = composer.to_string();
 					if let Some(catalog) = &attr.catalog {
 						for cat in catalog {
 							let key = (composer.clone(), cat.scheme.clone());
@@ -167,6 +218,94 @@ pub fn build_index<P: AsRef<Path>>(data_dir: P) -> Result<Index, IndexError> {
 					}
 				}
 			}
+
+			for relation in comp.relations.iter().flatten() {
+				index
+					.relations
+					.entry(relation.target.clone())
+					.or_default()
+					.push(RelationIndexEntry {
+						id: comp.id.clone(),
+						kind: relation.kind,
+						movement: relation.movement.clone(),
+						target_movement: relation.target_movement.clone(),
+					});
+			}
+
+			for person_ref in comp.people.iter().flatten() {
+				let ids = index.people.entry(person_ref.person.clone()).or_default();
+				if !ids.contains(&comp.id) {
+					ids.push(comp.id.clone());
+				}
+			}
+
+			if let Some(occasion) = &comp.occasion {
+				index.occasions.entry(occasion.clone()).or_default().push(comp.id.clone());
+			}
+
+			if let Some(chorale) = &comp.chorale {
+				index
+					.chorales
+					.entry(chorale.clone())
+					.or_default()
+					.push(FirstLineIndexEntry { id: comp.id.clone(), movement: None });
+			}
+			for visit in walk_movements(&comp) {
+				if let Some(chorale) = visit.node.chorale() {
+					index
+						.chorales
+						.entry(chorale.to_string())
+						.or_default()
+						.push(FirstLineIndexEntry {
+							id: comp.id.clone(),
+							movement: Some(format_movement_path(&visit.path)),
+						});
+				}
+			}
+
+			if let Some(line) = comp.text.as_ref().and_then(|text| text.first_line.as_deref()) {
+				index
+					.first_lines
+					.entry(normalize_words(line))
+					.or_default()
+					.push(FirstLineIndexEntry { id: comp.id.clone(), movement: None });
+			}
+			// A texted movement without an explicit first line is titled by it.
+			for visit in walk_movements(&comp) {
+				let line = visit
+					.node
+					.text()
+					.and_then(|text| text.first_line.as_deref().or(visit.node.title()));
+				if let Some(line) = line {
+					index
+						.first_lines
+						.entry(normalize_words(line))
+						.or_default()
+						.push(FirstLineIndexEntry {
+							id: comp.id.clone(),
+							movement: Some(format_movement_path(&visit.path)),
+						});
+				}
+			}
+
+			for nickname in comp.nicknames.iter().flatten() {
+				for name in nickname.name.values() {
+					let ids = index.nicknames.entry(normalize_words(name)).or_default();
+					if !ids.contains(&comp.id) {
+						ids.push(comp.id.clone());
+					}
+				}
+			}
+
+			if let Some(composer) = current_composer(&comp.attribution) {
+				for visit in walk_movements(&comp) {
+					let mut schemes_seen = std::collections::HashSet::new();
+					for cat in visit.node.catalog() {
+						let is_current = schemes_seen.insert(cat.scheme.as_str());
+						add_movement_catalog_entry(&mut index, composer, cat, &comp.id, &visit.path, is_current);
+					}
+				}
+			}
 		}
 	}
 
@@ -176,6 +315,16 @@ pub fn build_index<P: AsRef<Path>>(data_dir: P) -> Result<Index, IndexError> {
 	Ok(index)
 }
 
+/// Lowercase words with punctuation dropped, so "'Moonlight'" and
+/// "moonlight" index alike.
+pub fn normalize_words(name: &str) -> String {
+	name.split(|c: char| !c.is_alphanumeric())
+		.filter(|word| !word.is_empty())
+		.map(str::to_lowercase)
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
 fn build_cumulative_editions(index: &mut Index, entries: &[EditionEntry]) {
 	let mut by_scheme: HashMap<(String, String), Vec<&EditionEntry>> = HashMap::new();
 	for entry in entries {
@@ -251,11 +400,47 @@ fn add_catalog_entry(index: &mut Index, composer: &str, cat: &CatalogEntry, id:
 	}
 }
 
+fn add_movement_catalog_entry(
+	index: &mut Index,
+	composer: &str,
+	cat: &CatalogEntry,
+	id: &str,
+	path: &[usize],
+	is_current: bool,
+) {
+	let scheme_index = index
+		.catalog
+		.entry(composer.to_string())
+		.or_default()
+		.entry(cat.scheme.clone())
+		.or_default();
+
+	let number = normalize_catalog_number(&cat.number);
+	if !is_current && scheme_index.movements.contains_key(&number) {
+		return;
+	}
+	scheme_index.movements.insert(
+		number,
+		MovementIndexEntry {
+			id: id.to_string(),
+			path: path.to_vec(),
+			superseded: !is_current,
+			note: cat.note.clone(),
+		},
+	);
+}
+
 pub fn load_index<P: AsRef<Path>>(data_dir: P) -> Option<Index> {
 	let data_dir = data_dir.as_ref();
 	let index_path = data_dir.join(".indexes").join("index.json");
 	let composer_path = data_dir.join(".indexes").join("composer-index.json");
 	let inventory_path = data_dir.join(".indexes").join("inventory-index.json");
+	let nickname_path = data_dir.join(".indexes").join("nickname-index.json");
+	let relation_path = data_dir.join(".indexes").join("relation-index.json");
+	let person_path = data_dir.join(".indexes").join("person-index.json");
+	let first_line_path = data_dir.join(".indexes").join("first-line-index.json");
+	let occasion_path = data_dir.join(".indexes").join("occasion-index.json");
+	let chorale_path = data_dir.join(".indexes").join("chorale-index.json");
 
 	let catalog_content = fs::read_to_string(&index_path).ok()?;
 	let composer_content = fs::read_to_string(&composer_path).ok()?;
@@ -266,12 +451,48 @@ pub fn load_index<P: AsRef<Path>>(data_dir: P) -> Option<Index> {
 		Err(error) if error.kind() == std::io::ErrorKind::NotFound => InventoryIndex::default(),
 		Err(_) => return None,
 	};
+	let nicknames = match fs::read_to_string(&nickname_path) {
+		Ok(content) => serde_json::from_str(&content).ok()?,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(_) => return None,
+	};
+	let relations = match fs::read_to_string(&relation_path) {
+		Ok(content) => serde_json::from_str(&content).ok()?,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(_) => return None,
+	};
+	let people = match fs::read_to_string(&person_path) {
+		Ok(content) => serde_json::from_str(&content).ok()?,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(_) => return None,
+	};
+	let first_lines = match fs::read_to_string(&first_line_path) {
+		Ok(content) => serde_json::from_str(&content).ok()?,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(_) => return None,
+	};
+	let occasions = match fs::read_to_string(&occasion_path) {
+		Ok(content) => serde_json::from_str(&content).ok()?,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(_) => return None,
+	};
+	let chorales = match fs::read_to_string(&chorale_path) {
+		Ok(content) => serde_json::from_str(&content).ok()?,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+		Err(_) => return None,
+	};
 
 	Some(Index {
 		catalog,
 		by_composer,
 		editions: HashMap::new(),
 		inventory,
+		nicknames,
+		relations,
+		people,
+		first_lines,
+		occasions,
+		chorales,
 	})
 }
 
@@ -408,7 +629,7 @@ fn write_index_metadata(data_dir: &Path, metadata: &IndexMetadata) -> std::io::R
 
 /// Write via a temporary file and rename, so an interrupted or concurrent run
 /// cannot leave a half-written index behind.
-fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+pub(crate) fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
 	let parent = path.parent().unwrap_or_else(|| Path::new("."));
 	fs::create_dir_all(parent)?;
 
@@ -453,6 +674,12 @@ pub fn save_index<P: AsRef<Path>>(index: &Index, data_dir: P) -> std::io::Result
 	write_index(index, indexes_dir.join("index.json"))?;
 	write_composer_index(index, indexes_dir.join("composer-index.json"))?;
 	write_inventory_index(index, indexes_dir.join("inventory-index.json"))?;
+	write_nickname_index(index, indexes_dir.join("nickname-index.json"))?;
+	write_relation_index(index, indexes_dir.join("relation-index.json"))?;
+	write_person_index(index, indexes_dir.join("person-index.json"))?;
+	write_first_line_index(index, indexes_dir.join("first-line-index.json"))?;
+	write_occasion_index(index, indexes_dir.join("occasion-index.json"))?;
+	write_chorale_index(index, indexes_dir.join("chorale-index.json"))?;
 
 	if !index.editions.is_empty() {
 		write_edition_indexes(index, data_dir)?;
@@ -499,6 +726,36 @@ pub fn write_inventory_index<P: AsRef<Path>>(index: &Index, output_path: P) -> s
 	write_atomic(output_path.as_ref(), &json)
 }
 
+pub fn write_nickname_index<P: AsRef<Path>>(index: &Index, output_path: P) -> std::io::Result<()> {
+	let json = serde_json::to_string_pretty(&index.nicknames)?;
+	write_atomic(output_path.as_ref(), &json)
+}
+
+pub fn write_relation_index<P: AsRef<Path>>(index: &Index, output_path: P) -> std::io::Result<()> {
+	let json = serde_json::to_string_pretty(&index.relations)?;
+	write_atomic(output_path.as_ref(), &json)
+}
+
+pub fn write_person_index<P: AsRef<Path>>(index: &Index, output_path: P) -> std::io::Result<()> {
+	let json = serde_json::to_string_pretty(&index.people)?;
+	write_atomic(output_path.as_ref(), &json)
+}
+
+pub fn write_first_line_index<P: AsRef<Path>>(index: &Index, output_path: P) -> std::io::Result<()> {
+	let json = serde_json::to_string_pretty(&index.first_lines)?;
+	write_atomic(output_path.as_ref(), &json)
+}
+
+pub fn write_occasion_index<P: AsRef<Path>>(index: &Index, output_path: P) -> std::io::Result<()> {
+	let json = serde_json::to_string_pretty(&index.occasions)?;
+	write_atomic(output_path.as_ref(), &json)
+}
+
+pub fn write_chorale_index<P: AsRef<Path>>(index: &Index, output_path: P) -> std::io::Result<()> {
+	let json = serde_json::to_string_pretty(&index.chorales)?;
+	write_atomic(output_path.as_ref(), &json)
+}
+
 pub fn write_edition_indexes<P: AsRef<Path>>(index: &Index, data_dir: P) -> std::io::Result<()> {
 	let editions_dir = data_dir.as_ref().join(".indexes").join("editions");
 	fs::create_dir_all(&editions_dir)?;
@@ -695,6 +952,7 @@ mod tests {
 			edition: None,
 			since: None,
 			note: None,
+			sources: None,
 		};
 
 		add_catalog_entry(&mut index, "bach", &cat, "78129abd", true);
@@ -713,6 +971,7 @@ mod tests {
 			edition: None,
 			since: None,
 			note: None,
+			sources: None,
 		};
 
 		add_catalog_entry(&mut index, "bach", &cat, "abc12345", true);
@@ -732,6 +991,7 @@ mod tests {
 			edition: Some("6".into()),
 			since: None,
 			note: None,
+			sources: None,
 		};
 
 		add_catalog_entry(&mut index, "mozart", &cat, "a7a495c0", false);
@@ -780,6 +1040,35 @@ mod tests {
 		assert!(!index.editions["mozart-k"]["9"].contains_key("300i"));
 	}
 
+	#[test]
+	fn test_movement_numbers_are_indexed_apart_from_works() {
+		let temp = tempfile::tempdir().unwrap();
+		let dir = temp.path().join("compositions/ab");
+		fs::create_dir_all(&dir).unwrap();
+		fs::write(dir.join("cd1234.json"), r#"{
+			"id": "abcd1234",
+			"form": "bagatelles",
+			"attribution": [{
+				"composer": "beethoven",
+				"catalog": [{"scheme": "op", "number": "33"}]
+			}],
+			"movements": [
+				{"form": "bagatelle", "catalog": [{"scheme": "op", "number": "33/1"}]},
+				{"form": "bagatelle", "catalog": [
+					{"scheme": "op", "number": "33/2"},
+					{"scheme": "op", "number": "33/9"}
+				]}
+			]
+		}"#).unwrap();
+
+		let index = build_index(temp.path()).unwrap();
+		let op = &index.catalog["beethoven"]["op"];
+		assert_eq!(op.current.len(), 1);
+		assert_eq!(op.movements["33/2"].path, vec![2]);
+		assert!(!op.movements["33/2"].superseded);
+		assert!(op.movements["33/9"].superseded);
+	}
+
 	#[test]
 	fn test_add_catalog_entry_with_note() {
 		let mut index = Index::default();
@@ -789,6 +1078,7 @@ mod tests {
 			edition: None,
 			since: Some("1990".into()),
 			note: Some("spurious attribution".into()),
+			sources: None,
 		};
 
 		add_catalog_entry(&mut index, "bach", &cat, "78129abd", true);
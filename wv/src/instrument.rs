@@ -0,0 +1,440 @@
+//! The instrument vocabulary: ids, labels and accepted spellings, read from
+//! `taxonomy/instruments.toml` in the data directory.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::types::{InstrumentPart, Instrumentation, PartRole};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstrumentDefinition {
+	pub label: String,
+	#[serde(default)]
+	pub plural: Option<String>,
+	pub family: String,
+	#[serde(default)]
+	pub aliases: Vec<String>,
+}
+
+impl InstrumentDefinition {
+	pub fn plural(&self) -> String {
+		self.plural
+			.clone()
+			.unwrap_or_else(|| format!("{}s", self.label))
+	}
+
+	/// Label, plural and aliases: every spelling that names the instrument.
+	fn spellings(&self) -> impl Iterator<Item = String> + '_ {
+		[self.label.clone(), self.plural()].into_iter().chain(self.aliases.iter().cloned())
+	}
+}
+
+/// Instruments by id.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct InstrumentVocabulary {
+	pub instruments: BTreeMap<String, InstrumentDefinition>,
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InstrumentVocabularyError {
+	#[error("failed to read {path}: {message}")]
+	Io { path: PathBuf, message: String },
+	#[error("invalid instrument vocabulary {path}: {message}")]
+	Parse { path: PathBuf, message: String },
+}
+
+impl InstrumentVocabulary {
+	pub fn get(&self, id: &str) -> Option<&InstrumentDefinition> {
+		self.instruments.get(id)
+	}
+
+	/// The id a spelling names, ignoring case.
+	pub fn resolve(&self, name: &str) -> Option<&str> {
+		let name = name.trim().to_lowercase();
+		self.instruments
+			.iter()
+			.find(|(_, definition)| definition.spellings().any(|spelling| spelling.to_lowercase() == name))
+			.map(|(id, _)| id.as_str())
+	}
+
+	/// Problems with the vocabulary itself: spellings that name more than
+	/// one instrument.
+	pub fn check(&self) -> Vec<String> {
+		let mut problems = Vec::new();
+		let mut seen: HashMap<String, &str> = HashMap::new();
+		for (id, definition) in &self.instruments {
+			for spelling in definition.spellings() {
+				let spelling = spelling.to_lowercase();
+				match seen.get(&spelling) {
+					Some(other) if *other != id => {
+						problems.push(format!("{}: '{}' also names {}", id, spelling, other));
+					}
+					_ => {
+						seen.insert(spelling, id);
+					}
+				}
+			}
+		}
+		problems
+	}
+}
+
+pub fn instrument_vocabulary_path<P: AsRef<Path>>(data_dir: P) -> PathBuf {
+	data_dir.as_ref().join("taxonomy").join("instruments.toml")
+}
+
+type VocabularyResult = Result<Option<Rc<InstrumentVocabulary>>, InstrumentVocabularyError>;
+
+thread_local! {
+	static VOCABULARY_CACHE: RefCell<HashMap<PathBuf, VocabularyResult>> = RefCell::new(HashMap::new());
+}
+
+/// The dataset's instrument vocabulary, or `None` for datasets without one.
+/// Read once per process.
+pub fn load_instrument_vocabulary<P: AsRef<Path>>(data_dir: P) -> VocabularyResult {
+	let path = instrument_vocabulary_path(data_dir);
+	if let Some(cached) = VOCABULARY_CACHE.with(|cache| cache.borrow().get(&path).cloned()) {
+		return cached;
+	}
+	let vocabulary = read_instrument_vocabulary(&path);
+	VOCABULARY_CACHE.with(|cache| {
+		cache.borrow_mut().insert(path, vocabulary.clone());
+	});
+	vocabulary
+}
+
+pub(crate) fn read_instrument_vocabulary(path: &Path) -> VocabularyResult {
+	let content = match fs::read_to_string(path) {
+		Ok(content) => content,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+		Err(error) => {
+			return Err(InstrumentVocabularyError::Io { path: path.to_path_buf(), message: error.to_string() })
+		}
+	};
+	toml::from_str(&content)
+		.map(|vocabulary| Some(Rc::new(vocabulary)))
+		.map_err(|error| InstrumentVocabularyError::Parse { path: path.to_path_buf(), message: error.to_string() })
+}
+
+/// The instrument's label, or its id when there is no vocabulary to look
+/// it up in.
+fn instrument_name(id: &str, plural: bool, vocabulary: Option<&InstrumentVocabulary>) -> String {
+	match vocabulary.and_then(|vocabulary| vocabulary.get(id)) {
+		Some(definition) if plural => definition.plural(),
+		Some(definition) => definition.label.clone(),
+		None => id.to_string(),
+	}
+}
+
+pub fn format_part(part: &InstrumentPart, vocabulary: Option<&InstrumentVocabulary>) -> String {
+	let count = part.count.unwrap_or(1);
+	let mut text = instrument_name(&part.instrument, count > 1, vocabulary);
+	for alternative in part.alternatives.iter().flatten() {
+		text = format!("{} or {}", text, instrument_name(alternative, count > 1, vocabulary));
+	}
+
+	// The count goes before the modifier: "2 solo violins".
+	if part.role == Some(PartRole::Soloist) {
+		text = format!("solo {}", text);
+	}
+	if count > 1 {
+		text = format!("{} {}", count, text);
+	}
+
+	text = match part.role {
+		Some(PartRole::Obbligato) => format!("{} obbligato", text),
+		Some(PartRole::Continuo) if part.instrument != "continuo" => format!("{} (continuo)", text),
+		_ => text,
+	};
+
+	if let Some(note) = &part.note {
+		text = format!("{} ({})", text, note);
+	}
+	text
+}
+
+pub fn format_instrumentation(instrumentation: &Instrumentation, vocabulary: Option<&InstrumentVocabulary>) -> String {
+	match instrumentation {
+		Instrumentation::Text(text) => text.clone(),
+		Instrumentation::Parts(parts) => parts
+			.iter()
+			.map(|part| format_part(part, vocabulary))
+			.collect::<Vec<_>>()
+			.join(", "),
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedInstrumentation {
+	pub parts: Vec<InstrumentPart>,
+	/// Fragments the parser could not map onto the vocabulary.
+	pub unrecognized: Vec<String>,
+}
+
+/// Best-effort reading of a free-text instrumentation string, for migrating
+/// existing records. Anything it cannot place is returned rather than guessed.
+pub fn parse_instrumentation(text: &str, vocabulary: &InstrumentVocabulary) -> ParsedInstrumentation {
+	let lower = text.to_lowercase();
+	// A bare "bass" among voices is a singer; in an instrumental list it is
+	// the string bass.
+	let bass_is_voice = ["soprano", "alto", "tenor", "choir", "chorus", "voice"]
+		.iter()
+		.any(|voice| lower.contains(voice));
+
+	let mut parsed = ParsedInstrumentation::default();
+	for fragment in split_fragments(text) {
+		match parse_fragment(&fragment, bass_is_voice, vocabulary) {
+			Some(part) => parsed.parts.push(part),
+			None => parsed.unrecognized.push(fragment),
+		}
+	}
+	parsed
+}
+
+/// Split on commas, semicolons, and "and", ignoring any inside parentheses.
+fn split_fragments(text: &str) -> Vec<String> {
+	let mut fragments = Vec::new();
+	let mut current = String::new();
+	let mut depth = 0usize;
+
+	let mut push = |current: &mut String| {
+		let fragment = current.trim();
+		let fragment = fragment.strip_prefix("and ").unwrap_or(fragment).trim();
+		if !fragment.is_empty() {
+			fragments.push(fragment.to_string());
+		}
+		current.clear();
+	};
+
+	let mut rest = text;
+	while let Some(c) = rest.chars().next() {
+		if depth == 0 && rest.starts_with(" and ") {
+			push(&mut current);
+			rest = &rest[" and ".len()..];
+			continue;
+		}
+		match c {
+			'(' => depth += 1,
+			')' => depth = depth.saturating_sub(1),
+			',' | ';' if depth == 0 => {
+				push(&mut current);
+				rest = &rest[1..];
+				continue;
+			}
+			_ => {}
+		}
+		current.push(c);
+		rest = &rest[c.len_utf8()..];
+	}
+	push(&mut current);
+	fragments
+}
+
+fn parse_count(word: &str) -> Option<u32> {
+	if let Ok(count) = word.parse() {
+		return Some(count);
+	}
+	let words = [
+		"one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven",
+		"twelve",
+	];
+	words
+		.iter()
+		.position(|w| w.eq_ignore_ascii_case(word))
+		.map(|i| i as u32 + 1)
+}
+
+/// Choir voicings such as "SATB" or "four-part".
+fn is_voicing(word: &str) -> bool {
+	(word.len() >= 2 && word.chars().all(|c| matches!(c, 'S' | 'A' | 'T' | 'B')))
+		|| word.to_lowercase().ends_with("-part")
+}
+
+fn lookup_instrument(name: &str, bass_is_voice: bool, vocabulary: &InstrumentVocabulary) -> Option<String> {
+	if name.trim().eq_ignore_ascii_case("bass") && !bass_is_voice {
+		return Some("double-bass".into());
+	}
+	vocabulary.resolve(name).map(str::to_string)
+}
+
+fn parse_fragment(fragment: &str, bass_is_voice: bool, vocabulary: &InstrumentVocabulary) -> Option<InstrumentPart> {
+	let mut notes: Vec<String> = Vec::new();
+	let mut alternatives: Vec<String> = Vec::new();
+	let mut role = None;
+
+	let mut bare = String::new();
+	let mut remaining = fragment;
+	while let Some(open) = remaining.find('(') {
+		let close = open + remaining[open..].find(')')?;
+		bare.push_str(&remaining[..open]);
+		let inner = remaining[open + 1..close].trim();
+		match inner.to_lowercase().as_str() {
+			"solo" => role = Some(PartRole::Soloist),
+			"obbligato" => role = Some(PartRole::Obbligato),
+			"continuo" => role = Some(PartRole::Continuo),
+			lower => match lower.strip_prefix("or ") {
+				Some(alternative) => alternatives.push(lookup_instrument(alternative, bass_is_voice, vocabulary)?),
+				None => notes.push(inner.to_string()),
+			},
+		}
+		remaining = &remaining[close + 1..];
+	}
+	bare.push_str(remaining);
+
+	let mut words: Vec<&str> = bare.split_whitespace().collect();
+	loop {
+		let last = words.last().map(|w| w.to_lowercase());
+		match last.as_deref() {
+			Some("libitum") if words.len() >= 2 && words[words.len() - 2].eq_ignore_ascii_case("ad") => {
+				words.truncate(words.len() - 2);
+				notes.push("ad libitum".into());
+			}
+			Some("obbligato") => {
+				words.pop();
+				role = Some(PartRole::Obbligato);
+			}
+			Some("soloist") | Some("soloists") => {
+				words.pop();
+				role = Some(PartRole::Soloist);
+			}
+			Some("four-hands") => {
+				words.pop();
+				notes.push("four-hands".into());
+			}
+			_ => break,
+		}
+	}
+
+	let count = words.first().and_then(|word| parse_count(word));
+	if count.is_some() {
+		words.remove(0);
+	}
+	match words.first().map(|word| word.to_lowercase()).as_deref() {
+		Some("solo") => {
+			words.remove(0);
+			role = Some(PartRole::Soloist);
+		}
+		Some("obbligato") => {
+			words.remove(0);
+			role = Some(PartRole::Obbligato);
+		}
+		_ => {}
+	}
+	if words.len() > 1 && is_voicing(words[0]) {
+		notes.insert(0, words.remove(0).to_string());
+	}
+
+	let name = words.join(" ");
+	let mut names = name.split(" or ");
+	let instrument = lookup_instrument(names.next()?, bass_is_voice, vocabulary)?;
+	for alternative in names {
+		alternatives.push(lookup_instrument(alternative, bass_is_voice, vocabulary)?);
+	}
+	if instrument == "continuo" && role == Some(PartRole::Continuo) {
+		role = None;
+	}
+
+	Some(InstrumentPart {
+		instrument,
+		count: count.filter(|count| *count > 1),
+		role,
+		alternatives: (!alternatives.is_empty()).then_some(alternatives),
+		note: (!notes.is_empty()).then(|| notes.join("; ")),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// The vocabulary shipped with the dataset.
+	fn vocabulary() -> Rc<InstrumentVocabulary> {
+		load_instrument_vocabulary(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap().unwrap()
+	}
+
+	fn parse(text: &str) -> ParsedInstrumentation {
+		parse_instrumentation(text, &vocabulary())
+	}
+
+	fn ids(parsed: &ParsedInstrumentation) -> Vec<&str> {
+		parsed.parts.iter().map(|part| part.instrument.as_str()).collect()
+	}
+
+	#[test]
+	fn vocabulary_spellings_are_unambiguous() {
+		assert_eq!(vocabulary().check(), Vec::<String>::new());
+
+		let vocabulary: InstrumentVocabulary = toml::from_str(r#"
+			[cello]
+			label = "cello"
+			family = "strings"
+			aliases = ["violoncello", "bass"]
+
+			[double-bass]
+			label = "double bass"
+			family = "strings"
+			aliases = ["bass"]
+		"#).unwrap();
+		assert_eq!(vocabulary.check(), ["double-bass: 'bass' also names cello"]);
+	}
+
+	#[test]
+	fn parses_cantata_scoring() {
+		let parsed = parse(
+			"soprano, bass, choir (SATB), 2 oboes d'amore, 2 violins, viola, organ obbligato, basso continuo",
+		);
+		assert!(parsed.unrecognized.is_empty(), "{:?}", parsed.unrecognized);
+		assert_eq!(
+			ids(&parsed),
+			["soprano", "bass", "choir", "oboe-damore", "violin", "viola", "organ", "continuo"]
+		);
+		assert_eq!(parsed.parts[2].note.as_deref(), Some("SATB"));
+		assert_eq!(parsed.parts[3].count, Some(2));
+		assert_eq!(parsed.parts[6].role, Some(PartRole::Obbligato));
+	}
+
+	#[test]
+	fn parses_alternatives_and_qualifiers() {
+		let parsed = parse("keyboard, violin (or flute), cello ad libitum");
+		assert!(parsed.unrecognized.is_empty());
+		assert_eq!(parsed.parts[1].alternatives, Some(vec!["flute".to_string()]));
+		assert_eq!(parsed.parts[2].note.as_deref(), Some("ad libitum"));
+
+		let parsed = parse("organ or harpsichord");
+		assert_eq!(parsed.parts[0].instrument, "organ");
+		assert_eq!(parsed.parts[0].alternatives, Some(vec!["harpsichord".to_string()]));
+	}
+
+	#[test]
+	fn bare_bass_depends_on_context() {
+		assert_eq!(ids(&parse("2 violins, organ, cello and bass")).last(), Some(&"double-bass"));
+		assert_eq!(ids(&parse("tenor and bass")).last(), Some(&"bass"));
+	}
+
+	#[test]
+	fn unknown_fragments_are_reported_not_guessed() {
+		let parsed = parse("piano, theremin");
+		assert_eq!(ids(&parsed), ["piano"]);
+		assert_eq!(parsed.unrecognized, ["theremin"]);
+	}
+
+	#[test]
+	fn formats_parts_from_vocabulary() {
+		let parsed = parse(
+			"2 solo violins, violin (or flute), organ (continuo), choir (SATB), 2 oboe d'amore, timpani",
+		);
+		assert!(parsed.unrecognized.is_empty());
+		assert_eq!(
+			format_instrumentation(&Instrumentation::Parts(parsed.parts), Some(&vocabulary())),
+			"2 solo violins, violin or flute, organ (continuo), choir (SATB), 2 oboes d'amore, timpani"
+		);
+	}
+}
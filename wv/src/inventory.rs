@@ -210,17 +210,17 @@ fn collect_toml_files(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<(), Invent
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::types::SortKey;
+	use crate::types::{CatalogGroup, SortKey};
 
 	fn op_defn() -> CatalogDefinition {
 		CatalogDefinition {
 			name: "Opus".into(),
 			pattern: Some(r"^(\d+)(?:/(\d+))?$".into()),
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 2, sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(2), sort_type: "int".into(), display: None, none_last: None },
 			]),
-			group_by: Some(vec![1]),
+			group_by: Some(vec![CatalogGroup::Index(1)]),
 			..Default::default()
 		}
 	}
@@ -300,11 +300,11 @@ mod tests {
 			name: "WoO".into(),
 			pattern: Some(r"^(\d+)([a-z])?(?:/(\d+))?$".into()),
 			sort_keys: Some(vec![
-				SortKey { group: 1, sort_type: "int".into(), display: None, none_last: None },
-				SortKey { group: 2, sort_type: "str".into(), display: None, none_last: None },
-				SortKey { group: 3, sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(1), sort_type: "int".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(2), sort_type: "str".into(), display: None, none_last: None },
+				SortKey { group: CatalogGroup::Index(3), sort_type: "int".into(), display: None, none_last: None },
 			]),
-			group_by: Some(vec![1, 2]),
+			group_by: Some(vec![CatalogGroup::Index(1), CatalogGroup::Index(2)]),
 			..Default::default()
 		};
 		assert!(group_member_key("2a", &defn).is_none());
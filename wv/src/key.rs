@@ -0,0 +1,290 @@
+//! Keys as written in the data: `D`, `f#`, `Bb`, `e.phr`. Upper case is
+//! major, lower case minor, and a suffix after the dot names a church mode.
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+#[error("invalid key '{input}': {message}")]
+pub struct KeyError {
+	pub input: String,
+	pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+	Major,
+	Minor,
+	Dorian,
+	Phrygian,
+	Lydian,
+	Mixolydian,
+	Locrian,
+}
+
+impl Mode {
+	const CHURCH: [(Mode, &'static str, &'static str); 5] = [
+		(Mode::Dorian, "dor", "dorian"),
+		(Mode::Phrygian, "phr", "phrygian"),
+		(Mode::Lydian, "lyd", "lydian"),
+		(Mode::Mixolydian, "mix", "mixolydian"),
+		(Mode::Locrian, "loc", "locrian"),
+	];
+
+	/// The suffix after the dot in a key code; `None` for major and minor.
+	pub fn suffix(self) -> Option<&'static str> {
+		Self::CHURCH.iter().find(|(mode, _, _)| *mode == self).map(|(_, suffix, _)| *suffix)
+	}
+
+	/// A mode by suffix (`dor`) or name (`dorian`, `minor`), ignoring case.
+	pub fn from_name(name: &str) -> Option<Mode> {
+		let name = name.to_lowercase();
+		match name.as_str() {
+			"major" => Some(Mode::Major),
+			"minor" => Some(Mode::Minor),
+			_ => Self::CHURCH
+				.iter()
+				.find(|(_, suffix, full)| name == *suffix || name == *full)
+				.map(|(mode, _, _)| *mode),
+		}
+	}
+
+	pub fn is_church_mode(self) -> bool {
+		!matches!(self, Mode::Major | Mode::Minor)
+	}
+
+	/// Semitones above the tonic of the major key sharing this mode's
+	/// signature: D Dorian and A minor both sit on C major.
+	fn degree(self) -> u8 {
+		match self {
+			Mode::Major => 0,
+			Mode::Dorian => 2,
+			Mode::Phrygian => 4,
+			Mode::Lydian => 5,
+			Mode::Mixolydian => 7,
+			Mode::Minor => 9,
+			Mode::Locrian => 11,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key {
+	/// Note letter, `A` to `G`.
+	pub tonic: char,
+	/// Sharps (positive) or flats (negative), at most two.
+	pub accidental: i8,
+	pub mode: Mode,
+}
+
+impl Key {
+	/// The tonic as a pitch class, 0 for C through 11 for B.
+	pub fn pitch_class(&self) -> u8 {
+		let natural: i8 = match self.tonic {
+			'C' => 0,
+			'D' => 2,
+			'E' => 4,
+			'F' => 5,
+			'G' => 7,
+			'A' => 9,
+			_ => 11,
+		};
+		(natural + self.accidental).rem_euclid(12) as u8
+	}
+
+	/// `#`, `b`, `##`, `bb`, or empty.
+	pub fn accidental_code(&self) -> &'static str {
+		match self.accidental {
+			2 => "##",
+			1 => "#",
+			-1 => "b",
+			-2 => "bb",
+			_ => "",
+		}
+	}
+
+	/// The spelled tonic, e.g. `F#` or `Bb`.
+	pub fn tonic_name(&self) -> String {
+		format!("{}{}", self.tonic, self.accidental_code())
+	}
+
+	fn signature(&self) -> u8 {
+		(self.pitch_class() + 12 - self.mode.degree()) % 12
+	}
+
+	/// Same sounding tonic in any mode: D major, d minor and D Dorian.
+	pub fn same_tonic(&self, other: &Key) -> bool {
+		self.pitch_class() == other.pitch_class()
+	}
+
+	/// Same sounding key, however spelled: C♯ major and D♭ major.
+	pub fn enharmonic(&self, other: &Key) -> bool {
+		self.same_tonic(other) && self.mode == other.mode
+	}
+
+	/// Another mode on the same key signature: C major and a minor.
+	pub fn relative(&self, other: &Key) -> bool {
+		self.mode != other.mode && self.signature() == other.signature()
+	}
+
+	/// Another mode on the same tonic: C major and c minor.
+	pub fn parallel(&self, other: &Key) -> bool {
+		self.mode != other.mode && self.same_tonic(other)
+	}
+}
+
+impl fmt::Display for Key {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let tonic = match self.mode {
+			Mode::Minor => self.tonic.to_ascii_lowercase(),
+			_ => self.tonic,
+		};
+		write!(f, "{}{}", tonic, self.accidental_code())?;
+		if let Some(suffix) = self.mode.suffix() {
+			write!(f, ".{}", suffix)?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for Key {
+	type Err = KeyError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let error = |message: &str| KeyError {
+			input: s.to_string(),
+			message: message.to_string(),
+		};
+		let (main, suffix) = match s.trim().split_once('.') {
+			Some((main, suffix)) => (main, Some(suffix)),
+			None => (s.trim(), None),
+		};
+		let mut chars = main.chars();
+		let letter = chars
+			.next()
+			.filter(|c| matches!(c.to_ascii_uppercase(), 'A'..='G'))
+			.ok_or_else(|| error("expected a note letter A-G"))?;
+		let accidental = match chars.as_str() {
+			"" => 0,
+			"#" | "♯" => 1,
+			"##" | "x" | "X" | "𝄪" => 2,
+			"b" | "♭" => -1,
+			"bb" | "𝄫" => -2,
+			_ => return Err(error("expected #, b, ## or bb after the note letter")),
+		};
+		let mode = match suffix {
+			None if letter.is_ascii_lowercase() => Mode::Minor,
+			None => Mode::Major,
+			Some(suffix) => Mode::CHURCH
+				.iter()
+				.find(|(_, code, _)| suffix.eq_ignore_ascii_case(code))
+				.map(|(mode, _, _)| *mode)
+				.ok_or_else(|| error("expected mode dor, phr, lyd, mix or loc"))?,
+		};
+		Ok(Key {
+			tonic: letter.to_ascii_uppercase(),
+			accidental,
+			mode,
+		})
+	}
+}
+
+/// A `--key` filter: a key (`D`, `c#`), a tonic in any mode (`D.*`), or a
+/// mode alone (`minor`, `dorian`, `modal`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyQuery {
+	Key(Key),
+	Tonic(Key),
+	Mode(Mode),
+	Modal,
+}
+
+impl KeyQuery {
+	/// Spelling never matters: `--key Db` finds works in C♯.
+	pub fn matches(&self, key: &Key) -> bool {
+		match self {
+			KeyQuery::Key(wanted) => wanted.enharmonic(key),
+			KeyQuery::Tonic(wanted) => wanted.same_tonic(key),
+			KeyQuery::Mode(mode) => key.mode == *mode,
+			KeyQuery::Modal => key.mode.is_church_mode(),
+		}
+	}
+}
+
+impl FromStr for KeyQuery {
+	type Err = KeyError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		if s.eq_ignore_ascii_case("modal") {
+			return Ok(KeyQuery::Modal);
+		}
+		if let Some(mode) = Mode::from_name(s) {
+			return Ok(KeyQuery::Mode(mode));
+		}
+		if let Some(tonic) = s.strip_suffix(".*") {
+			// Case marks the mode in a key code, so it means nothing here.
+			let mut chars = tonic.chars();
+			let tonic: String = chars.next().map(|c| c.to_ascii_uppercase()).into_iter().chain(chars).collect();
+			return tonic.parse().map(KeyQuery::Tonic);
+		}
+		s.parse().map(KeyQuery::Key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn key(code: &str) -> Key {
+		code.parse().unwrap()
+	}
+
+	#[test]
+	fn parses_and_prints_key_codes() {
+		assert_eq!(key("f#"), Key { tonic: 'F', accidental: 1, mode: Mode::Minor });
+		assert_eq!(key("bb"), Key { tonic: 'B', accidental: -1, mode: Mode::Minor });
+		assert_eq!(key("E.PHR").mode, Mode::Phrygian);
+		assert_eq!(key("Cx").accidental, 2);
+		assert_eq!(key("F♯"), key("F#"));
+		assert_eq!(key("b♭"), key("bb"));
+		assert_eq!(key("C𝄪"), key("C##"));
+		assert_eq!(key("E𝄫.dor"), key("Ebb.dor"));
+		for code in ["C", "f#", "Bb", "E.phr", "D.dor", "Cbb"] {
+			assert_eq!(key(code).to_string(), code);
+		}
+		for code in ["H", "C#b", "d.ionian", "d.", "", "D minor"] {
+			assert!(code.parse::<Key>().is_err(), "{}", code);
+		}
+	}
+
+	#[test]
+	fn relates_keys() {
+		assert!(key("C#").enharmonic(&key("Db")));
+		assert!(!key("C#").enharmonic(&key("c#")));
+		assert!(key("D").same_tonic(&key("d.dor")));
+		assert!(key("C").relative(&key("a")));
+		assert!(key("a").relative(&key("d.dor")));
+		assert!(key("Eb").relative(&key("c")));
+		assert!(!key("C").relative(&key("C")));
+		assert!(key("C").parallel(&key("c")));
+		assert!(!key("C").parallel(&key("a")));
+	}
+
+	#[test]
+	fn queries_match_across_spellings_and_modes() {
+		let query = |q: &str| q.parse::<KeyQuery>().unwrap();
+		assert!(query("Db").matches(&key("C#")));
+		assert!(!query("D").matches(&key("d")));
+		assert!(query("d.*").matches(&key("D")));
+		assert!(query("D.*").matches(&key("d.dor")));
+		assert!(query("cb.*").matches(&key("b")));
+		assert!(query("minor").matches(&key("g#")));
+		assert!(!query("minor").matches(&key("e.phr")));
+		assert!(query("modal").matches(&key("e.phr")));
+		assert!(query("Phrygian").matches(&key("e.phr")));
+		assert!("H".parse::<KeyQuery>().is_err());
+	}
+}
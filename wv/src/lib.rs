@@ -2,15 +2,27 @@ pub mod add;
 pub mod catalog;
 pub mod commands;
 pub mod config;
+pub mod date;
+pub mod dupes;
+pub mod duration;
 pub mod display;
+pub mod form;
+pub mod format;
+pub mod incipit;
 pub mod index;
+pub mod instrument;
 pub mod inventory;
+pub mod key;
+pub mod lint;
 pub mod merge;
+pub mod movement;
+pub mod occasion;
 pub mod output;
 pub mod parse;
 pub mod query;
 pub mod types;
 pub mod validate;
+pub mod validation_cache;
 pub mod xref;
 
 pub use add::{
@@ -18,30 +30,54 @@ pub use add::{
 };
 pub use catalog::{
 	clear_catalog_cache, is_fallback_key, load_catalog_def, looks_like_group, matches_group,
-	merge_catalog_definitions, normalize_catalog_number, sort_key, sort_numbers,
+	merge_catalog_definitions, normalize_catalog_number, normalize_input, sort_key, sort_numbers,
 	sort_numbers_by_scheme, CatalogLoadError, SortValue,
 };
 pub use config::{
 	is_data_dir, resolve_data_dir, resolve_editor, Config, DataDirError, DisplayConfig, KeySymbols,
 	PatternConfig, XrefConfig,
 };
+pub use date::{DateError, DateValue, PartialDate};
+pub use duration::{node_duration, work_duration, DurationError, DurationValue};
 pub use display::{
-	expand_key, expand_title, format_catalog, format_form, truncate_instrumentation,
+	expand_key, expand_title, format_catalog, format_credits, format_form, truncate_instrumentation,
 	ExpansionContext,
 };
+pub use format::{format_data, format_inventory, format_json, FileKind, FormatError};
+pub use form::{form_taxonomy_path, load_form_taxonomy, FormDefinition, FormTaxonomy, FormTaxonomyError};
+pub use incipit::{
+	contains_intervals, intervals, parse_data, parse_incipit, pitch_sequence, render_svg,
+	render_text, Clef, KeySignature, PaeError, PaeEvent, PaeNote, ParsedIncipit,
+};
+pub use instrument::{
+	format_instrumentation, format_part, instrument_vocabulary_path, load_instrument_vocabulary,
+	parse_instrumentation, InstrumentDefinition, InstrumentVocabulary, InstrumentVocabularyError,
+	ParsedInstrumentation,
+};
 pub use inventory::{build_inventory_index, load_inventory, normalize_inventory, InventoryCatalogIndex, InventoryError, InventoryIndex, InventoryLookup, InventorySchemeIndex};
 pub use index::{
 	build_index, get_or_build_index, index_is_stale, load_edition_index, load_index,
 	mark_index_dirty, save_index, write_composer_index, write_edition_indexes, write_index, write_inventory_index, Index,
-	EditionIndexError, IndexEntry, IndexError, SchemeIndex,
+	EditionIndexError, FirstLineIndexEntry, IndexEntry, IndexError, MovementIndexEntry,
+	RelationIndexEntry, SchemeIndex,
 };
+pub use key::{Key, KeyError, KeyQuery, Mode};
+pub use lint::{apply_fixes, lint_config_path, load_lint_config, Diagnostic, Fix, LintConfig, LintError, Linter, RuleConfig, Severity, RULES};
 pub use merge::{
 	all_catalog_entries, collection_path_from_id, current_catalog_number,
 	current_catalog_number_for_edition, current_composer, merge_attribution, state_as_of,
 	MergedAttribution,
 };
-pub use parse::{load_collection, load_composer, load_composition, ParseError};
+pub use movement::{
+	format_movement_path, movement_at, parse_movement_path, walk_movements, MovementNode,
+	MovementVisit,
+};
+pub use occasion::{easter, occasion_label, occasions_on, previous_sunday};
+pub use parse::{
+	load_chorale, load_collection, load_composer, load_composition, load_person, load_reference,
+	ParseError,
+};
 pub use query::{QueryBuilder, QueryError, QueryResult};
 pub use types::*;
-pub use validate::{validate_all, validate_file, ValidationError, Validator};
+pub use validate::{validate_all, validate_file, ErrorCode, Level, ValidationError, Validator};
 pub use xref::{check_duplicates, MbLookup, MbLookupResult, XrefStats};
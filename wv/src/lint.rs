@@ -0,0 +1,635 @@
+//! House-style checks for composition files, beyond what `validate`
+//! requires. Rules are named, carry a severity that `.wvlint.toml` in the
+//! data directory can change, and some offer a fix `wv lint --fix` applies.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::catalog::normalize_catalog_number;
+use crate::config::DisplayConfig;
+use crate::display::{expand_title, ExpansionContext};
+use crate::form::{edit_distance, load_form_taxonomy, FormTaxonomy, FormTaxonomyError};
+use crate::instrument::{load_instrument_vocabulary, parse_instrumentation, InstrumentVocabulary, InstrumentVocabularyError};
+use crate::movement::walk_movements;
+use crate::types::{Composition, InstrumentPart, Instrumentation};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+	Off,
+	Warning,
+	Error,
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Severity::Off => "off",
+			Severity::Warning => "warning",
+			Severity::Error => "error",
+		})
+	}
+}
+
+/// Rule IDs, default severities, and what each checks.
+pub const RULES: [(&str, Severity, &str); 7] = [
+	("title-case", Severity::Warning, "movement titles start with a capital and are not all capitals"),
+	("form-vocabulary", Severity::Warning, "forms use the canonical names in taxonomy/forms.json"),
+	("instrumentation-order", Severity::Warning, "voices come first, in soprano-alto-tenor-bass order, and continuo last"),
+	("instrumentation-abbreviation", Severity::Warning, "instrument names and counts are written out in full"),
+	("redundant-title", Severity::Warning, "a title is not given when the generated title would read the same"),
+	("catalog-number", Severity::Warning, "catalog numbers are stored normalized (lower case)"),
+	("missing-key", Severity::Warning, "sonatas and symphonies state their key"),
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+	pub severity: Option<Severity>,
+	/// For `missing-key`: the forms (with their subforms) that need a key.
+	pub forms: Option<Vec<String>>,
+}
+
+/// `.wvlint.toml`: per-rule settings under `[rules.<id>]`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintConfig {
+	#[serde(default)]
+	pub rules: BTreeMap<String, RuleConfig>,
+}
+
+#[derive(Error, Debug)]
+pub enum LintError {
+	#[error("failed to read {path}: {message}")]
+	Io { path: PathBuf, message: String },
+	#[error("invalid lint config {path}: {message}")]
+	Config { path: PathBuf, message: String },
+	#[error(transparent)]
+	Forms(#[from] FormTaxonomyError),
+	#[error(transparent)]
+	Instruments(#[from] InstrumentVocabularyError),
+}
+
+pub fn lint_config_path<P: AsRef<Path>>(data_dir: P) -> PathBuf {
+	data_dir.as_ref().join(".wvlint.toml")
+}
+
+/// The dataset's lint settings; defaults when it has no `.wvlint.toml`.
+pub fn load_lint_config<P: AsRef<Path>>(data_dir: P) -> Result<LintConfig, LintError> {
+	let path = lint_config_path(data_dir);
+	let content = match fs::read_to_string(&path) {
+		Ok(content) => content,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(LintConfig::default()),
+		Err(error) => return Err(LintError::Io { path, message: error.to_string() }),
+	};
+	let config: LintConfig = toml::from_str(&content)
+		.map_err(|error| LintError::Config { path: path.clone(), message: error.to_string() })?;
+	if let Some(unknown) = config.rules.keys().find(|id| !RULES.iter().any(|(rule, _, _)| rule == id)) {
+		return Err(LintError::Config { path, message: format!("unknown rule '{}'", unknown) });
+	}
+	Ok(config)
+}
+
+/// A rewrite of one JSON member, e.g. `"form": "church cantata"` to
+/// `"form": "sacred cantata"`, applied to the file text so key order and
+/// layout survive.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+	pub from: String,
+	pub to: String,
+}
+
+impl Fix {
+	fn member(key: &str, from: &str, to: &str) -> Self {
+		let member = |value: &str| format!("\"{}\": {}", key, serde_json::Value::from(value));
+		Fix { from: member(from), to: member(to) }
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+	pub path: String,
+	pub rule: &'static str,
+	pub severity: Severity,
+	pub message: String,
+	pub fix: Option<Fix>,
+}
+
+impl fmt::Display for Diagnostic {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}: {}[{}]: {}", self.path, self.severity, self.rule, self.message)?;
+		if self.fix.is_some() {
+			write!(f, " (fixable)")?;
+		}
+		Ok(())
+	}
+}
+
+/// Apply fixes to a file's text, each to one occurrence of its member, so
+/// that two diagnostics with the same fix rewrite two members. Returns the
+/// new text and how many fixes found their target.
+pub fn apply_fixes(content: &str, diagnostics: &[Diagnostic]) -> (String, usize) {
+	let mut content = content.to_string();
+	let mut applied = 0;
+	for fix in diagnostics.iter().filter_map(|diagnostic| diagnostic.fix.as_ref()) {
+		if content.contains(&fix.from) {
+			content = content.replacen(&fix.from, &fix.to, 1);
+			applied += 1;
+		}
+	}
+	(content, applied)
+}
+
+pub struct Linter {
+	config: LintConfig,
+	forms: Option<Rc<FormTaxonomy>>,
+	instruments: Option<Rc<InstrumentVocabulary>>,
+	display: DisplayConfig,
+}
+
+impl Linter {
+	pub fn new<P: AsRef<Path>>(data_dir: P, display: &DisplayConfig) -> Result<Self, LintError> {
+		let data_dir = data_dir.as_ref();
+		Ok(Self {
+			config: load_lint_config(data_dir)?,
+			forms: load_form_taxonomy(data_dir)?,
+			instruments: load_instrument_vocabulary(data_dir)?,
+			display: display.clone(),
+		})
+	}
+
+	fn severity(&self, rule: &str) -> Severity {
+		self.config
+			.rules
+			.get(rule)
+			.and_then(|config| config.severity)
+			.or_else(|| RULES.iter().find(|(id, _, _)| *id == rule).map(|(_, severity, _)| *severity))
+			.unwrap_or(Severity::Off)
+	}
+
+	pub fn lint_composition(&self, comp: &Composition, path_str: &str) -> Vec<Diagnostic> {
+		let mut findings: Vec<(&'static str, String, Option<Fix>)> = Vec::new();
+		findings.extend(lint_title_case(comp));
+		findings.extend(self.lint_forms(comp));
+		findings.extend(lint_instrumentation_order(comp, self.instruments.as_deref()));
+		findings.extend(lint_instrumentation_abbreviations(comp));
+		findings.extend(self.lint_redundant_title(comp));
+		findings.extend(lint_catalog_numbers(comp));
+		findings.extend(self.lint_missing_key(comp));
+
+		findings
+			.into_iter()
+			.filter_map(|(rule, message, fix)| {
+				let severity = self.severity(rule);
+				(severity != Severity::Off).then(|| Diagnostic {
+					path: path_str.to_string(),
+					rule,
+					severity,
+					message,
+					fix,
+				})
+			})
+			.collect()
+	}
+
+	fn lint_forms(&self, comp: &Composition) -> Vec<(&'static str, String, Option<Fix>)> {
+		let Some(forms) = &self.forms else {
+			return Vec::new();
+		};
+		let named = std::iter::once(("form".to_string(), comp.form.as_str())).chain(
+			walk_movements(comp).into_iter().filter_map(|visit| {
+				let form = visit.node.form()?;
+				Some((format!("{}.form", visit.location), form))
+			}),
+		);
+
+		let mut findings = Vec::new();
+		for (location, form) in named {
+			// Compounds like "aria e coro" are left as written: swapping one
+			// part for its canonical name would mix languages.
+			let parts = forms.parts(form);
+			for part in &parts {
+				match forms.resolve(part) {
+					Some(name) if name != *part && parts.len() == 1 => findings.push((
+						"form-vocabulary",
+						format!("{}: '{}' should be written '{}'", location, form, name),
+						is_spelling_variant(form, name).then(|| Fix::member("form", form, name)),
+					)),
+					Some(_) => {}
+					None => findings.push((
+						"form-vocabulary",
+						format!("{}: '{}' is not in the form taxonomy", location, part),
+						None,
+					)),
+				}
+			}
+		}
+		findings
+	}
+
+	fn lint_redundant_title(&self, comp: &Composition) -> Vec<(&'static str, String, Option<Fix>)> {
+		let Some(titles) = &comp.title else {
+			return Vec::new();
+		};
+		let untitled = Composition { title: None, ..comp.clone() };
+		let mut languages: Vec<&String> = titles.keys().collect();
+		languages.sort();
+		languages
+			.into_iter()
+			.filter_map(|language| {
+				let display = DisplayConfig { language: language.clone(), ..self.display.clone() };
+				let generated = expand_title(&ExpansionContext {
+					composition: &untitled,
+					collection: None,
+					position_in_collection: None,
+					config: &display,
+					forms: self.forms.as_deref(),
+					instruments: self.instruments.as_deref(),
+				});
+				(titles[language] == generated).then(|| {
+					(
+						"redundant-title",
+						format!("title.{}: '{}' is what the title pattern already gives", language, generated),
+						None,
+					)
+				})
+			})
+			.collect()
+	}
+
+	fn lint_missing_key(&self, comp: &Composition) -> Vec<(&'static str, String, Option<Fix>)> {
+		if comp.key.is_some() {
+			return Vec::new();
+		}
+		let configured = self.config.rules.get("missing-key").and_then(|config| config.forms.clone());
+		let wanted = configured.unwrap_or_else(|| vec!["sonata".into(), "symphony".into()]);
+		let needs_key = wanted.iter().any(|form| match &self.forms {
+			Some(forms) => forms.is_a(&comp.form, form),
+			None => comp.form.eq_ignore_ascii_case(form),
+		});
+		if !needs_key {
+			return Vec::new();
+		}
+		vec![("missing-key", format!("key: a {} should state its key", comp.form), None)]
+	}
+}
+
+/// Whether `variant` is `name` spelled differently rather than another word
+/// for it: the same number of words, each differing at most in case or, for
+/// longer words, two letters ("Recitativo", "choral"). Synonyms such as
+/// "tutti" for "chorus" or "basso solo" for "solo" say more than the
+/// canonical name, so they are reported but not rewritten.
+fn is_spelling_variant(variant: &str, name: &str) -> bool {
+	let variant = variant.to_lowercase();
+	let name = name.to_lowercase();
+	let words: Vec<&str> = variant.split_whitespace().collect();
+	let names: Vec<&str> = name.split_whitespace().collect();
+	words.len() == names.len()
+		&& words.iter().zip(&names).all(|(word, name)| {
+			let limit = if word.chars().count().min(name.chars().count()) >= 4 { 2 } else { 0 };
+			edit_distance(word, name) <= limit
+		})
+}
+
+fn lint_title_case(comp: &Composition) -> Vec<(&'static str, String, Option<Fix>)> {
+	walk_movements(comp)
+		.into_iter()
+		.filter_map(|visit| {
+			let title = visit.node.title()?;
+			let fixed = sentence_case(title)?;
+			Some((
+				"title-case",
+				format!("{}.title: '{}' should be '{}'", visit.location, title, fixed),
+				Some(Fix::member("title", title, &fixed)),
+			))
+		})
+		.collect()
+}
+
+/// The title with a leading capital, lowering it first if it is shouted in
+/// capitals; `None` when it is already fine. Roman numerals and short
+/// abbreviations are left alone.
+fn sentence_case(title: &str) -> Option<String> {
+	let letters: Vec<char> = title.chars().filter(|c| c.is_alphabetic()).collect();
+	let first = *letters.first()?;
+	let shouted = letters.len() >= 4
+		&& letters.iter().all(|c| c.is_uppercase())
+		&& !letters.iter().all(|c| "IVXLCDM".contains(*c));
+	if !shouted && !first.is_lowercase() {
+		return None;
+	}
+	let lowered = if shouted { title.to_lowercase() } else { title.to_string() };
+	let start = lowered.find(|c: char| c.is_alphabetic())?;
+	let mut chars = lowered[start..].chars();
+	let capital: String = chars.next()?.to_uppercase().collect();
+	Some(format!("{}{}{}", &lowered[..start], capital, chars.as_str()))
+}
+
+/// Parts as structured, or as read from text the parser fully understands
+/// once abbreviations are expanded. Text needs the vocabulary to be read.
+fn instrument_parts(
+	instrumentation: &Instrumentation,
+	vocabulary: Option<&InstrumentVocabulary>,
+) -> Option<Vec<InstrumentPart>> {
+	match instrumentation {
+		Instrumentation::Parts(parts) => Some(parts.clone()),
+		Instrumentation::Text(text) => {
+			let expanded = expand_abbreviations(text).unwrap_or_else(|| text.clone());
+			let parsed = parse_instrumentation(&expanded, vocabulary?);
+			parsed.unrecognized.is_empty().then_some(parsed.parts)
+		}
+	}
+}
+
+/// Voices in score order, then everything else, then continuo.
+fn part_rank(part: &InstrumentPart, vocabulary: Option<&InstrumentVocabulary>) -> usize {
+	const VOICES: [&str; 6] = ["soprano", "alto", "tenor", "bass", "voice", "choir"];
+	if let Some(position) = VOICES.iter().position(|voice| *voice == part.instrument) {
+		return position;
+	}
+	match vocabulary.and_then(|vocabulary| vocabulary.get(&part.instrument)).map(|definition| definition.family.as_str()) {
+		Some("continuo") => VOICES.len() + 1,
+		_ => VOICES.len(),
+	}
+}
+
+fn lint_instrumentation_order(
+	comp: &Composition,
+	vocabulary: Option<&InstrumentVocabulary>,
+) -> Vec<(&'static str, String, Option<Fix>)> {
+	let Some(parts) = comp.instrumentation.as_ref().and_then(|instrumentation| instrument_parts(instrumentation, vocabulary)) else {
+		return Vec::new();
+	};
+	parts
+		.windows(2)
+		.find(|pair| part_rank(&pair[0], vocabulary) > part_rank(&pair[1], vocabulary))
+		.map(|pair| {
+			(
+				"instrumentation-order",
+				format!(
+					"instrumentation: '{}' should come before '{}' (voices first, continuo last)",
+					pair[1].instrument, pair[0].instrument
+				),
+				None,
+			)
+		})
+		.into_iter()
+		.collect()
+}
+
+/// Shorthand found in imported records, with the spelling to use instead.
+const ABBREVIATIONS: [(&str, &str); 32] = [
+	("vn", "violin"),
+	("vns", "violins"),
+	("vln", "violin"),
+	("vlns", "violins"),
+	("va", "viola"),
+	("vas", "violas"),
+	("vla", "viola"),
+	("vlas", "violas"),
+	("vc", "cello"),
+	("vcs", "cellos"),
+	("db", "double bass"),
+	("pf", "piano"),
+	("pno", "piano"),
+	("kbd", "keyboard"),
+	("hpd", "harpsichord"),
+	("org", "organ"),
+	("fl", "flute"),
+	("fls", "flutes"),
+	("ob", "oboe"),
+	("obs", "oboes"),
+	("cl", "clarinet"),
+	("cls", "clarinets"),
+	("bn", "bassoon"),
+	("bns", "bassoons"),
+	("hn", "horn"),
+	("hns", "horns"),
+	("tpt", "trumpet"),
+	("tpts", "trumpets"),
+	("tbn", "trombone"),
+	("tbns", "trombones"),
+	("timp", "timpani"),
+	("bc", "basso continuo"),
+];
+
+const NUMBER_WORDS: [&str; 11] = [
+	"two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve",
+];
+
+/// Free-text instrumentation with abbreviations expanded and counts as
+/// digits; `None` when nothing changes.
+fn expand_abbreviations(text: &str) -> Option<String> {
+	let mut expanded = String::new();
+	let mut rest = text;
+	while !rest.is_empty() {
+		let start = rest.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(rest.len());
+		expanded.push_str(&rest[..start]);
+		rest = &rest[start..];
+		let end = rest.find(|c: char| !c.is_ascii_alphabetic() && c != '.').unwrap_or(rest.len());
+		let word = rest[..end].trim_end_matches('.');
+		// "four-part choir" and "piano four-hands" read better spelled out.
+		let compound = rest[word.len()..].starts_with('-');
+		let replacement = if compound {
+			None
+		} else if word == "b.c" {
+			Some("basso continuo".to_string())
+		} else if let Some((_, full)) = ABBREVIATIONS.iter().find(|(short, _)| *short == word) {
+			Some(full.to_string())
+		} else {
+			NUMBER_WORDS.iter().position(|number| *number == word).map(|i| (i + 2).to_string())
+		};
+		match replacement {
+			Some(replacement) => {
+				expanded.push_str(&replacement);
+				rest = &rest[end..];
+			}
+			None => {
+				// Keep the word but not a sentence-ending period it swallowed.
+				expanded.push_str(word);
+				rest = &rest[word.len()..];
+			}
+		}
+	}
+	(expanded != text).then_some(expanded)
+}
+
+fn lint_instrumentation_abbreviations(comp: &Composition) -> Vec<(&'static str, String, Option<Fix>)> {
+	let Some(Instrumentation::Text(text)) = &comp.instrumentation else {
+		return Vec::new();
+	};
+	expand_abbreviations(text)
+		.map(|expanded| {
+			(
+				"instrumentation-abbreviation",
+				format!("instrumentation: '{}' should be written '{}'", text, expanded),
+				Some(Fix::member("instrumentation", text, &expanded)),
+			)
+		})
+		.into_iter()
+		.collect()
+}
+
+fn lint_catalog_numbers(comp: &Composition) -> Vec<(&'static str, String, Option<Fix>)> {
+	let attributed = comp.attribution.iter().enumerate().flat_map(|(i, entry)| {
+		entry
+			.catalog
+			.iter()
+			.flatten()
+			.enumerate()
+			.map(move |(j, catalog)| (format!("attribution[{}].catalog[{}]", i, j), catalog))
+	});
+	let visits = walk_movements(comp);
+	let movements = visits.iter().flat_map(|visit| {
+		visit
+			.node
+			.catalog()
+			.iter()
+			.enumerate()
+			.map(move |(j, catalog)| (format!("{}.catalog[{}]", visit.location, j), catalog))
+	});
+
+	attributed
+		.chain(movements)
+		.filter_map(|(location, catalog)| {
+			let normalized = normalize_catalog_number(&catalog.number);
+			(normalized != catalog.number).then(|| {
+				(
+					"catalog-number",
+					format!("{}: number '{}' should be stored as '{}'", location, catalog.number, normalized),
+					Some(Fix::member("number", &catalog.number, &normalized)),
+				)
+			})
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::config::Config;
+
+	fn linter(config: &str) -> Linter {
+		Linter {
+			config: toml::from_str(config).unwrap(),
+			forms: Some(Rc::new(serde_json::from_str(r#"{
+				"cantata": {},
+				"sacred cantata": {"parent": "cantata", "synonyms": ["church cantata"]},
+				"sonata": {},
+				"sonatina": {"parent": "sonata", "synonyms": ["sonatine"]},
+				"recitative": {},
+				"chorale": {"synonyms": ["choral"]}
+			}"#).unwrap())),
+			instruments: load_instrument_vocabulary(concat!(env!("CARGO_MANIFEST_DIR"), "/..")).unwrap(),
+			display: Config::default().display,
+		}
+	}
+
+	fn composition(json: &str) -> Composition {
+		serde_json::from_str(json).unwrap()
+	}
+
+	fn messages(diagnostics: &[Diagnostic]) -> Vec<String> {
+		diagnostics.iter().map(|d| format!("{}[{}] {}", d.severity, d.rule, d.message)).collect()
+	}
+
+	#[test]
+	fn reports_style_problems_by_rule() {
+		let comp = composition(r#"{
+			"id": "11111111",
+			"form": "church cantata",
+			"instrumentation": "2 vns, bc, soprano",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "Anh. 5"}]}],
+			"movements": [
+				{"title": "recitativo", "form": "choral"},
+				{"title": "ALLEGRO MA NON TROPPO"},
+				{"title": "Menuet II"}
+			]
+		}"#);
+		assert_eq!(messages(&linter("").lint_composition(&comp, "test")), [
+			"warning[title-case] movements[0].title: 'recitativo' should be 'Recitativo'",
+			"warning[title-case] movements[1].title: 'ALLEGRO MA NON TROPPO' should be 'Allegro ma non troppo'",
+			"warning[form-vocabulary] form: 'church cantata' should be written 'sacred cantata'",
+			"warning[form-vocabulary] movements[0].form: 'choral' should be written 'chorale'",
+			"warning[instrumentation-order] instrumentation: 'soprano' should come before 'continuo' (voices first, continuo last)",
+			"warning[instrumentation-abbreviation] instrumentation: '2 vns, bc, soprano' should be written '2 violins, basso continuo, soprano'",
+			"warning[catalog-number] attribution[0].catalog[0]: number 'Anh. 5' should be stored as 'anh. 5'",
+		]);
+	}
+
+	#[test]
+	fn config_changes_severity_and_options() {
+		let comp = composition(r#"{
+			"id": "11111111",
+			"form": "sonatine",
+			"title": {"en": "Sonatine"},
+			"attribution": [{"composer": "beethoven"}]
+		}"#);
+		let strict = linter(r#"
+			[rules.form-vocabulary]
+			severity = "off"
+			[rules.missing-key]
+			severity = "error"
+		"#);
+		assert_eq!(messages(&strict.lint_composition(&comp, "test")), [
+			"warning[redundant-title] title.en: 'Sonatine' is what the title pattern already gives",
+			"error[missing-key] key: a sonatine should state its key",
+		]);
+
+		let cantatas = linter(r#"
+			[rules.missing-key]
+			forms = ["cantata"]
+			[rules.form-vocabulary]
+			severity = "off"
+		"#);
+		assert_eq!(messages(&cantatas.lint_composition(&comp, "test")), [
+			"warning[redundant-title] title.en: 'Sonatine' is what the title pattern already gives",
+		]);
+	}
+
+	#[test]
+	fn fixes_rewrite_members_in_place() {
+		let content = "{\n\t\"form\": \"sonatine\",\n\t\"movements\": [\n\t\t{\"title\": \"aria\", \"form\": \"choral\"},\n\t\t{\"title\": \"aria\", \"form\": \"choral\"}\n\t]\n}\n";
+		let comp = composition(r#"{"id": "1", "form": "sonatine", "key": "C", "attribution": [], "movements": [
+			{"title": "aria", "form": "choral"},
+			{"title": "aria", "form": "choral"}
+		]}"#);
+		let diagnostics = linter("").lint_composition(&comp, "test");
+		let (fixed, applied) = apply_fixes(content, &diagnostics);
+		assert_eq!(applied, 5);
+		assert_eq!(fixed, "{\n\t\"form\": \"sonatina\",\n\t\"movements\": [\n\t\t{\"title\": \"Aria\", \"form\": \"chorale\"},\n\t\t{\"title\": \"Aria\", \"form\": \"chorale\"}\n\t]\n}\n");
+	}
+
+	#[test]
+	fn synonyms_are_reported_but_not_fixed() {
+		let comp = composition(r#"{"id": "1", "form": "church cantata", "attribution": [], "movements": [{"form": "tutti"}, {"form": "basso solo"}]}"#);
+		let forms = Linter {
+			forms: Some(Rc::new(serde_json::from_str(r#"{
+				"cantata": {},
+				"sacred cantata": {"parent": "cantata", "synonyms": ["church cantata"]},
+				"chorus": {"synonyms": ["tutti", "Chorus"]},
+				"solo": {"synonyms": ["basso solo"]}
+			}"#).unwrap())),
+			..linter("")
+		};
+		let diagnostics = forms.lint_composition(&comp, "test");
+		assert_eq!(diagnostics.len(), 3);
+		assert!(diagnostics.iter().all(|diagnostic| diagnostic.fix.is_none()));
+		assert!(is_spelling_variant("Recitativo", "recitative"));
+		assert!(is_spelling_variant("Chorus", "chorus"));
+		assert!(!is_spelling_variant("coro", "chorus"));
+		assert!(!is_spelling_variant("recitative dialogus", "dialogue"));
+	}
+
+	#[test]
+	fn expands_abbreviations_and_number_words() {
+		assert_eq!(expand_abbreviations("two oboes, vn., b.c.").as_deref(), Some("2 oboes, violin, basso continuo"));
+		assert_eq!(expand_abbreviations("four-part choir (SATB), 2 violins, viola"), None);
+	}
+}
@@ -1,10 +1,12 @@
 use std::path::Path;
 
-use crate::types::{AttributionEntry, CatalogEntry, Dates, Status};
+use crate::types::{AttributionEntry, CatalogEntry, Contributor, Dates, Status};
 
 #[derive(Debug, Clone, Default)]
 pub struct MergedAttribution {
 	pub composer: Option<String>,
+	/// Contributors from the entry the composer came from.
+	pub contributors: Vec<Contributor>,
 	pub dates: Dates,
 	pub status: Option<Status>,
 	pub catalog: Vec<CatalogEntry>,
@@ -36,7 +38,8 @@ pub fn merge_attribution(entries: &[AttributionEntry]) -> MergedAttribution {
 
 	for entry in entries {
 		if result.composer.is_none() {
-			result.composer = entry.composer.clone();
+			result.composer = entry.principal().map(str::to_string);
+			result.contributors = entry.contributors.clone().unwrap_or_default();
 		}
 
 		if let Some(dates) = &entry.dates {
@@ -68,7 +71,7 @@ pub fn collection_path_from_id<P: AsRef<Path>>(collections_dir: P, id: &str) ->
 }
 
 pub fn current_composer(entries: &[AttributionEntry]) -> Option<&str> {
-	entries.iter().find_map(|e| e.composer.as_deref())
+	entries.iter().find_map(AttributionEntry::principal)
 }
 
 pub fn current_catalog_number<'a>(entries: &'a [AttributionEntry], scheme: &str) -> Option<&'a str> {
@@ -108,13 +111,15 @@ pub fn state_as_of(entries: &[AttributionEntry], date: &str) -> Vec<AttributionE
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::date::DateValue;
 
 	#[test]
 	fn test_simple_merge() {
 		let entries = vec![AttributionEntry {
 			composer: Some("mozart".into()),
+			contributors: None,
 			dates: Some(Dates {
-				composed: Some(1783),
+				composed: Some(DateValue::year(1783)),
 				published: None,
 				premiered: None,
 				revised: None,
@@ -126,14 +131,16 @@ mod tests {
 				edition: Some("9".into()),
 				since: None,
 				note: None,
+				sources: None,
 			}]),
 			since: None,
 			note: None,
+			sources: None,
 		}];
 
 		let merged = merge_attribution(&entries);
 		assert_eq!(merged.composer, Some("mozart".into()));
-		assert_eq!(merged.dates.composed, Some(1783));
+		assert_eq!(merged.dates.composed, Some(DateValue::year(1783)));
 		assert_eq!(merged.catalog.len(), 1);
 		assert_eq!(merged.catalog[0].number, "332");
 	}
@@ -143,8 +150,9 @@ mod tests {
 		let entries = vec![
 			AttributionEntry {
 				composer: Some("telemann".into()),
+				contributors: None,
 				dates: Some(Dates {
-					composed: Some(1725),
+					composed: Some(DateValue::year(1725)),
 					published: None,
 					premiered: None,
 					revised: None,
@@ -156,12 +164,15 @@ mod tests {
 					edition: None,
 					since: None,
 					note: None,
+					sources: None,
 				}]),
 				since: Some("2020".into()),
 				note: None,
+				sources: None,
 			},
 			AttributionEntry {
 				composer: Some("bach".into()),
+				contributors: None,
 				dates: None,
 				status: Some(Status::Spurious),
 				catalog: Some(vec![CatalogEntry {
@@ -170,15 +181,17 @@ mod tests {
 					edition: None,
 					since: None,
 					note: None,
+					sources: None,
 				}]),
 				since: None,
 				note: None,
+				sources: None,
 			},
 		];
 
 		let merged = merge_attribution(&entries);
 		assert_eq!(merged.composer, Some("telemann".into()));
-		assert_eq!(merged.dates.composed, Some(1725));
+		assert_eq!(merged.dates.composed, Some(DateValue::year(1725)));
 		assert_eq!(merged.catalog.len(), 2);
 	}
 
@@ -187,46 +200,71 @@ mod tests {
 		let entries = vec![
 			AttributionEntry {
 				composer: None,
+				contributors: None,
 				dates: Some(Dates {
-					composed: Some(1725),
+					composed: Some(DateValue::year(1725)),
 					..Default::default()
 				}),
 				status: None,
 				catalog: None,
 				since: None,
 				note: None,
+				sources: None,
 			},
 			AttributionEntry {
 				composer: Some("bach".into()),
+				contributors: None,
 				dates: None,
 				status: None,
 				catalog: None,
 				since: None,
 				note: None,
+				sources: None,
 			},
 		];
 
 		assert_eq!(current_composer(&entries), Some("bach"));
 	}
 
+	#[test]
+	fn test_current_composer_from_contributors() {
+		let entries: Vec<AttributionEntry> = serde_json::from_str(r#"[{
+			"contributors": [
+				{"composer": "haydn", "role": "arranger"},
+				{"composer": "pleyel", "role": "co-composer"},
+				{"composer": "beethoven", "role": "co-composer"}
+			]
+		}]"#).unwrap();
+
+		assert_eq!(current_composer(&entries), Some("pleyel"));
+		let merged = merge_attribution(&entries);
+		assert_eq!(merged.composer.as_deref(), Some("pleyel"));
+		assert_eq!(merged.contributors.len(), 3);
+		assert_eq!(entries[0].credited().collect::<Vec<_>>(), ["haydn", "pleyel", "beethoven"]);
+	}
+
 	#[test]
 	fn test_state_as_of() {
 		let entries = vec![
 			AttributionEntry {
 				composer: Some("telemann".into()),
+				contributors: None,
 				dates: None,
 				status: None,
 				catalog: None,
 				since: Some("2020".into()),
 				note: None,
+				sources: None,
 			},
 			AttributionEntry {
 				composer: Some("bach".into()),
+				contributors: None,
 				dates: None,
 				status: None,
 				catalog: None,
 				since: Some("1950".into()),
 				note: None,
+				sources: None,
 			},
 		];
 
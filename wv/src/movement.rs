@@ -0,0 +1,208 @@
+use crate::duration::DurationValue;
+use crate::types::{CatalogEntry, Composition, Incipit, Movement, Section, SungText};
+
+/// A movement or a section. The two types differ only in which child lists
+/// they allow, so most readers want to treat them alike.
+#[derive(Debug, Clone, Copy)]
+pub enum MovementNode<'a> {
+	Movement(&'a Movement),
+	Section(&'a Section),
+}
+
+impl<'a> MovementNode<'a> {
+	pub fn title(self) -> Option<&'a str> {
+		match self {
+			Self::Movement(m) => m.title.as_deref(),
+			Self::Section(s) => s.title.as_deref(),
+		}
+	}
+
+	pub fn form(self) -> Option<&'a str> {
+		match self {
+			Self::Movement(m) => m.form.as_deref(),
+			Self::Section(s) => s.form.as_deref(),
+		}
+	}
+
+	pub fn key(self) -> Option<&'a str> {
+		match self {
+			Self::Movement(m) => m.key.as_deref(),
+			Self::Section(s) => s.key.as_deref(),
+		}
+	}
+
+	pub fn text(self) -> Option<&'a SungText> {
+		match self {
+			Self::Movement(m) => m.text.as_ref(),
+			Self::Section(s) => s.text.as_ref(),
+		}
+	}
+
+	pub fn duration(self) -> Option<DurationValue> {
+		match self {
+			Self::Movement(m) => m.duration,
+			Self::Section(s) => s.duration,
+		}
+	}
+
+	pub fn chorale(self) -> Option<&'a str> {
+		match self {
+			Self::Movement(m) => m.chorale.as_deref(),
+			Self::Section(s) => s.chorale.as_deref(),
+		}
+	}
+
+	pub fn incipit(self) -> Option<&'a Incipit> {
+		match self {
+			Self::Movement(m) => m.incipit.as_ref(),
+			Self::Section(s) => s.incipit.as_ref(),
+		}
+	}
+
+	pub fn catalog(self) -> &'a [CatalogEntry] {
+		let catalog = match self {
+			Self::Movement(m) => m.catalog.as_deref(),
+			Self::Section(s) => s.catalog.as_deref(),
+		};
+		catalog.unwrap_or_default()
+	}
+
+	/// Title, falling back to form, as printed by `--movements`.
+	pub fn label(self) -> &'a str {
+		self.title().or(self.form()).unwrap_or("?")
+	}
+
+	pub(crate) fn children(self) -> (Option<&'a [Movement]>, Option<&'a [Section]>) {
+		match self {
+			Self::Movement(m) => (None, m.sections.as_deref()),
+			Self::Section(s) => (s.movements.as_deref(), s.sections.as_deref()),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct MovementVisit<'a> {
+	/// 1-based position at each level.
+	pub path: Vec<usize>,
+	/// JSON location, e.g. `sections[0].movements[2]`.
+	pub location: String,
+	pub node: MovementNode<'a>,
+}
+
+/// Every movement and section in document order. Positions count `movements`
+/// before `sections` at each level; no record uses both lists on one node, so
+/// in practice a path matches the numbering `--movements` prints.
+pub fn walk_movements(comp: &Composition) -> Vec<MovementVisit<'_>> {
+	let mut visits = Vec::new();
+	walk_level(
+		comp.movements.as_deref(),
+		comp.sections.as_deref(),
+		&[],
+		"",
+		&mut visits,
+	);
+	visits
+}
+
+fn walk_level<'a>(
+	movements: Option<&'a [Movement]>,
+	sections: Option<&'a [Section]>,
+	parent_path: &[usize],
+	parent_location: &str,
+	visits: &mut Vec<MovementVisit<'a>>,
+) {
+	let nodes = movements
+		.unwrap_or_default()
+		.iter()
+		.enumerate()
+		.map(|(i, m)| (format!("movements[{}]", i), MovementNode::Movement(m)))
+		.chain(
+			sections
+				.unwrap_or_default()
+				.iter()
+				.enumerate()
+				.map(|(i, s)| (format!("sections[{}]", i), MovementNode::Section(s))),
+		);
+
+	for (position, (field, node)) in nodes.enumerate() {
+		let mut path = parent_path.to_vec();
+		path.push(position + 1);
+		let location = if parent_location.is_empty() {
+			field
+		} else {
+			format!("{}.{}", parent_location, field)
+		};
+		visits.push(MovementVisit {
+			path: path.clone(),
+			location: location.clone(),
+			node,
+		});
+		let (child_movements, child_sections) = node.children();
+		walk_level(child_movements, child_sections, &path, &location, visits);
+	}
+}
+
+pub fn movement_at<'a>(comp: &'a Composition, path: &[usize]) -> Option<MovementNode<'a>> {
+	walk_movements(comp)
+		.into_iter()
+		.find(|visit| visit.path == path)
+		.map(|visit| visit.node)
+}
+
+pub fn format_movement_path(path: &[usize]) -> String {
+	path.iter()
+		.map(ToString::to_string)
+		.collect::<Vec<_>>()
+		.join(".")
+}
+
+/// Inverse of `format_movement_path`: "2.5" is the fifth item under the second.
+pub fn parse_movement_path(path: &str) -> Option<Vec<usize>> {
+	path.split('.')
+		.map(|part| part.parse().ok().filter(|n| *n > 0))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn passion() -> Composition {
+		serde_json::from_str(r#"{
+			"id": "8c3d6f2a",
+			"form": "sacred oratorio",
+			"attribution": [{"composer": "bach"}],
+			"sections": [
+				{
+					"title": "Part I",
+					"movements": [
+						{"title": "Kommt, ihr Töchter", "form": "chorus"},
+						{"form": "recitative", "sections": [
+							{"title": "Ja nicht auf das Fest", "form": "chorus"}
+						]}
+					]
+				},
+				{"title": "Part II"}
+			]
+		}"#).unwrap()
+	}
+
+	#[test]
+	fn walk_assigns_nested_paths_and_locations() {
+		let comp = passion();
+		let visits = walk_movements(&comp);
+		let paths: Vec<String> = visits.iter().map(|v| format_movement_path(&v.path)).collect();
+		assert_eq!(paths, ["1", "1.1", "1.2", "1.2.1", "2"]);
+		assert_eq!(visits[3].location, "sections[0].movements[1].sections[0]");
+		assert_eq!(visits[3].node.label(), "Ja nicht auf das Fest");
+	}
+
+	#[test]
+	fn movement_at_resolves_path() {
+		let comp = passion();
+		assert_eq!(movement_at(&comp, &[1, 2]).map(|n| n.label()), Some("recitative"));
+		assert!(movement_at(&comp, &[3]).is_none());
+		assert_eq!(parse_movement_path("1.2"), Some(vec![1, 2]));
+		assert_eq!(parse_movement_path("1.0"), None);
+	}
+}
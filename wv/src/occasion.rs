@@ -0,0 +1,274 @@
+//! Occasions of the Lutheran church year, named as in the Bach cantata
+//! literature, and the calendar dates they fall on.
+
+use crate::date::PartialDate;
+
+/// Days named by their distance from Easter Sunday.
+const EASTER_FEASTS: [(&str, &str, i64); 24] = [
+	("septuagesima", "Septuagesima", -63),
+	("sexagesima", "Sexagesima", -56),
+	("estomihi", "Estomihi", -49),
+	("invocavit", "Invocavit", -42),
+	("reminiscere", "Reminiscere", -35),
+	("oculi", "Oculi", -28),
+	("laetare", "Laetare", -21),
+	("judica", "Judica", -14),
+	("palm-sunday", "Palm Sunday", -7),
+	("good-friday", "Good Friday", -2),
+	("easter", "Easter Sunday", 0),
+	("easter-monday", "Easter Monday", 1),
+	("easter-tuesday", "Easter Tuesday", 2),
+	("quasimodogeniti", "Quasimodogeniti", 7),
+	("misericordias-domini", "Misericordias Domini", 14),
+	("jubilate", "Jubilate", 21),
+	("cantate", "Cantate", 28),
+	("rogate", "Rogate", 35),
+	("ascension", "Ascension", 39),
+	("exaudi", "Exaudi", 42),
+	("pentecost", "Pentecost", 49),
+	("pentecost-monday", "Pentecost Monday", 50),
+	("pentecost-tuesday", "Pentecost Tuesday", 51),
+	("trinity", "Trinity Sunday", 56),
+];
+
+/// Feasts on a fixed month and day.
+const FIXED_FEASTS: [(&str, &str, u8, u8); 11] = [
+	("new-year", "New Year's Day", 1, 1),
+	("epiphany", "Epiphany", 1, 6),
+	("purification", "Purification of Mary", 2, 2),
+	("annunciation", "Annunciation", 3, 25),
+	("john-baptist", "St John the Baptist", 6, 24),
+	("visitation", "Visitation of Mary", 7, 2),
+	("michaelmas", "St Michael", 9, 29),
+	("reformation", "Reformation Day", 10, 31),
+	("christmas", "Christmas Day", 12, 25),
+	("christmas-2", "Second Day of Christmas", 12, 26),
+	("christmas-3", "Third Day of Christmas", 12, 27),
+];
+
+/// Sundays without a feast of their own, and occasions with no fixed date.
+const OTHER_OCCASIONS: [(&str, &str); 5] = [
+	("sunday-after-christmas", "Sunday after Christmas"),
+	("sunday-after-new-year", "Sunday after New Year"),
+	("council-election", "Council election"),
+	("wedding", "Wedding"),
+	("funeral", "Funeral"),
+];
+
+/// Numbered Sundays: prefix, separator, highest number, and label suffix.
+const NUMBERED_SUNDAYS: [(&str, char, u32, &str); 3] = [
+	("advent", '-', 4, "Sunday of Advent"),
+	("epiphany", '+', 6, "Sunday after Epiphany"),
+	("trinity", '+', 27, "Sunday after Trinity"),
+];
+
+fn ordinal(n: u32) -> String {
+	let suffix = match (n % 10, n % 100) {
+		(_, 11..=13) => "th",
+		(1, _) => "st",
+		(2, _) => "nd",
+		(3, _) => "rd",
+		_ => "th",
+	};
+	format!("{}{}", n, suffix)
+}
+
+/// Readable name for an occasion slug, or `None` if it is not in the
+/// vocabulary.
+pub fn occasion_label(slug: &str) -> Option<String> {
+	let named = EASTER_FEASTS
+		.iter()
+		.map(|(slug, label, _)| (*slug, *label))
+		.chain(FIXED_FEASTS.iter().map(|(slug, label, _, _)| (*slug, *label)))
+		.chain(OTHER_OCCASIONS.iter().copied())
+		.find(|(candidate, _)| *candidate == slug);
+	if let Some((_, label)) = named {
+		return Some(label.to_string());
+	}
+	NUMBERED_SUNDAYS.iter().find_map(|(prefix, separator, max, label)| {
+		let n: u32 = slug.strip_prefix(prefix)?.strip_prefix(*separator)?.parse().ok()?;
+		// Reject "trinity+04" and the like, so each Sunday has one spelling.
+		(n >= 1 && n <= *max && !slug.ends_with(&format!("{}0{}", separator, n)))
+			.then(|| format!("{} {}", ordinal(n), label))
+	})
+}
+
+/// Days since 1970-01-01 in the proleptic Gregorian calendar.
+fn day_number(year: i32, month: u8, day: u8) -> i64 {
+	let (year, month) = if month <= 2 {
+		(year as i64 - 1, month as i64 + 9)
+	} else {
+		(year as i64, month as i64 - 3)
+	};
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * month + 2) / 5 + day as i64 - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146_097 + day_of_era - 719_468
+}
+
+fn date_from_day_number(days: i64) -> PartialDate {
+	let days = days + 719_468;
+	let era = days.div_euclid(146_097);
+	let day_of_era = days - era * 146_097;
+	let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+	PartialDate {
+		year: year as i32,
+		month: Some(month as u8),
+		day: Some(day as u8),
+	}
+}
+
+/// 0 for Sunday through 6 for Saturday.
+fn weekday(days: i64) -> i64 {
+	(days + 4).rem_euclid(7)
+}
+
+/// Years in which the Protestant German states, which reckoned Easter
+/// astronomically from 1700 to 1775, kept it a week before the Gregorian date.
+const PROTESTANT_EASTER: [(i32, u8, u8); 2] = [(1724, 4, 9), (1744, 3, 29)];
+
+/// Easter Sunday as kept in Lutheran Germany: the Gregorian date (anonymous
+/// Gregorian algorithm), except where [`PROTESTANT_EASTER`] differs.
+pub fn easter(year: i32) -> PartialDate {
+	if let Some(&(_, month, day)) = PROTESTANT_EASTER.iter().find(|(y, _, _)| *y == year) {
+		return PartialDate {
+			year,
+			month: Some(month),
+			day: Some(day),
+		};
+	}
+	let a = year % 19;
+	let b = year / 100;
+	let c = year % 100;
+	let d = b / 4;
+	let e = b % 4;
+	let f = (b + 8) / 25;
+	let g = (b - f + 1) / 3;
+	let h = (19 * a + b - d - g + 15) % 30;
+	let i = c / 4;
+	let k = c % 4;
+	let l = (32 + 2 * e + 2 * i - h - k) % 7;
+	let m = (a + 11 * h + 22 * l) / 451;
+	let month = (h + l - 7 * m + 114) / 31;
+	let day = (h + l - 7 * m + 114) % 31 + 1;
+	PartialDate {
+		year,
+		month: Some(month as u8),
+		day: Some(day as u8),
+	}
+}
+
+fn easter_day_number(year: i32) -> i64 {
+	let date = easter(year);
+	day_number(year, date.month.unwrap_or(4), date.day.unwrap_or(1))
+}
+
+/// Occasions falling on a date: a feast, a named or numbered Sunday, or both
+/// (e.g. Annunciation on Palm Sunday). Empty for ordinary weekdays.
+pub fn occasions_on(year: i32, month: u8, day: u8) -> Vec<String> {
+	let today = day_number(year, month, day);
+	let easter = easter_day_number(year);
+	let mut occasions: Vec<String> = Vec::new();
+
+	for (slug, _, offset) in EASTER_FEASTS {
+		if today - easter == offset {
+			occasions.push(slug.into());
+		}
+	}
+	for (slug, _, feast_month, feast_day) in FIXED_FEASTS {
+		if (month, day) == (feast_month, feast_day) {
+			occasions.push(slug.into());
+		}
+	}
+	let on_easter_feast = EASTER_FEASTS.iter().any(|(_, _, offset)| today - easter == *offset);
+	if weekday(today) != 0 || (!occasions.is_empty() && on_easter_feast) {
+		return occasions;
+	}
+
+	let november_27 = day_number(year, 11, 27);
+	let advent = november_27 + (7 - weekday(november_27)) % 7;
+	let epiphany = day_number(year, 1, 6);
+	let trinity = easter + 56;
+	if (advent..advent + 28).contains(&today) {
+		occasions.push(format!("advent-{}", (today - advent) / 7 + 1));
+	} else if month == 12 && day >= 28 {
+		occasions.push("sunday-after-christmas".into());
+	} else if month == 1 && (2..=5).contains(&day) {
+		occasions.push("sunday-after-new-year".into());
+	} else if today > epiphany && today < easter - 63 {
+		occasions.push(format!("epiphany+{}", (today - epiphany - 1) / 7 + 1));
+	} else if today > trinity && today < advent {
+		occasions.push(format!("trinity+{}", (today - trinity) / 7));
+	}
+	occasions
+}
+
+/// The Sunday on or before a date, for placing a weekday in its church week.
+pub fn previous_sunday(year: i32, month: u8, day: u8) -> PartialDate {
+	let today = day_number(year, month, day);
+	date_from_day_number(today - weekday(today))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn computes_gregorian_easter() {
+		let dates: Vec<String> = [1725, 2000, 2024, 2025, 2026, 2038]
+			.into_iter()
+			.map(|year| easter(year).to_string())
+			.collect();
+		assert_eq!(dates, [
+			"1725-04-01",
+			"2000-04-23",
+			"2024-03-31",
+			"2025-04-20",
+			"2026-04-05",
+			"2038-04-25",
+		]);
+	}
+
+	#[test]
+	fn keeps_protestant_easter_where_it_differed() {
+		assert_eq!(easter(1724).to_string(), "1724-04-09");
+		assert_eq!(easter(1744).to_string(), "1744-03-29");
+		assert_eq!(occasions_on(1724, 4, 9), ["easter"]);
+		assert_eq!(occasions_on(1724, 4, 2), ["palm-sunday"]);
+		assert_eq!(occasions_on(1724, 4, 16), ["quasimodogeniti"]);
+	}
+
+	#[test]
+	fn names_sundays_and_feasts() {
+		assert_eq!(occasions_on(2026, 11, 29), ["advent-1"]);
+		assert_eq!(occasions_on(2026, 12, 20), ["advent-4"]);
+		assert_eq!(occasions_on(2026, 6, 28), ["trinity+4"]);
+		assert_eq!(occasions_on(2026, 4, 6), ["easter-monday"]);
+		assert_eq!(occasions_on(2026, 1, 11), ["epiphany+1"]);
+		assert_eq!(occasions_on(2026, 12, 27), ["christmas-3"]);
+		assert_eq!(occasions_on(2025, 12, 28), ["sunday-after-christmas"]);
+		assert_eq!(occasions_on(2029, 3, 25), ["palm-sunday", "annunciation"]);
+		assert!(occasions_on(2026, 7, 1).is_empty());
+		assert_eq!(occasions_on(2026, 5, 31), ["trinity"]);
+		assert_eq!(occasions_on(2026, 6, 7), ["trinity+1"]);
+		assert_eq!(occasions_on(2026, 11, 22), ["trinity+25"]);
+		assert_eq!(previous_sunday(2026, 7, 1).to_string(), "2026-06-28");
+	}
+
+	#[test]
+	fn vocabulary_has_one_spelling_per_occasion() {
+		assert_eq!(occasion_label("trinity+4").as_deref(), Some("4th Sunday after Trinity"));
+		assert_eq!(occasion_label("advent-1").as_deref(), Some("1st Sunday of Advent"));
+		assert_eq!(occasion_label("trinity+22").as_deref(), Some("22nd Sunday after Trinity"));
+		assert_eq!(occasion_label("easter-monday").as_deref(), Some("Easter Monday"));
+		for slug in ["trinity+28", "trinity+04", "advent+1", "epiphany+0", "easter-1"] {
+			assert_eq!(occasion_label(slug), None, "{}", slug);
+		}
+	}
+}
@@ -3,10 +3,18 @@ use std::path::{Path, PathBuf};
 
 use crate::catalog::{load_catalog_def, CatalogLoadError};
 use crate::config::Config;
-use crate::display::{expand_title, format_catalog, ExpansionContext};
-use crate::parse::{load_composition, path_for_id, ParseError};
+use crate::display::{expand_title, format_catalog, format_credits, ExpansionContext};
+use crate::duration::node_duration;
+use crate::form::{load_form_taxonomy, FormTaxonomyError};
+use crate::instrument::{load_instrument_vocabulary, InstrumentVocabularyError};
+use crate::index::Index;
+use crate::merge::current_composer;
+use crate::movement::{format_movement_path, movement_at, parse_movement_path, walk_movements};
+use crate::parse::{load_composition, load_reference, path_for_id, ParseError};
 use crate::query::QueryResult;
-use crate::types::{CatalogDefinition, Composition};
+use crate::types::{
+	CatalogDefinition, CatalogEntry, Composition, SourceRef, Status, SungText, TextSourceKind,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -16,6 +24,10 @@ pub enum OutputError {
 	#[error(transparent)]
 	Catalog(#[from] CatalogLoadError),
 	#[error(transparent)]
+	Forms(#[from] FormTaxonomyError),
+	#[error(transparent)]
+	Instruments(#[from] InstrumentVocabularyError),
+	#[error(transparent)]
 	Json(#[from] serde_json::Error),
 }
 
@@ -71,7 +83,7 @@ pub fn format_id_header(
 ) -> Result<String, OutputError> {
 	if let Some(attr) = comp.attribution.first() {
 		if let Some(cat) = attr.catalog.as_ref().and_then(|c| c.first()) {
-			let catalog_defn = load_catalog_def(data_dir, &cat.scheme, attr.composer.as_deref())?;
+			let catalog_defn = load_catalog_def(data_dir, &cat.scheme, attr.principal())?;
 			return Ok(format_catalog(&cat.scheme, &cat.number, catalog_defn.as_ref()));
 		}
 	}
@@ -83,6 +95,8 @@ pub struct OutputContext<'a> {
 	pub config: &'a Config,
 	pub scheme: Option<&'a str>,
 	pub catalog_defn: Option<&'a CatalogDefinition>,
+	/// With movement listings, include each movement's sung text.
+	pub text: bool,
 }
 
 pub fn output_json(results: &[QueryResult], ctx: &OutputContext) -> Result<(), OutputError> {
@@ -114,7 +128,7 @@ pub fn output_movements(results: &[QueryResult], ctx: &OutputContext) -> Result<
 						let defn = load_catalog_def(
 							ctx.data_dir,
 							scheme,
-							comp.attribution.first().and_then(|a| a.composer.as_deref()),
+							comp.attribution.first().and_then(|a| a.principal()),
 						)?;
 						format_catalog(scheme, number, defn.as_ref())
 					} else {
@@ -126,25 +140,8 @@ pub fn output_movements(results: &[QueryResult], ctx: &OutputContext) -> Result<
 		}
 
 		let prefix = if multi { "  " } else { "" };
-
-		if let Some(movements) = &comp.movements {
-			for (i, movement) in movements.iter().enumerate() {
-				let title = movement
-					.title
-					.as_deref()
-					.or(movement.form.as_deref())
-					.unwrap_or("?");
-				print(&format!("{}{}. {}", prefix, i + 1, title));
-			}
-		} else if let Some(sections) = &comp.sections {
-			for (i, section) in sections.iter().enumerate() {
-				let title = section
-					.title
-					.as_deref()
-					.or(section.form.as_deref())
-					.unwrap_or("?");
-				print(&format!("{}{}. {}", prefix, i + 1, title));
-			}
+		for line in movement_lines(&comp, ctx.text) {
+			print(&format!("{}{}", prefix, line));
 		}
 
 		if multi {
@@ -154,6 +151,64 @@ pub fn output_movements(results: &[QueryResult], ctx: &OutputContext) -> Result<
 	Ok(())
 }
 
+/// The top-level movements (or sections), numbered, with durations where
+/// known. With `text`, every movement is listed by path, and those with sung
+/// text get a second line.
+fn movement_lines(comp: &Composition, text: bool) -> Vec<String> {
+	let mut lines = Vec::new();
+	for visit in walk_movements(comp) {
+		if !text && visit.path.len() > 1 {
+			continue;
+		}
+		let indent = "  ".repeat(visit.path.len() - 1);
+		let mut line = format!("{}{}. {}", indent, format_movement_path(&visit.path), visit.node.label());
+		if let Some(duration) = node_duration(visit.node) {
+			line = format!("{} ({})", line, duration);
+		}
+		lines.push(line);
+		if !text {
+			continue;
+		}
+		if let Some(summary) = visit.node.text().and_then(|t| text_summary(t, comp.text.as_ref())) {
+			lines.push(format!("{}   {}", indent, summary));
+		}
+	}
+	lines
+}
+
+fn source_label(kind: TextSourceKind) -> &'static str {
+	match kind {
+		TextSourceKind::Bible => "Bible",
+		TextSourceKind::Chorale => "chorale",
+		TextSourceKind::Liturgy => "liturgy",
+		TextSourceKind::Poem => "poem",
+		TextSourceKind::Libretto => "libretto",
+		TextSourceKind::Other => "source",
+	}
+}
+
+/// `"First line" · Bible: Matthew 26:1–2 · de · picander`, with language,
+/// author and source taken from the work when the movement leaves them out.
+fn text_summary(text: &SungText, work: Option<&SungText>) -> Option<String> {
+	let inherited = |field: fn(&SungText) -> Option<String>| {
+		field(text).or_else(|| work.and_then(field))
+	};
+	let parts: Vec<String> = [
+		text.first_line.as_ref().map(|line| format!("\"{}\"", line)),
+		inherited(|t| {
+			t.source
+				.as_ref()
+				.map(|source| format!("{}: {}", source_label(source.kind), source.reference))
+		}),
+		inherited(|t| t.language.clone()),
+		inherited(|t| t.author.clone()),
+	]
+	.into_iter()
+	.flatten()
+	.collect();
+	(!parts.is_empty()).then(|| parts.join(" · "))
+}
+
 pub fn output_terse(results: &[QueryResult]) {
 	for result in results {
 		print(&result.id);
@@ -161,6 +216,8 @@ pub fn output_terse(results: &[QueryResult]) {
 }
 
 pub fn output_pretty(results: &[QueryResult], ctx: &OutputContext) -> Result<(), OutputError> {
+	let forms = load_form_taxonomy(ctx.data_dir)?;
+	let instruments = load_instrument_vocabulary(ctx.data_dir)?;
 	for result in results {
 		let comp_path = id_to_path(ctx.data_dir, &result.id);
 		let comp = load_required_composition(&comp_path)?;
@@ -169,8 +226,15 @@ pub fn output_pretty(results: &[QueryResult], ctx: &OutputContext) -> Result<(),
 			collection: None,
 			position_in_collection: None,
 			config: &ctx.config.display,
+			forms: forms.as_deref(),
+			instruments: instruments.as_deref(),
 		};
-		let title = expand_title(&expansion_ctx);
+		let mut title = expand_title(&expansion_ctx);
+		if let Some(path) = &result.movement {
+			if let Some(node) = movement_at(&comp, path) {
+				title = format!("{}: {}. {}", title, format_movement_path(path), node.label());
+			}
+		}
 
 		let catalog_str = match (&result.number, ctx.scheme) {
 			(Some(n), Some(s)) => format_catalog(s, n, ctx.catalog_defn),
@@ -179,7 +243,7 @@ pub fn output_pretty(results: &[QueryResult], ctx: &OutputContext) -> Result<(),
 					let defn = load_catalog_def(
 						ctx.data_dir,
 						scheme,
-						comp.attribution.first().and_then(|a| a.composer.as_deref()),
+						comp.attribution.first().and_then(|a| a.principal()),
 					)?;
 					format_catalog(scheme, number, defn.as_ref())
 				} else {
@@ -199,6 +263,7 @@ pub fn output_by_ids(
 	terse: bool,
 	movements: bool,
 	json: bool,
+	text: bool,
 ) -> Result<(), OutputError> {
 	if terse {
 		for id in ids {
@@ -225,21 +290,15 @@ pub fn output_by_ids(
 			let Some(comp) = load_optional_composition(&path)? else {
 				continue;
 			};
-			if let Some(movements) = &comp.movements {
-				for (i, movement) in movements.iter().enumerate() {
-					let title = movement.title.as_deref().or(movement.form.as_deref()).unwrap_or("?");
-					print(&format!("{}. {}", i + 1, title));
-				}
-			} else if let Some(sections) = &comp.sections {
-				for (i, section) in sections.iter().enumerate() {
-					let title = section.title.as_deref().or(section.form.as_deref()).unwrap_or("?");
-					print(&format!("{}. {}", i + 1, title));
-				}
+			for line in movement_lines(&comp, text) {
+				print(&line);
 			}
 		}
 		return Ok(());
 	}
 
+	let forms = load_form_taxonomy(data_dir)?;
+	let instruments = load_instrument_vocabulary(data_dir)?;
 	for id in ids {
 		let comp_path = id_to_path(data_dir, id);
 		if let Some(comp) = load_optional_composition(&comp_path)? {
@@ -248,6 +307,8 @@ pub fn output_by_ids(
 				collection: None,
 				position_in_collection: None,
 				config: &config.display,
+				forms: forms.as_deref(),
+				instruments: instruments.as_deref(),
 			};
 			let title = expand_title(&expansion_ctx);
 			let header = format_id_header(&comp, id, data_dir)?;
@@ -259,6 +320,208 @@ pub fn output_by_ids(
 	Ok(())
 }
 
+fn movement_suffix(comp: &Composition, movement: Option<&str>) -> Option<String> {
+	let path = parse_movement_path(movement?)?;
+	let node = movement_at(comp, &path)?;
+	Some(format!("{}. {}", format_movement_path(&path), node.label()))
+}
+
+/// "Title[: movement], catalog" for a related work, or its bare id if the
+/// file is missing.
+fn describe_related(
+	id: &str,
+	movement: Option<&str>,
+	data_dir: &Path,
+	config: &Config,
+) -> Result<String, OutputError> {
+	let Some(comp) = load_optional_composition(&id_to_path(data_dir, id))? else {
+		return Ok(id.to_string());
+	};
+	let forms = load_form_taxonomy(data_dir)?;
+	let instruments = load_instrument_vocabulary(data_dir)?;
+	let expansion_ctx = ExpansionContext {
+		composition: &comp,
+		collection: None,
+		position_in_collection: None,
+		config: &config.display,
+		forms: forms.as_deref(),
+		instruments: instruments.as_deref(),
+	};
+	let mut title = expand_title(&expansion_ctx);
+	if let Some(suffix) = movement_suffix(&comp, movement) {
+		title = format!("{}: {}", title, suffix);
+	}
+	Ok(format!("{}, {}", title, format_id_header(&comp, id, data_dir)?))
+}
+
+/// Each work followed by the works it declares relations to and, via the
+/// index, the works that declare relations to it.
+pub fn output_related(
+	ids: &[String],
+	index: &Index,
+	data_dir: &Path,
+	config: &Config,
+) -> Result<(), OutputError> {
+	for (i, id) in ids.iter().enumerate() {
+		let Some(comp) = load_optional_composition(&id_to_path(data_dir, id))? else {
+			print(id);
+			continue;
+		};
+		if i > 0 {
+			print("");
+		}
+		print(&describe_related(id, None, data_dir, config)?);
+
+		let mut lines = Vec::new();
+		for relation in comp.relations.iter().flatten() {
+			let target = describe_related(
+				&relation.target,
+				relation.target_movement.as_deref(),
+				data_dir,
+				config,
+			)?;
+			let own = movement_suffix(&comp, relation.movement.as_deref());
+			lines.push((relation.kind.label(), target, own));
+		}
+		for entry in index.relations.get(id).into_iter().flatten() {
+			let source = describe_related(&entry.id, entry.movement.as_deref(), data_dir, config)?;
+			let own = movement_suffix(&comp, entry.target_movement.as_deref());
+			lines.push((entry.kind.inverse_label(), source, own));
+		}
+
+		if lines.is_empty() {
+			print("  (no related works)");
+		}
+		for (label, work, own) in lines {
+			match own {
+				Some(own) => print(&format!("  {}: {} (this work: {})", label, work, own)),
+				None => print(&format!("  {}: {}", label, work)),
+			}
+		}
+	}
+	Ok(())
+}
+
+/// One line per cited source. References that cannot be loaded are shown
+/// by slug; `wv validate` reports them.
+fn source_lines(sources: &[SourceRef], data_dir: &Path) -> Vec<String> {
+	sources
+		.iter()
+		.map(|source| {
+			let path = data_dir.join("references").join(format!("{}.json", source.reference));
+			let mut line = match load_reference(&path) {
+				Ok(reference) => {
+					let mut line = reference.citation();
+					if let Some(rism) = &reference.rism {
+						line.push_str(&format!(" [RISM {}]", rism));
+					}
+					line
+				}
+				Err(_) => format!("{} (not in references/)", source.reference),
+			};
+			if let Some(locator) = &source.locator {
+				line.push_str(&format!(", {}", locator));
+			}
+			if let Some(url) = &source.url {
+				line.push_str(&format!(" <{}>", url));
+			}
+			if let Some(note) = &source.note {
+				line.push_str(&format!(" — {}", note));
+			}
+			line
+		})
+		.collect()
+}
+
+fn catalog_line(
+	entry: &CatalogEntry,
+	composer: Option<&str>,
+	data_dir: &Path,
+) -> Result<String, OutputError> {
+	let defn = load_catalog_def(data_dir, &entry.scheme, composer)?;
+	let mut line = format_catalog(&entry.scheme, &entry.number, defn.as_ref());
+	if let Some(since) = &entry.since {
+		line.push_str(&format!(", since {}", since));
+	}
+	Ok(line)
+}
+
+/// Each work's attribution history, oldest claim last as stored, with the
+/// references cited for each attribution and catalog number.
+pub fn output_sources(ids: &[String], data_dir: &Path, config: &Config) -> Result<(), OutputError> {
+	for (i, id) in ids.iter().enumerate() {
+		let Some(comp) = load_optional_composition(&id_to_path(data_dir, id))? else {
+			print(id);
+			continue;
+		};
+		if i > 0 {
+			print("");
+		}
+		print(&describe_related(id, None, data_dir, config)?);
+
+		let mut cited = 0;
+		for entry in &comp.attribution {
+			let credits = format_credits(entry.composer.as_deref(), entry.contributors.as_deref().unwrap_or_default());
+			let mut line = if credits.is_empty() {
+				"  unknown composer".to_string()
+			} else {
+				format!("  {}", credits)
+			};
+			if let Some(status) = &entry.status {
+				let status = match status {
+					Status::Certain => "certain",
+					Status::Probable => "probable",
+					Status::Doubtful => "doubtful",
+					Status::Spurious => "spurious",
+				};
+				line.push_str(&format!(", {}", status));
+			}
+			if let Some(since) = &entry.since {
+				line.push_str(&format!(", since {}", since));
+			}
+			print(&line);
+
+			let sources = entry.sources.as_deref().unwrap_or_default();
+			cited += sources.len();
+			for source in source_lines(sources, data_dir) {
+				print(&format!("    {}", source));
+			}
+			for catalog in entry.catalog.iter().flatten() {
+				print(&format!("    {}", catalog_line(catalog, entry.principal(), data_dir)?));
+				let sources = catalog.sources.as_deref().unwrap_or_default();
+				cited += sources.len();
+				for source in source_lines(sources, data_dir) {
+					print(&format!("      {}", source));
+				}
+			}
+		}
+
+		let composer = current_composer(&comp.attribution);
+		for visit in walk_movements(&comp) {
+			for catalog in visit.node.catalog() {
+				let Some(sources) = catalog.sources.as_deref() else {
+					continue;
+				};
+				print(&format!(
+					"  {}. {}: {}",
+					format_movement_path(&visit.path),
+					visit.node.label(),
+					catalog_line(catalog, composer, data_dir)?
+				));
+				cited += sources.len();
+				for source in source_lines(sources, data_dir) {
+					print(&format!("    {}", source));
+				}
+			}
+		}
+
+		if cited == 0 {
+			print("  (no sources cited)");
+		}
+	}
+	Ok(())
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -2,7 +2,7 @@ use std::fs;
 use std::path::Path;
 use thiserror::Error;
 
-use crate::types::{Collection, Composer, Composition};
+use crate::types::{ChoraleMelody, Collection, Composer, Composition, Person, Reference};
 
 #[derive(Error, Debug)]
 pub enum ParseError {
@@ -32,6 +32,24 @@ pub fn load_composer<P: AsRef<Path>>(path: P) -> Result<Composer, ParseError> {
 	Ok(composer)
 }
 
+pub fn load_person<P: AsRef<Path>>(path: P) -> Result<Person, ParseError> {
+	let content = fs::read_to_string(path)?;
+	let person: Person = serde_json::from_str(&content)?;
+	Ok(person)
+}
+
+pub fn load_reference<P: AsRef<Path>>(path: P) -> Result<Reference, ParseError> {
+	let content = fs::read_to_string(path)?;
+	let reference: Reference = serde_json::from_str(&content)?;
+	Ok(reference)
+}
+
+pub fn load_chorale<P: AsRef<Path>>(path: P) -> Result<ChoraleMelody, ParseError> {
+	let content = fs::read_to_string(path)?;
+	let chorale: ChoraleMelody = serde_json::from_str(&content)?;
+	Ok(chorale)
+}
+
 pub fn extract_id_from_path<P: AsRef<Path>>(path: P) -> Result<String, ParseError> {
 	let path = path.as_ref();
 	let invalid = || ParseError::InvalidPath(path.display().to_string());
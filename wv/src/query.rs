@@ -3,9 +3,12 @@ use std::path::{Path, PathBuf};
 
 use crate::catalog::{
 	is_fallback_key, load_catalog_def, looks_like_group, matches_group,
-	normalize_catalog_number, sort_key, sort_numbers, CatalogLoadError, SortValue,
+	normalize_input, resolve_scheme, sort_key, sort_numbers, CatalogLoadError, SortValue,
 };
-use crate::index::{load_edition_index, EditionIndexError, Index};
+use crate::index::{
+	load_edition_index, normalize_words, EditionIndexError, FirstLineIndexEntry, Index,
+};
+use crate::movement::parse_movement_path;
 use crate::parse::{load_composer, load_composition, path_for_id, ParseError};
 use crate::types::Composition;
 use thiserror::Error;
@@ -31,6 +34,8 @@ pub struct QueryResult {
 	pub superseded: bool,
 	pub current_number: Option<String>,
 	pub note: Option<String>,
+	/// Set when the number belongs to a movement rather than the whole record.
+	pub movement: Option<Vec<usize>>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -51,6 +56,66 @@ impl Index {
 	pub fn query(&self) -> QueryBuilder<'_> {
 		QueryBuilder::new(self)
 	}
+
+	/// IDs whose nickname matches exactly, or failing that, contains the
+	/// given words ("moonlight" finds "Moonlight Sonata").
+	pub fn find_by_nickname(&self, nickname: &str) -> Vec<String> {
+		let wanted = normalize_words(nickname);
+		if wanted.is_empty() {
+			return Vec::new();
+		}
+		if let Some(ids) = self.nicknames.get(&wanted) {
+			return ids.clone();
+		}
+		let padded = format!(" {} ", wanted);
+		let mut ids: Vec<String> = self
+			.nicknames
+			.iter()
+			.filter(|(name, _)| format!(" {} ", name).contains(&padded))
+			.flat_map(|(_, ids)| ids.iter().cloned())
+			.collect();
+		ids.sort();
+		ids.dedup();
+		ids
+	}
+
+	/// Movements and works whose sung text begins with the given words.
+	pub fn find_by_first_line(&self, text: &str) -> Vec<QueryResult> {
+		let wanted = normalize_words(text);
+		if wanted.is_empty() {
+			return Vec::new();
+		}
+		let prefix = format!("{} ", wanted);
+		let mut entries: Vec<&FirstLineIndexEntry> = self
+			.first_lines
+			.iter()
+			.filter(|(line, _)| **line == wanted || line.starts_with(&prefix))
+			.flat_map(|(_, entries)| entries)
+			.collect();
+		entries.sort();
+		entries.dedup();
+		entries.into_iter().map(entry_result).collect()
+	}
+
+	/// Works and movements setting a chorale melody, in ID and movement order.
+	pub fn find_by_chorale(&self, chorale: &str) -> Vec<QueryResult> {
+		let mut entries: Vec<&FirstLineIndexEntry> =
+			self.chorales.get(chorale).into_iter().flatten().collect();
+		entries.sort_by_key(|entry| (&entry.id, entry.movement.as_deref().and_then(parse_movement_path)));
+		entries.dedup();
+		entries.into_iter().map(entry_result).collect()
+	}
+}
+
+fn entry_result(entry: &FirstLineIndexEntry) -> QueryResult {
+	QueryResult {
+		id: entry.id.clone(),
+		number: None,
+		superseded: false,
+		current_number: None,
+		note: None,
+		movement: entry.movement.as_deref().and_then(parse_movement_path),
+	}
 }
 
 pub struct QueryBuilder<'a> {
@@ -71,6 +136,8 @@ impl<'a> QueryBuilder<'a> {
 		self
 	}
 
+	/// The scheme as typed. With a data directory, an alias ("kv") or a
+	/// number fused to its prefix ("K331") is read the way `get` reads it.
 	pub fn scheme(mut self, s: &str) -> Self {
 		self.query.scheme = Some(s.to_string());
 		self
@@ -113,7 +180,55 @@ impl<'a> QueryBuilder<'a> {
 		self
 	}
 
+	/// A number as typed, in stored form. The scheme's input rules apply
+	/// when the builder has a data directory to load them from.
+	fn normalize_number(&self, composer: &str, scheme: &str, number: &str) -> Result<String, QueryError> {
+		let defn = match self.query.data_dir.as_ref() {
+			Some(data_dir) => load_catalog_def(data_dir, scheme, Some(composer))?,
+			None => None,
+		};
+		Ok(normalize_input(number, defn.as_ref()))
+	}
+
+	/// The builder with its scheme token replaced by the scheme it stands for
+	/// and any fused number moved in front of the number or range, or `None`
+	/// when the token already names a scheme or names none.
+	fn with_resolved_scheme(&self) -> Result<Option<QueryBuilder<'a>>, QueryError> {
+		let (Some(composer), Some(token), Some(data_dir)) =
+			(self.query.composer.as_ref(), self.query.scheme.as_ref(), self.query.data_dir.as_ref())
+		else {
+			return Ok(None);
+		};
+		if self.index.catalog.get(composer).is_some_and(|schemes| schemes.contains_key(token)) {
+			return Ok(None);
+		}
+		let Some((scheme, fused)) = resolve_scheme(data_dir, composer, token)? else {
+			return Ok(None);
+		};
+		if &scheme == token && fused.is_none() {
+			return Ok(None);
+		}
+
+		let mut query = self.query.clone();
+		query.scheme = Some(scheme);
+		if let Some(fused) = fused {
+			if let (Some(start), Some(end)) = (query.range_start.take(), query.range_end.take()) {
+				query.range_start = Some(format!("{} {}", fused, start));
+				query.range_end = Some(format!("{} {}", fused, end));
+			} else {
+				query.number = Some(match query.number.take() {
+					Some(number) => format!("{} {}", fused, number),
+					None => fused,
+				});
+			}
+		}
+		Ok(Some(QueryBuilder { index: self.index, query }))
+	}
+
 	pub fn fetch_one(&self) -> Result<Option<String>, QueryError> {
+		if let Some(resolved) = self.with_resolved_scheme()? {
+			return resolved.fetch_one();
+		}
 		let Some(composer) = self.query.composer.as_ref() else {
 			return Ok(None);
 		};
@@ -124,7 +239,7 @@ impl<'a> QueryBuilder<'a> {
 			return Ok(None);
 		};
 
-		let normalized = normalize_catalog_number(number);
+		let normalized = self.normalize_number(composer, scheme, number)?;
 
 		if let Some(edition) = &self.query.edition {
 			let Some(data_dir) = self.query.data_dir.as_ref() else {
@@ -150,19 +265,29 @@ impl<'a> QueryBuilder<'a> {
 			}
 		}
 
+		if let Some(entry) = scheme_index.movements.get(&normalized) {
+			if !(entry.superseded && self.query.strict) {
+				return Ok(Some(entry.id.clone()));
+			}
+		}
+
 		Ok(None)
 	}
 
 	pub fn fetch(&self) -> Result<Vec<QueryResult>, QueryError> {
+		if let Some(resolved) = self.with_resolved_scheme()? {
+			return resolved.fetch();
+		}
 		match (&self.query.composer, &self.query.scheme, &self.query.number) {
 			(Some(composer), Some(scheme), Some(number)) => {
 				if let Some(result) = self.fetch_one_with_info()? {
 					Ok(vec![result])
 				} else {
+					let normalized = self.normalize_number(composer, scheme, number)?;
 					let dominated = if let Some(data_dir) = self.query.data_dir.as_ref() {
 						load_catalog_def(data_dir, scheme, Some(composer))?
 							.as_ref()
-							.map(|defn| looks_like_group(number, defn))
+							.map(|defn| looks_like_group(&normalized, defn))
 							.unwrap_or(false)
 					} else {
 						false
@@ -171,7 +296,7 @@ impl<'a> QueryBuilder<'a> {
 					if dominated {
 						let mut query = self.query.clone();
 						query.number = None;
-						query.group = Some(number.clone());
+						query.group = Some(normalized);
 						let builder = QueryBuilder {
 							index: self.index,
 							query,
@@ -202,7 +327,7 @@ impl<'a> QueryBuilder<'a> {
 			return Ok(None);
 		};
 
-		let normalized = normalize_catalog_number(number);
+		let normalized = self.normalize_number(composer, scheme, number)?;
 
 		if let Some(edition) = &self.query.edition {
 			let Some(data_dir) = self.query.data_dir.as_ref() else {
@@ -220,6 +345,7 @@ impl<'a> QueryBuilder<'a> {
 				superseded: false,
 				current_number: None,
 				note: None,
+				movement: None,
 			}));
 		}
 
@@ -234,6 +360,7 @@ impl<'a> QueryBuilder<'a> {
 				superseded: false,
 				current_number: None,
 				note: entry.note.clone(),
+				movement: None,
 			}));
 		}
 
@@ -251,6 +378,30 @@ impl<'a> QueryBuilder<'a> {
 					superseded: true,
 					current_number: current_num,
 					note: entry.note.clone(),
+					movement: None,
+				}));
+			}
+		}
+
+		if let Some(entry) = scheme_index.movements.get(&normalized) {
+			if !(entry.superseded && self.query.strict) {
+				let current_num = if entry.superseded {
+					scheme_index
+						.movements
+						.iter()
+						.find(|(_, v)| !v.superseded && v.id == entry.id && v.path == entry.path)
+						.map(|(k, _)| k.clone())
+				} else {
+					None
+				};
+
+				return Ok(Some(QueryResult {
+					id: entry.id.clone(),
+					number: Some(normalized),
+					superseded: entry.superseded,
+					current_number: current_num,
+					note: entry.note.clone(),
+					movement: Some(entry.path.clone()),
 				}));
 			}
 		}
@@ -277,6 +428,7 @@ impl<'a> QueryBuilder<'a> {
 				superseded: false,
 				current_number: None,
 				note: None,
+				movement: None,
 			})
 			.collect())
 	}
@@ -407,14 +559,14 @@ impl<'a> QueryBuilder<'a> {
 		}
 
 		if let Some(group) = &self.query.group {
-			let normalized_group = normalize_catalog_number(group);
+			let normalized_group = normalize_input(group, defn.as_ref());
 			keys.retain(|k| matches_group(k, &normalized_group, defn.as_ref()));
 		}
 
 		if let (Some(start), Some(end)) = (&self.query.range_start, &self.query.range_end) {
 			if let Some(ref d) = defn {
-				let normalized_start = normalize_catalog_number(start);
-				let normalized_end = normalize_catalog_number(end);
+				let normalized_start = normalize_input(start, Some(d));
+				let normalized_end = normalize_input(end, Some(d));
 
 				let start_key = sort_key(&normalized_start, d);
 				let end_key_raw = sort_key(&normalized_end, d);
@@ -467,6 +619,7 @@ impl<'a> QueryBuilder<'a> {
 					superseded: *is_superseded,
 					current_number: current_num,
 					note: note.clone(),
+					movement: None,
 				})
 			})
 			.collect())
@@ -521,7 +674,7 @@ fn make_inclusive_ceiling(key: Vec<SortValue>) -> Vec<SortValue> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::index::{IndexEntry, SchemeIndex};
+	use crate::index::{IndexEntry, MovementIndexEntry, SchemeIndex};
 	use std::collections::HashSet;
 
 	fn make_test_index() -> Index {
@@ -552,6 +705,26 @@ mod tests {
 			.or_default()
 			.insert("k".into(), mozart_k);
 
+		index.nicknames.insert("moonlight".into(), vec!["id5".into()]);
+		index.nicknames.insert("mondscheinsonate".into(), vec!["id5".into()]);
+		index.nicknames.insert("moonlight serenade".into(), vec!["id6".into()]);
+
+		let mut beethoven_op = SchemeIndex::default();
+		beethoven_op.current.insert("33".into(), IndexEntry { id: "id4".into(), note: None });
+		beethoven_op.movements.insert(
+			"33/3".into(),
+			MovementIndexEntry { id: "id4".into(), path: vec![3], superseded: false, note: None },
+		);
+		beethoven_op.movements.insert(
+			"33/9".into(),
+			MovementIndexEntry { id: "id4".into(), path: vec![3], superseded: true, note: None },
+		);
+		index
+			.catalog
+			.entry("beethoven".into())
+			.or_default()
+			.insert("op".into(), beethoven_op);
+
 		index
 	}
 
@@ -569,6 +742,26 @@ mod tests {
 		assert_eq!(id, Some("id1".into()));
 	}
 
+	#[test]
+	fn test_fetch_resolves_movement_number() {
+		let index = make_test_index();
+
+		let results = index.query().composer("beethoven").scheme("op").number("33/3").fetch().unwrap();
+		assert_eq!(results.len(), 1);
+		assert_eq!(results[0].id, "id4");
+		assert_eq!(results[0].movement, Some(vec![3]));
+
+		let results = index.query().composer("beethoven").scheme("op").number("33/9").fetch().unwrap();
+		assert!(results[0].superseded);
+		assert_eq!(results[0].current_number.as_deref(), Some("33/3"));
+
+		let strict = index.query().composer("beethoven").scheme("op").number("33/9").strict(true).fetch_one().unwrap();
+		assert_eq!(strict, None);
+
+		let listing = index.query().composer("beethoven").scheme("op").fetch().unwrap();
+		assert_eq!(listing.len(), 1);
+	}
+
 	#[test]
 	fn test_fetch_one_superseded_fallback() {
 		let index = make_test_index();
@@ -713,4 +906,34 @@ mod tests {
 			vec![SortValue::Int(10), SortValue::Int(1), SortValue::Int(2)]
 		);
 	}
+
+	#[test]
+	fn test_find_by_nickname() {
+		let index = make_test_index();
+		assert_eq!(index.find_by_nickname("'Moonlight'"), ["id5"]);
+		assert_eq!(index.find_by_nickname("serenade"), ["id6"]);
+		assert_eq!(index.find_by_nickname("MONDSCHEINSONATE"), ["id5"]);
+		assert!(index.find_by_nickname("moon").is_empty());
+	}
+
+	#[test]
+	fn test_find_by_first_line() {
+		let mut index = make_test_index();
+		index.first_lines.insert(
+			"kommt ihr töchter helft mir klagen".into(),
+			vec![FirstLineIndexEntry { id: "id1".into(), movement: Some("1.1".into()) }],
+		);
+		index.first_lines.insert(
+			"kommt eilet und laufet".into(),
+			vec![FirstLineIndexEntry { id: "id2".into(), movement: None }],
+		);
+
+		let results = index.find_by_first_line("Kommt, ihr Töchter");
+		assert_eq!(results.len(), 1);
+		assert_eq!(results[0].id, "id1");
+		assert_eq!(results[0].movement, Some(vec![1, 1]));
+
+		assert_eq!(index.find_by_first_line("kommt").len(), 2);
+		assert!(index.find_by_first_line("kommt ihr töch").is_empty());
+	}
 }
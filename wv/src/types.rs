@@ -1,28 +1,216 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::date::DateValue;
+use crate::duration::DurationValue;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Composition {
 	pub id: String,
-	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
 	pub title: Option<HashMap<String, String>>,
-	pub form: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub nicknames: Option<Vec<Nickname>>,
+	/// Liturgical occasion, as a slug from the church-year vocabulary
+	/// (e.g. "trinity+4"); see `occasion::occasion_label`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub occasion: Option<String>,
+	/// Chorale melody the work is built on, as a slug in `chorales/`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub chorale: Option<String>,
+	pub attribution: Vec<AttributionEntry>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub people: Option<Vec<PersonRef>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub key: Option<String>,
+	pub form: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub text: Option<SungText>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub instrumentation: Option<Instrumentation>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub duration: Option<DurationValue>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub instrumentation: Option<String>,
+	pub incipit: Option<Incipit>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub note: Option<String>,
-	pub attribution: Vec<AttributionEntry>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub movements: Option<Vec<Movement>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub sections: Option<Vec<Section>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	pub relations: Option<Vec<Relation>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub xref: Option<Xref>,
 }
 
+/// A link from this work (or one of its movements) to another composition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Relation {
+	#[serde(rename = "type")]
+	pub kind: RelationKind,
+	pub target: String,
+	/// Movement of this work the relation applies to, as a path like "2.5".
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub movement: Option<String>,
+	/// Movement of the target, as a path.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub target_movement: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelationKind {
+	ArrangementOf,
+	VersionOf,
+	ParodyOf,
+	BasedOn,
+	PartOf,
+}
+
+impl RelationKind {
+	/// How the relation reads from the work that declares it.
+	pub fn label(self) -> &'static str {
+		match self {
+			Self::ArrangementOf => "arrangement of",
+			Self::VersionOf => "version of",
+			Self::ParodyOf => "parody of",
+			Self::BasedOn => "based on",
+			Self::PartOf => "part of",
+		}
+	}
+
+	/// How the relation reads from its target.
+	pub fn inverse_label(self) -> &'static str {
+		match self {
+			Self::ArrangementOf => "arranged as",
+			Self::VersionOf => "other version",
+			Self::ParodyOf => "parodied in",
+			Self::BasedOn => "basis for",
+			Self::PartOf => "contains",
+		}
+	}
+}
+
+/// Someone other than the composer with a part in the work.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PersonRef {
+	/// Slug of a file in `people/` (or `composers/`).
+	pub person: String,
+	pub role: PersonRole,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PersonRole {
+	Librettist,
+	TextAuthor,
+	Dedicatee,
+	Arranger,
+	Editor,
+}
+
+/// The words set to music. On a movement, an unset language, author or
+/// source falls back to the composition's `text`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SungText {
+	/// The first line as sung, when the title differs from it or is absent.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub first_line: Option<String>,
+	/// Language code, e.g. "de" or "la".
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub language: Option<String>,
+	/// Person slug, as in `people`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub author: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub source: Option<TextSource>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TextSource {
+	#[serde(rename = "type")]
+	pub kind: TextSourceKind,
+	/// "Matthew 26:1–2", "Herzliebster Jesu, verse 1", etc.
+	pub reference: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextSourceKind {
+	Bible,
+	Chorale,
+	Liturgy,
+	Poem,
+	Libretto,
+	Other,
+}
+
+/// Opening notes in Plaine & Easie Code, as in RISM. See `crate::incipit`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Incipit {
+	/// E.g. "G-2" (treble), "C-3" (alto), "F-4" (bass).
+	pub clef: String,
+	/// E.g. "xFC" for two sharps, "bBEA" for three flats.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub key: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub time: Option<String>,
+	pub data: String,
+}
+
+/// A popular name such as "Moonlight", keyed by language like `title`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Nickname {
+	#[serde(serialize_with = "sorted")]
+	pub name: HashMap<String, String>,
+	/// Where the name comes from, e.g. "publisher's nickname".
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub provenance: Option<String>,
+}
+
+/// Free text remains valid; the structured form lists parts against the
+/// bundled instrument vocabulary.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Instrumentation {
+	Text(String),
+	Parts(Vec<InstrumentPart>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstrumentPart {
+	pub instrument: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub count: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub role: Option<PartRole>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub alternatives: Option<Vec<String>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartRole {
+	Soloist,
+	Obbligato,
+	Continuo,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Status {
@@ -36,6 +224,9 @@ pub enum Status {
 pub struct AttributionEntry {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub composer: Option<String>,
+	/// Others who share the credit: co-composers, completers, arrangers.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub contributors: Option<Vec<Contributor>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub dates: Option<Dates>,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -46,57 +237,167 @@ pub struct AttributionEntry {
 	pub since: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub note: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sources: Option<Vec<SourceRef>>,
+}
+
+impl AttributionEntry {
+	/// The composer whose catalogs number the work: `composer`, or else the
+	/// first contributor credited as composer or co-composer.
+	pub fn principal(&self) -> Option<&str> {
+		self.composer.as_deref().or_else(|| {
+			self.contributors
+				.iter()
+				.flatten()
+				.find(|contributor| matches!(contributor.role, ContributorRole::Composer | ContributorRole::CoComposer))
+				.map(|contributor| contributor.composer.as_str())
+		})
+	}
+
+	/// Everyone credited, `composer` first.
+	pub fn credited(&self) -> impl Iterator<Item = &str> {
+		self.composer
+			.as_deref()
+			.into_iter()
+			.chain(self.contributors.iter().flatten().map(|contributor| contributor.composer.as_str()))
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Contributor {
+	/// Slug of a file in `composers/` (or `people/`).
+	pub composer: String,
+	pub role: ContributorRole,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContributorRole {
+	Composer,
+	CoComposer,
+	Completer,
+	Arranger,
+}
+
+/// Where a claim comes from: an entry in `references/`, optionally narrowed
+/// to a page or item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourceRef {
+	/// Slug of a file in `references/`.
+	pub reference: String,
+	/// Page, item number or section within the reference, e.g. "p. 162".
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub locator: Option<String>,
+	/// Link to the cited page itself, e.g. a scan.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Dates {
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub composed: Option<i32>,
+	pub composed: Option<DateValue>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub published: Option<i32>,
+	pub published: Option<DateValue>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub premiered: Option<i32>,
+	pub premiered: Option<DateValue>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub revised: Option<i32>,
+	pub revised: Option<DateValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CatalogEntry {
 	pub scheme: String,
-	pub number: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub edition: Option<String>,
+	pub number: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub since: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub note: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sources: Option<Vec<SourceRef>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Movement {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub title: Option<String>,
-	#[serde(skip_serializing_if = "Option::is_none")]
-	pub key: Option<String>,
+	/// Programmatic title by language code, as in the "Pastoral" Symphony.
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
+	pub program: Option<HashMap<String, String>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub form: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub soloists: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	pub key: Option<String>,
+	/// Instruments of this movement, where it is worth naming them apart
+	/// from the work's (e.g. an obbligato).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub instrumentation: Option<Instrumentation>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub text: Option<SungText>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub duration: Option<DurationValue>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub chorale: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub incipit: Option<Incipit>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<MovementStatus>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub catalog: Option<Vec<CatalogEntry>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub sections: Option<Vec<Section>>,
 }
 
+/// How much of a movement survives, when not all of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MovementStatus {
+	Fragment,
+	Lost,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct Section {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub title: Option<String>,
-	#[serde(skip_serializing_if = "Option::is_none")]
-	pub key: Option<String>,
+	/// Programmatic title by language code, as in the "Pastoral" Symphony.
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
+	pub program: Option<HashMap<String, String>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub form: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub soloists: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	pub key: Option<String>,
+	/// Instruments of this movement, where it is worth naming them apart
+	/// from the work's (e.g. an obbligato).
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub instrumentation: Option<Instrumentation>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub text: Option<SungText>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub duration: Option<DurationValue>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub chorale: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub incipit: Option<Incipit>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub status: Option<MovementStatus>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub catalog: Option<Vec<CatalogEntry>>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub movements: Option<Vec<Movement>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub sections: Option<Vec<Section>>,
@@ -107,12 +408,12 @@ pub struct Xref {
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub oo: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	pub wp: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	pub mb: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub imslp: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub wp: Option<String>,
-	#[serde(skip_serializing_if = "Option::is_none")]
 	pub wd: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub viaf: Option<String>,
@@ -134,8 +435,9 @@ pub struct Inventory {
 #[serde(deny_unknown_fields)]
 pub struct Collection {
 	pub id: String,
+	#[serde(serialize_with = "sorted")]
 	pub title: HashMap<String, String>,
-	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
 	pub expansion_pattern: Option<HashMap<String, String>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub composer: Option<String>,
@@ -160,12 +462,95 @@ pub struct Composer {
 	pub died: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub nationality: Option<String>,
-	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
 	pub catalogs: Option<HashMap<String, CatalogDefinition>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub xref: Option<Xref>,
 }
 
+/// A hymn tune set in chorales, chorale cantatas and chorale preludes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChoraleMelody {
+	pub id: String,
+	/// Tune name, usually the first line of the hymn it was written for.
+	pub title: String,
+	/// Number in Zahn, Die Melodien der deutschen evangelischen Kirchenlieder.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub zahn: Option<String>,
+	/// Who wrote the tune and when, as free text (e.g. "Johann Crüger, 1653").
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub origin: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+impl ChoraleMelody {
+	/// "Title (Zahn 5385a)", or the bare title without a Zahn number.
+	pub fn label(&self) -> String {
+		match &self.zahn {
+			Some(zahn) => format!("{} (Zahn {})", self.title, zahn),
+			None => self.title.clone(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Person {
+	pub id: String,
+	pub name: ComposerName,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub born: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub died: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub xref: Option<Xref>,
+}
+
+/// A catalog, monograph, edition or manuscript cited as a source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Reference {
+	pub id: String,
+	pub title: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub author: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub year: Option<i32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub publisher: Option<String>,
+	/// RISM record ID, for manuscripts and prints described by RISM.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub rism: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub url: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub note: Option<String>,
+}
+
+impl Reference {
+	/// "Author, Title (Publisher, Year)" with whatever parts are known.
+	pub fn citation(&self) -> String {
+		let mut citation = match &self.author {
+			Some(author) => format!("{}, {}", author, self.title),
+			None => self.title.clone(),
+		};
+		let imprint: Vec<String> = self
+			.publisher
+			.iter()
+			.cloned()
+			.chain(self.year.map(|year| year.to_string()))
+			.collect();
+		if !imprint.is_empty() {
+			citation.push_str(&format!(" ({})", imprint.join(", ")));
+		}
+		citation
+	}
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ComposerName {
@@ -189,19 +574,26 @@ pub struct CatalogDefinition {
 	pub allowed_uppercase_suffixes: Option<Vec<String>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub pattern: Option<String>,
+	/// Alternatives tried after `pattern`, in order. Naming the groups lets
+	/// each alternative capture the same components in its own layout.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub patterns: Option<Vec<String>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub sort_keys: Option<Vec<SortKey>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub group_by: Option<Vec<usize>>,
+	pub group_by: Option<Vec<CatalogGroup>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub examples: Option<Vec<CatalogExample>>,
+	/// Rewrites applied, in order, to numbers typed by users before lookup.
 	#[serde(skip_serializing_if = "Option::is_none")]
-	pub aliases: Option<Vec<String>>,
+	pub input_rules: Option<Vec<InputRule>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
+	pub aliases: Option<Vec<String>>,
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
 	pub editions: Option<HashMap<String, EditionInfo>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub current_edition: Option<String>,
-	#[serde(skip_serializing_if = "Option::is_none")]
+	#[serde(skip_serializing_if = "Option::is_none", serialize_with = "sorted_option")]
 	pub categories: Option<HashMap<String, String>>,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub constraints: Option<Vec<CatalogConstraint>>,
@@ -213,10 +605,27 @@ pub struct CatalogDefinition {
 	pub mb_part_format: Option<String>,
 }
 
+/// A capture group in a catalog pattern, by 1-based index or by name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CatalogGroup {
+	Index(usize),
+	Name(String),
+}
+
+impl std::fmt::Display for CatalogGroup {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			CatalogGroup::Index(index) => write!(f, "{}", index),
+			CatalogGroup::Name(name) => write!(f, "{}", name),
+		}
+	}
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct CatalogConstraint {
-	pub group: usize,
+	pub group: CatalogGroup,
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub name: Option<String>,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -237,7 +646,7 @@ pub struct CatalogIntRange {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct SortKey {
-	pub group: usize,
+	pub group: CatalogGroup,
 	#[serde(rename = "type")]
 	pub sort_type: String,
 	#[serde(skip_serializing_if = "Option::is_none")]
@@ -251,6 +660,28 @@ pub struct SortKey {
 pub struct CatalogExample {
 	pub number: String,
 	pub display: String,
+	/// Spellings a user might type that must normalize to `number`.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub input: Vec<String>,
+}
+
+/// One step of turning a typed number into its stored form. Input has
+/// already been trimmed and lowercased when the first rule runs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum InputRule {
+	/// Drop the first listed prefix the input starts with, and any
+	/// whitespace after it.
+	StripPrefix(Vec<String>),
+	/// Replace every match of `pattern` (case-insensitive) with `with`,
+	/// which may refer to captures as `$1` or `${name}`.
+	Replace { pattern: String, with: String },
+	/// Join a leading category to the rest with `:`, so "iii 32" and
+	/// "iii/32" become "iii:32". With `arabic`, "3/32" does as well.
+	Category {
+		#[serde(default)]
+		arabic: bool,
+	},
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -259,3 +690,12 @@ pub struct EditionInfo {
 	pub year: i32,
 	pub editor: String,
 }
+
+/// Maps are written in key order so that files and output don't churn.
+pub(crate) fn sorted<S: Serializer, V: Serialize>(map: &HashMap<String, V>, serializer: S) -> Result<S::Ok, S::Error> {
+	map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
+fn sorted_option<S: Serializer, V: Serialize>(map: &Option<HashMap<String, V>>, serializer: S) -> Result<S::Ok, S::Error> {
+	map.as_ref().map(|map| map.iter().collect::<BTreeMap<_, _>>()).serialize(serializer)
+}
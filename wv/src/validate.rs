@@ -1,27 +1,160 @@
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 
 use jsonschema::Validator as JsonSchemaValidator;
 use regex::Regex;
 use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::OnceLock;
 
 use crate::catalog::{
-	cached_regex_result, load_catalog_def, normalize_catalog_number,
-	validate_catalog_case_rules, validate_catalog_domain, validate_catalog_formats,
+	cached_regex_result, check_examples, compiled_patterns, definition_patterns, has_pattern, load_catalog_def,
+	normalize_catalog_number, validate_catalog_case_rules, validate_catalog_domain, validate_catalog_formats,
+	validate_input_rules,
 };
+use crate::date::PartialDate;
+use crate::form::{form_taxonomy_path, load_form_taxonomy, FormTaxonomy};
+use crate::instrument::{
+	instrument_vocabulary_path, load_instrument_vocabulary, read_instrument_vocabulary, InstrumentVocabulary,
+	InstrumentVocabularyError,
+};
+use crate::incipit::parse_incipit;
 use crate::inventory::{build_inventory_index, normalize_inventory, InventoryIndex};
+use crate::key::Key;
+use crate::merge::current_composer;
+use crate::movement::{movement_at, parse_movement_path, walk_movements};
+use crate::occasion::occasion_label;
 use crate::parse::extract_id_from_path;
-use crate::types::{AttributionEntry, CatalogDefinition, Collection, Composer, Composition};
+use crate::types::{
+	AttributionEntry, CatalogDefinition, CatalogGroup, Collection, Composer, Composition, Dates, Instrumentation,
+	ChoraleMelody, Person, Reference, SourceRef, Status,
+};
+use crate::validation_cache::{content_hash, CacheEntry, CompositionSummary, Fingerprint, ValidationCache};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidationError {
 	pub path: String,
+	/// JSON pointer to the offending value; empty for the whole file.
+	pub pointer: String,
+	pub code: ErrorCode,
+	pub severity: Level,
 	pub message: String,
 }
 
+impl ValidationError {
+	pub(crate) fn new(path: impl Into<String>, code: ErrorCode, message: impl Into<String>) -> Self {
+		Self {
+			path: path.into(),
+			pointer: String::new(),
+			code,
+			severity: Level::Error,
+			message: message.into(),
+		}
+	}
+
+	/// Point at a location written the way messages write it, e.g.
+	/// `attribution[0].catalog[1]`.
+	pub(crate) fn at(mut self, location: &str) -> Self {
+		self.pointer = json_pointer(location);
+		self
+	}
+
+	/// Worth a second look, but not necessarily wrong.
+	pub(crate) fn warning(mut self) -> Self {
+		self.severity = Level::Warning;
+		self
+	}
+}
+
+/// How serious a finding is. Warnings do not block `wv add`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+	Warning,
+	Error,
+}
+
+/// What a finding is about. The kebab-case names are stable, for tools
+/// that filter on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorCode {
+	Io,
+	InvalidJson,
+	Whitespace,
+	InvalidSchema,
+	Schema,
+	ModelMismatch,
+	UnknownFileKind,
+	InvalidId,
+	IdMismatch,
+	EmptyAttribution,
+	MissingComposer,
+	UnknownComposer,
+	UnknownPerson,
+	UnknownReference,
+	UnknownChorale,
+	UnknownInstrument,
+	UnknownForm,
+	UnknownOccasion,
+	InvalidKey,
+	InvalidDate,
+	OutsideLifespan,
+	PosthumousDate,
+	RevisionOrder,
+	AttributionOrder,
+	SpuriousAttribution,
+	InvalidIncipit,
+	InvalidRelation,
+	InvalidTaxonomy,
+	InvalidCatalog,
+	UnknownScheme,
+	SchemeCase,
+	UnknownEdition,
+	CatalogNumberCase,
+	CatalogPattern,
+	CatalogDomain,
+	CatalogExample,
+	NotInInventory,
+	DuplicateIdentifier,
+	DuplicateMember,
+	UnresolvedMember,
+	AmbiguousMember,
+	InvalidInventory,
+	DuplicateInventory,
+}
+
+impl std::fmt::Display for ErrorCode {
+	/// The serde name, so that text, JSON and SARIF reports agree.
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match serde_json::to_value(self) {
+			Ok(serde_json::Value::String(name)) => f.write_str(&name),
+			_ => write!(f, "{:?}", self),
+		}
+	}
+}
+
+/// `movements[2].catalog[0]` as `/movements/2/catalog/0`.
+fn json_pointer(location: &str) -> String {
+	let mut pointer = String::new();
+	for segment in location.split('.').filter(|segment| !segment.is_empty()) {
+		let mut parts = segment.split('[');
+		let key = parts.next().unwrap_or_default();
+		if !key.is_empty() {
+			pointer.push('/');
+			pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+		}
+		for index in parts {
+			pointer.push('/');
+			pointer.push_str(index.trim_end_matches(']'));
+		}
+	}
+	pointer
+}
+
 impl std::fmt::Display for ValidationError {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(f, "{}: {}", self.path, self.message)
@@ -32,6 +165,11 @@ impl std::fmt::Display for ValidationError {
 enum DataKind {
 	Composition,
 	Composer,
+	Person,
+	Reference,
+	Chorale,
+	Taxonomy,
+	Instruments,
 	Catalog,
 	Collection,
 	Inventory,
@@ -57,14 +195,10 @@ enum CachedComposition {
 }
 
 impl CachedComposition {
-	fn load(path: &Path) -> Self {
-		let content = match fs::read_to_string(path) {
-			Ok(content) => content,
-			Err(error) => return Self::ReadError(error.to_string()),
-		};
+	fn parse(content: &str) -> Self {
 		let has_multiple_spaces = content.contains("  ");
 
-		match serde_json::from_str(&content) {
+		match serde_json::from_str(content) {
 			Ok(value) => Self::Parsed {
 				value,
 				has_multiple_spaces,
@@ -94,10 +228,9 @@ impl SchemaCheck {
 	}
 
 	fn schema_error(&self) -> Option<ValidationError> {
-		self.error.as_ref().map(|message| ValidationError {
-			path: self.path.display().to_string(),
-			message: message.clone(),
-		})
+		self.error
+			.as_ref()
+			.map(|message| ValidationError::new(self.path.display().to_string(), ErrorCode::InvalidSchema, message.clone()))
 	}
 
 	fn validate(&self, value: &Value, path_str: &str) -> Vec<ValidationError> {
@@ -115,27 +248,66 @@ impl SchemaCheck {
 					format!("${}", instance_path)
 				};
 				ValidationError {
-					path: path_str.to_string(),
-					message: format!("schema {}: {}", location, error),
+					pointer: instance_path,
+					..ValidationError::new(path_str, ErrorCode::Schema, format!("schema {}: {}", location, error))
 				}
 			})
 			.collect()
 	}
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+struct Lifespan {
+	born: Option<PartialDate>,
+	died: Option<PartialDate>,
+}
+
+impl Lifespan {
+	fn from_composer(value: &Value) -> Self {
+		let date = |field: &str| {
+			value
+				.get(field)
+				.and_then(Value::as_str)
+				.and_then(|s| s.parse().ok())
+		};
+		Self {
+			born: date("born"),
+			died: date("died"),
+		}
+	}
+}
+
 pub struct Validator {
 	data_dir: PathBuf,
 	composers: HashSet<String>,
+	people: HashSet<String>,
+	references: HashSet<String>,
+	chorales: HashSet<String>,
+	forms: Option<FormTaxonomy>,
+	instruments: Option<InstrumentVocabulary>,
 	catalog_schemes: HashSet<String>,
 	global_catalog_schemes: HashSet<String>,
 	composer_catalog_schemes: HashMap<String, HashSet<String>>,
+	composer_lifespans: HashMap<String, Lifespan>,
 	current_catalog_targets: HashMap<(String, String, String), Vec<String>>,
+	composition_ids: HashMap<String, PathBuf>,
 	composition_cache: HashMap<PathBuf, CachedComposition>,
+	composition_summaries: HashMap<PathBuf, CompositionSummary>,
+	composition_hashes: HashMap<PathBuf, String>,
+	cache: ValidationCache,
+	/// Fingerprint of everything every composition's checks depend on.
+	context: String,
+	/// Fingerprints of `composer:<slug>` and `catalog:<scheme>` dependencies.
+	dependency_hashes: HashMap<String, String>,
 	validation_paths: Vec<PathBuf>,
 	discovery_errors: Vec<ValidationError>,
 	inventory_index_error: Option<String>,
 	composition_schema: SchemaCheck,
 	composer_schema: SchemaCheck,
+	person_schema: SchemaCheck,
+	reference_schema: SchemaCheck,
+	chorale_schema: SchemaCheck,
+	form_taxonomy_schema: SchemaCheck,
 	catalog_schema: SchemaCheck,
 	collection_schema: SchemaCheck,
 	inventory_index: InventoryIndex,
@@ -145,9 +317,13 @@ impl Validator {
 	pub fn new<P: AsRef<Path>>(data_dir: P) -> Self {
 		let data_dir = data_dir.as_ref();
 		let mut composers = HashSet::new();
+		let mut people = HashSet::new();
+		let mut references = HashSet::new();
+		let mut chorales = HashSet::new();
 		let mut catalog_schemes = HashSet::new();
 		let mut global_catalog_schemes = HashSet::new();
 		let mut composer_catalog_schemes = HashMap::new();
+		let mut composer_lifespans = HashMap::new();
 		let mut validation_paths = Vec::new();
 		let mut discovery_errors = Vec::new();
 
@@ -164,6 +340,9 @@ impl Validator {
 
 			if let Ok(content) = fs::read_to_string(path) {
 				if let Ok(value) = serde_json::from_str::<Value>(&content) {
+					if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+						composer_lifespans.insert(stem.to_string(), Lifespan::from_composer(&value));
+					}
 					if let Some(catalogs) = value.get("catalogs").and_then(Value::as_object) {
 						let schemes: HashSet<String> = catalogs.keys().cloned().collect();
 						catalog_schemes.extend(schemes.iter().cloned());
@@ -176,6 +355,56 @@ impl Validator {
 		}
 		validation_paths.extend(composer_paths);
 
+		// Older datasets have no people/ directory; that is not an error.
+		let people_dir = data_dir.join("people");
+		if people_dir.is_dir() {
+			let mut person_paths = Vec::new();
+			collect_json_files(&people_dir, &mut person_paths, &mut discovery_errors);
+			for path in &person_paths {
+				if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+					people.insert(stem.to_string());
+				}
+			}
+			validation_paths.extend(person_paths);
+		}
+
+		let references_dir = data_dir.join("references");
+		if references_dir.is_dir() {
+			let mut reference_paths = Vec::new();
+			collect_json_files(&references_dir, &mut reference_paths, &mut discovery_errors);
+			for path in &reference_paths {
+				if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+					references.insert(stem.to_string());
+				}
+			}
+			validation_paths.extend(reference_paths);
+		}
+
+		let chorales_dir = data_dir.join("chorales");
+		if chorales_dir.is_dir() {
+			let mut chorale_paths = Vec::new();
+			collect_json_files(&chorales_dir, &mut chorale_paths, &mut discovery_errors);
+			for path in &chorale_paths {
+				if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+					chorales.insert(stem.to_string());
+				}
+			}
+			validation_paths.extend(chorale_paths);
+		}
+
+		// A taxonomy that fails to load is reported against its own file;
+		// compositions are then checked without one.
+		let forms = load_form_taxonomy(data_dir).ok().flatten().map(|forms| (*forms).clone());
+		let taxonomy_path = form_taxonomy_path(data_dir);
+		if taxonomy_path.is_file() {
+			validation_paths.push(taxonomy_path);
+		}
+		let instruments = load_instrument_vocabulary(data_dir).ok().flatten().map(|instruments| (*instruments).clone());
+		let vocabulary_path = instrument_vocabulary_path(data_dir);
+		if vocabulary_path.is_file() {
+			validation_paths.push(vocabulary_path);
+		}
+
 		let mut catalog_paths = Vec::new();
 		collect_json_files(
 			&data_dir.join("catalogs"),
@@ -199,7 +428,11 @@ impl Validator {
 		validation_paths.extend(collection_paths);
 
 		let mut current_catalog_targets = HashMap::new();
+		let mut composition_ids = HashMap::new();
 		let mut composition_cache = HashMap::new();
+		let mut composition_summaries = HashMap::new();
+		let mut composition_hashes = HashMap::new();
+		let cache = ValidationCache::load(data_dir);
 		let mut composition_paths = Vec::new();
 		collect_json_files(
 			&data_dir.join("compositions"),
@@ -207,26 +440,41 @@ impl Validator {
 			&mut discovery_errors,
 		);
 		for path in &composition_paths {
-			let cached = CachedComposition::load(path);
-			if let CachedComposition::Parsed { value, .. } = &cached {
-				if let Ok(composition) = serde_json::from_value::<Composition>(value.clone()) {
-					let mut schemes_seen = HashSet::new();
-					for attribution in &composition.attribution {
-						let (Some(composer), Some(catalog)) = (&attribution.composer, &attribution.catalog) else {
-							continue;
-						};
-						for entry in catalog {
-							if schemes_seen.insert((composer.clone(), entry.scheme.clone())) {
-								current_catalog_targets
-									.entry((composer.clone(), entry.scheme.clone(), entry.number.clone()))
-									.or_insert_with(Vec::new)
-									.push(composition.id.clone());
-							}
-						}
-					}
+			let content = match fs::read_to_string(path) {
+				Ok(content) => content,
+				Err(error) => {
+					composition_cache.insert(path.clone(), CachedComposition::ReadError(error.to_string()));
+					continue;
+				}
+			};
+			let hash = content_hash(&content);
+			// An unchanged file's summary comes from the cache without parsing it.
+			let cached_summary = cache_key(data_dir, path)
+				.and_then(|key| cache.files.get(&key))
+				.filter(|entry| entry.hash == hash)
+				.map(|entry| entry.summary.clone());
+			let summary = cached_summary.or_else(|| {
+				let cached = CachedComposition::parse(&content);
+				let summary = match &cached {
+					CachedComposition::Parsed { value, .. } => serde_json::from_value::<Composition>(value.clone())
+						.ok()
+						.map(|composition| CompositionSummary::of(&composition, current_catalog_identifiers(&composition))),
+					_ => None,
+				};
+				composition_cache.insert(path.clone(), cached);
+				summary
+			});
+			composition_hashes.insert(path.clone(), hash);
+			if let Some(summary) = summary {
+				for key in &summary.identifiers {
+					current_catalog_targets
+						.entry(key.clone())
+						.or_insert_with(Vec::new)
+						.push(summary.id.clone());
 				}
+				composition_ids.insert(summary.id.clone(), path.clone());
+				composition_summaries.insert(path.clone(), summary);
 			}
-			composition_cache.insert(path.clone(), cached);
 		}
 		validation_paths.extend(composition_paths);
 
@@ -236,28 +484,70 @@ impl Validator {
 			&mut inventory_paths,
 			&mut discovery_errors,
 		);
-		validation_paths.extend(inventory_paths);
+		inventory_paths.sort();
 
 		let (inventory_index, inventory_index_error) = match build_inventory_index(data_dir) {
 			Ok(index) => (index, None),
 			Err(error) => (InventoryIndex::default(), Some(error.to_string())),
 		};
 
+		let mut dependency_hashes = HashMap::new();
+		for composer in &composers {
+			let mut fingerprint = Fingerprint::default();
+			fingerprint.update(&fs::read(data_dir.join("composers").join(format!("{}.json", composer))).unwrap_or_default());
+			for path in inventory_paths
+				.iter()
+				.filter(|path| inventory_composer_from_path(path) == Some(composer.as_str()))
+			{
+				fingerprint
+					.update(path.to_string_lossy().as_bytes())
+					.update(&fs::read(path).unwrap_or_default());
+			}
+			dependency_hashes.insert(format!("composer:{}", composer), fingerprint.finish());
+		}
+		for scheme in &global_catalog_schemes {
+			let content = fs::read(data_dir.join("catalogs").join(format!("{}.json", scheme))).unwrap_or_default();
+			dependency_hashes.insert(format!("catalog:{}", scheme), Fingerprint::default().update(&content).finish());
+		}
+		validation_paths.extend(inventory_paths);
+
 		let schemas_dir = data_dir.join("schemas");
+		let context = validation_context(
+			&schemas_dir,
+			[&form_taxonomy_path(data_dir), &instrument_vocabulary_path(data_dir)],
+			[&composers, &people, &references, &chorales, &catalog_schemes, &global_catalog_schemes],
+			inventory_index_error.as_deref(),
+		);
 
 		Self {
 			data_dir: data_dir.to_path_buf(),
 			composers,
+			people,
+			references,
+			chorales,
+			forms,
+			instruments,
 			catalog_schemes,
 			global_catalog_schemes,
 			composer_catalog_schemes,
+			composer_lifespans,
 			current_catalog_targets,
+			composition_ids,
 			composition_cache,
+			composition_summaries,
+			composition_hashes,
+			cache,
+			context,
+			dependency_hashes,
 			validation_paths,
 			discovery_errors,
 			inventory_index_error,
 			composition_schema: SchemaCheck::load(schemas_dir.join("composition.schema.json")),
 			composer_schema: SchemaCheck::load(schemas_dir.join("composer.schema.json")),
+			person_schema: SchemaCheck::load(schemas_dir.join("person.schema.json")),
+			reference_schema: SchemaCheck::load(schemas_dir.join("reference.schema.json")),
+			chorale_schema: SchemaCheck::load(schemas_dir.join("chorale.schema.json")),
+			form_taxonomy_schema: SchemaCheck::load(schemas_dir.join("form-taxonomy.schema.json")),
 			catalog_schema: SchemaCheck::load(schemas_dir.join("catalog.schema.json")),
 			collection_schema: SchemaCheck::load(schemas_dir.join("collection.schema.json")),
 			inventory_index,
@@ -295,13 +585,19 @@ impl Validator {
 		match data_kind(path) {
 			Some(DataKind::Composition) => self.validate_composition_file(path),
 			Some(DataKind::Composer) => self.validate_composer_file(path),
+			Some(DataKind::Person) => self.validate_person_file(path),
+			Some(DataKind::Reference) => self.validate_reference_file(path),
+			Some(DataKind::Chorale) => self.validate_chorale_file(path),
+			Some(DataKind::Taxonomy) => self.validate_taxonomy_file(path),
+			Some(DataKind::Instruments) => self.validate_instruments_file(path),
 			Some(DataKind::Catalog) => self.validate_catalog_file(path),
 			Some(DataKind::Collection) => self.validate_collection_file(path),
 			Some(DataKind::Inventory) => self.validate_inventory_file(path),
-			None => vec![ValidationError {
-				path: path.display().to_string(),
-				message: "Cannot determine data type; path must be under compositions/, composers/, catalogs/, collections/, or inventories/".into(),
-			}],
+			None => vec![ValidationError::new(
+				path.display().to_string(),
+				ErrorCode::UnknownFileKind,
+				"Cannot determine data type; path must be under compositions/, composers/, people/, references/, chorales/, taxonomy/, catalogs/, collections/, or inventories/",
+			)],
 		}
 	}
 
@@ -327,48 +623,52 @@ impl Validator {
 			Ok(Some(definition)) => definition,
 			Ok(None) => return errors,
 			Err(error) => {
-				errors.push(ValidationError {
-					path: path_str.to_string(),
-					message: format!("{}: {}", location, error),
-				});
+				errors.push(ValidationError::new(
+					path_str.to_string(),
+					ErrorCode::InvalidCatalog,
+					format!("{}: {}", location, error),
+				).at(location));
 				return errors;
 			}
 		};
 
 		if !is_valid_catalog_number_case(number, &definition) {
-			errors.push(ValidationError {
-				path: path_str.to_string(),
-				message: format!(
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::CatalogNumberCase,
+				format!(
 					"{}: catalog number '{}' {}",
 					location,
 					number,
 					catalog_number_case_requirement(&definition)
 				),
-			});
+			).at(location));
 		}
 
 		let mut pattern_valid = true;
-		if let Some(pattern) = &definition.pattern {
-			match cached_regex_result(pattern) {
-				Ok(regex) if !regex.is_match(number) => {
+		if has_pattern(&definition) {
+			match compiled_patterns(&definition) {
+				Ok(regexes) if !regexes.iter().any(|regex| regex.is_match(number)) => {
 					pattern_valid = false;
-					errors.push(ValidationError {
-						path: path_str.to_string(),
-						message: format!(
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::CatalogPattern,
+						format!(
 							"{}: catalog number '{}' does not match '{}' pattern",
 							location, number, scheme
 						),
-					});
+					).at(location));
 				}
 				Err(error) => {
 					pattern_valid = false;
-					errors.push(ValidationError {
-						path: path_str.to_string(),
-						message: format!(
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::InvalidCatalog,
+						format!(
 							"{}: invalid '{}' catalog pattern: {}",
 							location, scheme, error
 						),
-					});
+					).at(location));
 				}
 				_ => {}
 			}
@@ -380,13 +680,14 @@ impl Validator {
 				.as_ref()
 				.map_or(false, |editions| editions.contains_key(edition));
 			if !defined {
-				errors.push(ValidationError {
-					path: path_str.to_string(),
-					message: format!(
+				errors.push(ValidationError::new(
+					path_str.to_string(),
+					ErrorCode::UnknownEdition,
+					format!(
 						"{}: edition '{}' is not defined for {}:{}",
 						location, edition, composer, scheme
 					),
-				});
+				).at(location));
 			}
 		}
 
@@ -394,13 +695,14 @@ impl Validator {
 		if pattern_valid {
 			if let Err(error) = validate_catalog_domain(number, &definition) {
 				domain_valid = false;
-				errors.push(ValidationError {
-					path: path_str.to_string(),
-					message: format!(
+				errors.push(ValidationError::new(
+					path_str.to_string(),
+					ErrorCode::CatalogDomain,
+					format!(
 						"{}: catalog number '{}' is outside the structural domain for '{}': {}",
 						location, number, scheme, error
 					),
-				});
+				).at(location));
 			}
 		}
 
@@ -411,13 +713,14 @@ impl Validator {
 			{
 				let normalized = normalize_catalog_number(number);
 				if catalog.complete && !catalog.entries.contains(&normalized) {
-					errors.push(ValidationError {
-						path: path_str.to_string(),
-						message: format!(
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::NotInInventory,
+						format!(
 							"{}: {}:{}:{} is not present in the complete catalog inventory",
 							location, composer, scheme, number
 						),
-					});
+					).at(location));
 				}
 			}
 		}
@@ -425,36 +728,57 @@ impl Validator {
 		errors
 	}
 
-	fn validate_current_catalog_uniqueness(
+	/// Movement numbers belong to the work's current composer; they are checked
+	/// against the same catalog rules as work-level numbers.
+	fn validate_movement_catalogs(
 		&self,
 		composition: &Composition,
 		path_str: &str,
 	) -> Vec<ValidationError> {
 		let mut errors = Vec::new();
-		let mut schemes_seen = HashSet::new();
+		let visits = walk_movements(composition);
+		if visits.iter().all(|visit| visit.node.catalog().is_empty()) {
+			return errors;
+		}
 
-		for attribution in &composition.attribution {
-			let (Some(composer), Some(catalog)) = (&attribution.composer, &attribution.catalog) else {
-				continue;
-			};
-			for entry in catalog {
-				if !schemes_seen.insert((composer.clone(), entry.scheme.clone())) {
-					continue;
+		let Some(composer) = current_composer(&composition.attribution) else {
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::MissingComposer,
+				"movement catalog numbers require a current composer",
+			));
+			return errors;
+		};
+
+		for visit in &visits {
+			for (j, cat) in visit.node.catalog().iter().enumerate() {
+				let location = format!("{}.catalog[{}]", visit.location, j);
+				if cat.scheme != cat.scheme.to_lowercase() {
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::SchemeCase,
+						format!("{}: catalog scheme '{}' must be lowercase", location, cat.scheme),
+					).at(&location));
 				}
-				let key = (composer.clone(), entry.scheme.clone(), entry.number.clone());
-				if let Some(ids) = self.current_catalog_targets.get(&key) {
-					let mut unique: Vec<_> = ids.iter().cloned().collect::<HashSet<_>>().into_iter().collect();
-					if unique.len() > 1 {
-						unique.sort();
-						errors.push(ValidationError {
-							path: path_str.to_string(),
-							message: format!(
-								"current catalog identifier {}:{}:{} is shared by compositions {}",
-								composer, entry.scheme, entry.number, unique.join(", ")
-							),
-						});
-					}
+				if !self.catalog_is_allowed_for_composer(composer, &cat.scheme) {
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::UnknownScheme,
+						format!(
+							"{}: catalog scheme '{}' is not defined for composer '{}' or globally",
+							location, cat.scheme, composer
+						),
+					).at(&location));
+					continue;
 				}
+				errors.extend(self.validate_catalog_reference(
+					composer,
+					&cat.scheme,
+					&cat.number,
+					cat.edition.as_deref(),
+					path_str,
+					&location,
+				));
 			}
 		}
 
@@ -462,44 +786,79 @@ impl Validator {
 	}
 
 	pub fn validate_composition_file(&self, path: &Path) -> Vec<ValidationError> {
+		let (mut errors, summary) = self.composition_errors(path);
+		if let Some(summary) = summary {
+			errors.extend(self.validate_composition_links(&summary, &path.display().to_string()));
+		}
+		errors
+	}
+
+	/// Findings about the composition on its own, from the cache when it is
+	/// still valid, and its summary if it got far enough to have one.
+	fn composition_errors(&self, path: &Path) -> (Vec<ValidationError>, Option<CompositionSummary>) {
+		if let Some(errors) = self.cached_errors(path) {
+			return (errors, self.composition_summaries.get(path).cloned());
+		}
 		if let Some(cached) = self.composition_cache.get(path) {
-			return self.validate_cached_composition(path, cached);
+			return self.check_cached_composition(path, cached);
 		}
 
-		let (value, errors) = match self.read_and_validate(path, &self.composition_schema, true) {
-			Ok(result) => result,
-			Err(errors) => return errors,
-		};
-		self.validate_composition_value(path, &value, errors)
+		match self.read_and_validate(path, &self.composition_schema, true) {
+			Ok((value, errors)) => self.check_composition_value(path, &value, errors),
+			Err(errors) => (errors, None),
+		}
+	}
+
+	fn cached_errors(&self, path: &Path) -> Option<Vec<ValidationError>> {
+		let entry = self.cache.files.get(&cache_key(&self.data_dir, path)?)?;
+		if self.cache.context != self.context || self.composition_hashes.get(path) != Some(&entry.hash) {
+			return None;
+		}
+		if entry.dependencies.iter().any(|(key, hash)| self.dependency_hash(key) != *hash) {
+			return None;
+		}
+		let path_str = path.display().to_string();
+		Some(
+			entry
+				.errors
+				.iter()
+				.map(|error| ValidationError {
+					path: path_str.clone(),
+					..error.clone()
+				})
+				.collect(),
+		)
+	}
+
+	fn dependency_hash(&self, key: &str) -> String {
+		self.dependency_hashes.get(key).cloned().unwrap_or_default()
 	}
 
-	fn validate_cached_composition(
+	fn check_cached_composition(
 		&self,
 		path: &Path,
 		cached: &CachedComposition,
-	) -> Vec<ValidationError> {
+	) -> (Vec<ValidationError>, Option<CompositionSummary>) {
 		let path_str = path.display().to_string();
 		match cached {
-			CachedComposition::ReadError(message) => vec![ValidationError {
-				path: path_str,
-				message: format!("Failed to read file: {}", message),
-			}],
+			CachedComposition::ReadError(message) => (
+				vec![ValidationError::new(path_str, ErrorCode::Io, format!("Failed to read file: {}", message))],
+				None,
+			),
 			CachedComposition::InvalidJson {
 				message,
 				has_multiple_spaces,
 			} => {
 				let mut errors = Vec::new();
 				if *has_multiple_spaces {
-					errors.push(ValidationError {
-						path: path_str.clone(),
-						message: "Contains multiple consecutive spaces".into(),
-					});
+					errors.push(ValidationError::new(
+						path_str.clone(),
+						ErrorCode::Whitespace,
+						"Contains multiple consecutive spaces",
+					));
 				}
-				errors.push(ValidationError {
-					path: path_str,
-					message: format!("Invalid JSON: {}", message),
-				});
-				errors
+				errors.push(ValidationError::new(path_str, ErrorCode::InvalidJson, format!("Invalid JSON: {}", message)));
+				(errors, None)
 			}
 			CachedComposition::Parsed {
 				value,
@@ -507,39 +866,123 @@ impl Validator {
 			} => {
 				let mut errors = Vec::new();
 				if *has_multiple_spaces {
-					errors.push(ValidationError {
-						path: path_str.clone(),
-						message: "Contains multiple consecutive spaces".into(),
-					});
+					errors.push(ValidationError::new(
+						path_str.clone(),
+						ErrorCode::Whitespace,
+						"Contains multiple consecutive spaces",
+					));
 				}
 				let schema_errors = self.composition_schema.validate(value, &path_str);
 				if !schema_errors.is_empty() {
 					errors.extend(schema_errors);
-					return errors;
+					return (errors, None);
 				}
-				self.validate_composition_value(path, value, errors)
+				self.check_composition_value(path, value, errors)
 			}
 		}
 	}
 
-	fn validate_composition_value(
+	fn check_composition_value(
 		&self,
 		path: &Path,
 		value: &Value,
 		mut errors: Vec<ValidationError>,
-	) -> Vec<ValidationError> {
+	) -> (Vec<ValidationError>, Option<CompositionSummary>) {
 		let path_str = path.display().to_string();
 		let Some(comp) = deserialize_model::<Composition>(value, &path_str, &mut errors) else {
-			return errors;
+			return (errors, None);
 		};
 
 		errors.extend(self.validate_id(&comp.id, path, &path_str));
-		errors.extend(self.validate_key(&comp.key, &path_str));
+		errors.extend(validate_keys(&comp, &path_str));
+		errors.extend(self.validate_instrumentation(&comp.instrumentation, &path_str));
 		errors.extend(self.validate_attribution(&comp.attribution, &path_str, true));
-		errors.extend(self.validate_current_catalog_uniqueness(&comp, &path_str));
+		errors.extend(self.validate_movement_catalogs(&comp, &path_str));
+		errors.extend(validate_relations(&comp, &path_str));
+		errors.extend(self.validate_people(&comp, &path_str));
+		errors.extend(self.validate_sources(&comp, &path_str));
+		errors.extend(self.validate_chorales(&comp, &path_str));
+		errors.extend(self.validate_forms(&comp, &path_str));
+		errors.extend(validate_incipits(&comp, &path_str));
+		errors.extend(validate_occasion(&comp, &path_str));
+		errors.extend(validate_repeated_identifiers(&comp, &path_str));
+		let summary = CompositionSummary::of(&comp, current_catalog_identifiers(&comp));
+		(errors, Some(summary))
+	}
+
+	/// Checks against other compositions: relation targets and shared current
+	/// identifiers. These are never cached.
+	fn validate_composition_links(&self, summary: &CompositionSummary, path_str: &str) -> Vec<ValidationError> {
+		let mut errors = Vec::new();
+		for relation in &summary.relations {
+			if relation.target == summary.id {
+				continue;
+			}
+			let location = format!("relations[{}]", relation.index);
+			let mut report = |message: String| {
+				errors.push(ValidationError::new(
+					path_str.to_string(),
+					ErrorCode::InvalidRelation,
+					format!("{}: {}", location, message),
+				).at(&location));
+			};
+
+			let Some(target) = self.composition_ids.get(&relation.target) else {
+				report(format!("target composition '{}' not found", relation.target));
+				continue;
+			};
+			if let Some(movement) = &relation.target_movement {
+				let found = self.composition_summaries.get(target).is_some_and(|target| {
+					parse_movement_path(movement).is_some_and(|path| target.movements.contains(&path))
+				});
+				if !found {
+					report(format!(
+						"movement '{}' not found in target composition '{}'",
+						movement, relation.target
+					));
+				}
+			}
+		}
+
+		let mut seen = HashSet::new();
+		for key in &summary.identifiers {
+			if !seen.insert(key) {
+				continue;
+			}
+			let (composer, scheme, number) = key;
+			if let Some(ids) = self.current_catalog_targets.get(key) {
+				let mut unique: Vec<_> = ids.iter().cloned().collect::<HashSet<_>>().into_iter().collect();
+				if unique.len() > 1 {
+					unique.sort();
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::DuplicateIdentifier,
+						format!(
+							"current catalog identifier {}:{}:{} is shared by compositions {}",
+							composer, scheme, number, unique.join(", ")
+						),
+					));
+				}
+			}
+		}
+
 		errors
 	}
 
+	/// A cache entry for a composition checked in this run.
+	fn cache_entry(&self, path: &Path, errors: &[ValidationError], summary: CompositionSummary) -> Option<CacheEntry> {
+		Some(CacheEntry {
+			hash: self.composition_hashes.get(path)?.clone(),
+			dependencies: summary
+				.dependencies
+				.iter()
+				.map(|key| (key.clone(), self.dependency_hash(key)))
+				.collect(),
+			summary,
+			errors: errors.to_vec(),
+		})
+	}
+
 	fn validate_catalog_definition_domain(
 		&self,
 		definition: &CatalogDefinition,
@@ -548,59 +991,96 @@ impl Validator {
 	) -> Vec<ValidationError> {
 		let mut errors = Vec::new();
 		if let Err(message) = validate_catalog_formats(definition) {
-			errors.push(ValidationError {
-				path: path_str.to_string(),
-				message: format!("{}: {}", location, message),
-			});
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::InvalidCatalog,
+				format!("{}: {}", location, message),
+			));
 		}
 		if let Err(message) = validate_catalog_case_rules(definition) {
-			errors.push(ValidationError {
-				path: path_str.to_string(),
-				message: format!("{}: {}", location, message),
-			});
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::InvalidCatalog,
+				format!("{}: {}", location, message),
+			));
+		}
+		if let Err(message) = validate_input_rules(definition) {
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::InvalidCatalog,
+				format!("{}: {}", location, message),
+			));
 		}
-		let capture_count = match definition.pattern.as_deref() {
-			Some(pattern) => match cached_regex_result(pattern) {
-				Ok(regex) => Some(regex.captures_len().saturating_sub(1)),
+		let patterns: Vec<Rc<Regex>> = definition_patterns(definition)
+			.filter_map(|pattern| match cached_regex_result(pattern) {
+				Ok(regex) => Some(regex),
 				Err(error) => {
-					errors.push(ValidationError {
-						path: path_str.to_string(),
-						message: format!("{}: invalid catalog pattern: {}", location, error),
-					});
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::InvalidCatalog,
+						format!("{}: invalid catalog pattern: {}", location, error),
+					));
 					None
 				}
-			},
-			None => None,
-		};
+			})
+			.collect();
+
+		// An alternative may leave out optional components, but every group
+		// the rules refer to must exist in some pattern.
+		let referenced = definition
+			.sort_keys
+			.iter()
+			.flatten()
+			.map(|key| ("sort key", &key.group))
+			.chain(definition.group_by.iter().flatten().map(|group| ("group_by", group)))
+			.chain(definition.constraints.iter().flatten().map(|constraint| ("constraint", &constraint.group)));
+		for (kind, group) in referenced {
+			let resolves = |regex: &Rc<Regex>| match group {
+				CatalogGroup::Index(index) => *index >= 1 && *index < regex.captures_len(),
+				CatalogGroup::Name(name) => regex.capture_names().any(|candidate| candidate == Some(name.as_str())),
+			};
+			if patterns.is_empty() || patterns.iter().any(resolves) {
+				continue;
+			}
+			let message = match (group, patterns.as_slice()) {
+				(CatalogGroup::Index(index), [regex]) => format!(
+					"{}: {} group {} exceeds pattern capture count {}",
+					location,
+					kind,
+					index,
+					regex.captures_len() - 1
+				),
+				(CatalogGroup::Index(index), _) => format!(
+					"{}: {} group {} exceeds the capture count of every pattern",
+					location, kind, index
+				),
+				(CatalogGroup::Name(name), _) => format!(
+					"{}: {} group '{}' is not named in any pattern",
+					location, kind, name
+				),
+			};
+			errors.push(ValidationError::new(path_str.to_string(), ErrorCode::InvalidCatalog, message));
+		}
 
 		if let Some(constraints) = &definition.constraints {
 			for constraint in constraints {
-				if let Some(count) = capture_count {
-					if constraint.group > count {
-						errors.push(ValidationError {
-							path: path_str.to_string(),
-							message: format!(
-								"{}: constraint group {} exceeds pattern capture count {}",
-								location, constraint.group, count
-							),
-						});
-					}
-				}
 				if let (Some(min), Some(max)) = (constraint.min, constraint.max) {
 					if min > max {
-						errors.push(ValidationError {
-							path: path_str.to_string(),
-							message: format!("{}: constraint group {} has min {} greater than max {}", location, constraint.group, min, max),
-						});
+						errors.push(ValidationError::new(
+							path_str.to_string(),
+							ErrorCode::InvalidCatalog,
+							format!("{}: constraint group {} has min {} greater than max {}", location, constraint.group, min, max),
+						));
 					}
 				}
 				if let Some(ranges) = &constraint.ranges {
 					for range in ranges {
 						if range.min > range.max {
-							errors.push(ValidationError {
-								path: path_str.to_string(),
-								message: format!("{}: constraint group {} has range {}..{}", location, constraint.group, range.min, range.max),
-							});
+							errors.push(ValidationError::new(
+								path_str.to_string(),
+								ErrorCode::InvalidCatalog,
+								format!("{}: constraint group {} has range {}..{}", location, constraint.group, range.min, range.max),
+							));
 						}
 					}
 				}
@@ -622,10 +1102,11 @@ impl Validator {
 
 		if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
 			if composer.id != stem {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!("Composer ID '{}' doesn't match filename '{}'", composer.id, stem),
-				});
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::IdMismatch,
+					format!("Composer ID '{}' doesn't match filename '{}'", composer.id, stem),
+				).at("id"));
 			}
 		}
 
@@ -635,26 +1116,31 @@ impl Validator {
 				.as_ref()
 				.map_or(false, |catalogs| catalogs.contains_key(default_scheme));
 			if !defined_locally && !self.global_catalog_schemes.contains(default_scheme) {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!("default_scheme '{}' is not defined for this composer or globally", default_scheme),
-				});
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::UnknownScheme,
+					format!("default_scheme '{}' is not defined for this composer or globally", default_scheme),
+				).at("default_scheme"));
 			}
 		}
 
 		if let Some(catalogs) = &composer.catalogs {
 			for (scheme, definition) in catalogs {
 				match load_catalog_def(&self.data_dir, scheme, Some(&composer.id)) {
-					Ok(Some(effective)) => errors.extend(self.validate_catalog_definition_domain(
-						&effective,
-						&path_str,
-						&format!("catalog '{}'", scheme),
-					)),
+					Ok(Some(effective)) => {
+						let location = format!("catalog '{}'", scheme);
+						errors.extend(self.validate_catalog_definition_domain(&effective, &path_str, &location));
+						// Examples inherited from a global catalog are checked there.
+						if definition.examples.is_some() {
+							errors.extend(catalog_example_errors(scheme, &effective, &path_str, &location));
+						}
+					}
 					Ok(None) => {}
-					Err(error) => errors.push(ValidationError {
-						path: path_str.clone(),
-						message: format!("catalog '{}': {}", scheme, error),
-					}),
+					Err(error) => errors.push(ValidationError::new(
+						path_str.clone(),
+						ErrorCode::InvalidCatalog,
+						format!("catalog '{}': {}", scheme, error),
+					)),
 				}
 				if let Some(current_edition) = &definition.current_edition {
 					let defined = definition
@@ -662,13 +1148,14 @@ impl Validator {
 						.as_ref()
 						.map_or(false, |editions| editions.contains_key(current_edition));
 					if !defined {
-						errors.push(ValidationError {
-							path: path_str.clone(),
-							message: format!(
+						errors.push(ValidationError::new(
+							path_str.clone(),
+							ErrorCode::UnknownEdition,
+							format!(
 								"catalog '{}': current_edition '{}' is not defined in editions",
 								scheme, current_edition
 							),
-						});
+						));
 					}
 				}
 			}
@@ -692,14 +1179,18 @@ impl Validator {
 			path.file_stem().and_then(|s| s.to_str()),
 		) {
 			if id != stem {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!("Catalog ID '{}' doesn't match filename '{}'", id, stem),
-				});
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::IdMismatch,
+					format!("Catalog ID '{}' doesn't match filename '{}'", id, stem),
+				).at("id"));
 			}
 		}
 
 		errors.extend(self.validate_catalog_definition_domain(&catalog, &path_str, "catalog"));
+		if let Some(scheme) = path.file_stem().and_then(|s| s.to_str()) {
+			errors.extend(catalog_example_errors(scheme, &catalog, &path_str, "catalog"));
+		}
 		errors
 	}
 
@@ -715,23 +1206,26 @@ impl Validator {
 
 		if let Some(expected) = collection_id_from_path(path) {
 			if collection.id != expected {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!("Collection ID '{}' doesn't match path (expected '{}')", collection.id, expected),
-				});
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::IdMismatch,
+					format!("Collection ID '{}' doesn't match path (expected '{}')", collection.id, expected),
+				).at("id"));
 			}
 		}
 
 		if let Some(composer) = &collection.composer {
 			if !self.composers.contains(composer) {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!("composer '{}' not found in composers/", composer),
-				});
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::UnknownComposer,
+					format!("composer '{}' not found in composers/", composer),
+				).at("composer"));
 			}
 		}
 
 		errors.extend(self.validate_attribution(&collection.attribution, &path_str, false));
+		errors.extend(self.check_sources(attribution_sources(&collection.attribution), &path_str));
 
 		let composer = collection
 			.attribution
@@ -741,28 +1235,31 @@ impl Validator {
 			.or_else(|| collection.id.split_once('-').map(|(composer, _)| composer));
 
 		let Some(composer) = composer else {
-			errors.push(ValidationError {
-				path: path_str,
-				message: "cannot determine collection composer".into(),
-			});
+			errors.push(ValidationError::new(
+				path_str,
+				ErrorCode::MissingComposer,
+				"cannot determine collection composer",
+			));
 			return errors;
 		};
 
 		if !self.composers.contains(composer) {
-			errors.push(ValidationError {
-				path: path_str.clone(),
-				message: format!("collection composer '{}' not found in composers/", composer),
-			});
+			errors.push(ValidationError::new(
+				path_str.clone(),
+				ErrorCode::UnknownComposer,
+				format!("collection composer '{}' not found in composers/", composer),
+			));
 		}
 
 		if !self.catalog_is_allowed_for_composer(composer, &collection.scheme) {
-			errors.push(ValidationError {
-				path: path_str.clone(),
-				message: format!(
+			errors.push(ValidationError::new(
+				path_str.clone(),
+				ErrorCode::UnknownScheme,
+				format!(
 					"catalog scheme '{}' is not defined for composer '{}' or globally",
 					collection.scheme, composer
 				),
-			});
+			).at("scheme"));
 			return errors;
 		}
 
@@ -770,10 +1267,11 @@ impl Validator {
 		for (i, number) in collection.compositions.iter().enumerate() {
 			let location = format!("compositions[{}]", i);
 			if !seen.insert(number) {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!("{}: duplicate collection member '{}'", location, number),
-				});
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::DuplicateMember,
+					format!("{}: duplicate collection member '{}'", location, number),
+				).at(&location));
 			}
 
 			errors.extend(self.validate_catalog_reference(
@@ -787,20 +1285,22 @@ impl Validator {
 
 			let key = (composer.to_string(), collection.scheme.clone(), number.clone());
 			match self.current_catalog_targets.get(&key) {
-				None => errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!(
+				None => errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::UnresolvedMember,
+					format!(
 						"{}: {}:{}:{} does not resolve to a current composition",
 						location, composer, collection.scheme, number
 					),
-				}),
+				).at(&location)),
 				Some(ids) => {
 					let unique: HashSet<_> = ids.iter().collect();
 					if unique.len() > 1 {
-						errors.push(ValidationError {
-							path: path_str.clone(),
-							message: format!("{}: catalog identifier resolves ambiguously", location),
-						});
+						errors.push(ValidationError::new(
+							path_str.clone(),
+							ErrorCode::AmbiguousMember,
+							format!("{}: catalog identifier resolves ambiguously", location),
+						).at(&location));
 					}
 				}
 			}
@@ -814,41 +1314,41 @@ impl Validator {
 		let inventory = match crate::inventory::load_inventory(path) {
 			Ok(inventory) => inventory,
 			Err(error) => {
-				return vec![ValidationError {
-					path: path_str,
-					message: error.to_string(),
-				}];
+				return vec![ValidationError::new(path_str, ErrorCode::InvalidInventory, error.to_string())];
 			}
 		};
 		let mut errors = Vec::new();
 
 		if !self.composers.contains(&inventory.composer) {
-			errors.push(ValidationError {
-				path: path_str.clone(),
-				message: format!("composer '{}' not found in composers/", inventory.composer),
-			});
+			errors.push(ValidationError::new(
+				path_str.clone(),
+				ErrorCode::UnknownComposer,
+				format!("composer '{}' not found in composers/", inventory.composer),
+			).at("composer"));
 			return errors;
 		}
 		if !self.catalog_is_allowed_for_composer(&inventory.composer, &inventory.scheme) {
-			errors.push(ValidationError {
-				path: path_str.clone(),
-				message: format!(
+			errors.push(ValidationError::new(
+				path_str.clone(),
+				ErrorCode::UnknownScheme,
+				format!(
 					"catalog scheme '{}' is not defined for composer '{}' or globally",
 					inventory.scheme, inventory.composer
 				),
-			});
+			).at("scheme"));
 			return errors;
 		}
 
 		if let Some(path_composer) = inventory_composer_from_path(path) {
 			if path_composer != inventory.composer {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!(
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::InvalidInventory,
+					format!(
 						"inventory composer '{}' does not match path composer '{}'",
 						inventory.composer, path_composer
 					),
-				});
+				).at("composer"));
 			}
 		}
 
@@ -859,10 +1359,7 @@ impl Validator {
 		) {
 			Ok(definition) => definition,
 			Err(error) => {
-				errors.push(ValidationError {
-					path: path_str,
-					message: error.to_string(),
-				});
+				errors.push(ValidationError::new(path_str, ErrorCode::InvalidCatalog, error.to_string()));
 				return errors;
 			}
 		};
@@ -872,13 +1369,14 @@ impl Validator {
 				.and_then(|definition| definition.editions.as_ref())
 				.map_or(false, |editions| editions.contains_key(edition));
 			if !defined {
-				errors.push(ValidationError {
-					path: path_str.clone(),
-					message: format!(
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::UnknownEdition,
+					format!(
 						"edition '{}' is not defined for {}:{}",
 						edition, inventory.composer, inventory.scheme
 					),
-				});
+				).at("edition"));
 			}
 		}
 
@@ -895,10 +1393,7 @@ impl Validator {
 					));
 				}
 			}
-			Err(message) => errors.push(ValidationError {
-				path: path_str,
-				message,
-			}),
+			Err(message) => errors.push(ValidationError::new(path_str, ErrorCode::InvalidInventory, message)),
 		}
 
 		errors
@@ -914,28 +1409,23 @@ impl Validator {
 		let content = match fs::read_to_string(path) {
 			Ok(content) => content,
 			Err(error) => {
-				return Err(vec![ValidationError {
-					path: path_str,
-					message: format!("Failed to read file: {}", error),
-				}]);
+				return Err(vec![ValidationError::new(path_str, ErrorCode::Io, format!("Failed to read file: {}", error))]);
 			}
 		};
 		let mut errors = Vec::new();
 
 		if check_spacing && content.contains("  ") {
-			errors.push(ValidationError {
-				path: path_str.clone(),
-				message: "Contains multiple consecutive spaces".into(),
-			});
+			errors.push(ValidationError::new(
+				path_str.clone(),
+				ErrorCode::Whitespace,
+				"Contains multiple consecutive spaces",
+			));
 		}
 
 		let value: Value = match serde_json::from_str(&content) {
 			Ok(value) => value,
 			Err(error) => {
-				errors.push(ValidationError {
-					path: path_str,
-					message: format!("Invalid JSON: {}", error),
-				});
+				errors.push(ValidationError::new(path_str, ErrorCode::InvalidJson, format!("Invalid JSON: {}", error)));
 				return Err(errors);
 			}
 		};
@@ -953,103 +1443,399 @@ impl Validator {
 		let mut errors = Vec::new();
 
 		if !id_pattern().is_match(id) {
-			errors.push(ValidationError {
-				path: path_str.to_string(),
-				message: format!("ID '{}' is not 8 lowercase hex characters", id),
-			});
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::InvalidId,
+				format!("ID '{}' is not 8 lowercase hex characters", id),
+			).at("id"));
 			return errors;
 		}
 
 		let expected_id = extract_id_from_path(path).ok();
 		if let Some(expected) = expected_id {
 			if expected != id {
-				errors.push(ValidationError {
-					path: path_str.to_string(),
-					message: format!("ID '{}' doesn't match path (expected '{}')", id, expected),
-				});
+				errors.push(ValidationError::new(
+					path_str.to_string(),
+					ErrorCode::IdMismatch,
+					format!("ID '{}' doesn't match path (expected '{}')", id, expected),
+				).at("id"));
 			}
 		}
 
 		errors
 	}
 
-	fn validate_key(&self, key: &Option<String>, path_str: &str) -> Vec<ValidationError> {
-		let mut errors = Vec::new();
+	fn validate_person_file(&self, path: &Path) -> Vec<ValidationError> {
+		let (value, mut errors) = match self.read_and_validate(path, &self.person_schema, false) {
+			Ok(result) => result,
+			Err(errors) => return errors,
+		};
+		let path_str = path.display().to_string();
+		let Some(person) = deserialize_model::<Person>(&value, &path_str, &mut errors) else {
+			return errors;
+		};
 
-		if let Some(k) = key {
-			if !key_pattern().is_match(k) {
-				errors.push(ValidationError {
-					path: path_str.to_string(),
-					message: format!("Invalid key format: '{}'", k),
-				});
+		if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+			if person.id != stem {
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::IdMismatch,
+					format!("Person ID '{}' doesn't match filename '{}'", person.id, stem),
+				).at("id"));
 			}
 		}
-
 		errors
 	}
 
-	fn validate_attribution(
-		&self,
-		attribution: &[AttributionEntry],
-		path_str: &str,
-		require_nonempty: bool,
-	) -> Vec<ValidationError> {
-		let mut errors = Vec::new();
-
-		if require_nonempty && attribution.is_empty() {
-			errors.push(ValidationError {
-				path: path_str.to_string(),
-				message: "Attribution array is empty".into(),
-			});
+	fn validate_reference_file(&self, path: &Path) -> Vec<ValidationError> {
+		let (value, mut errors) = match self.read_and_validate(path, &self.reference_schema, false) {
+			Ok(result) => result,
+			Err(errors) => return errors,
+		};
+		let path_str = path.display().to_string();
+		let Some(reference) = deserialize_model::<Reference>(&value, &path_str, &mut errors) else {
 			return errors;
+		};
+
+		if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+			if reference.id != stem {
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::IdMismatch,
+					format!("Reference ID '{}' doesn't match filename '{}'", reference.id, stem),
+				).at("id"));
+			}
 		}
+		errors
+	}
 
-		for (i, entry) in attribution.iter().enumerate() {
-			if let Some(composer) = &entry.composer {
-				if !self.composers.contains(composer) {
-					errors.push(ValidationError {
-						path: path_str.to_string(),
-						message: format!(
-							"attribution[{}]: composer '{}' not found in composers/",
-							i, composer
-						),
-					});
-				}
+	fn validate_chorale_file(&self, path: &Path) -> Vec<ValidationError> {
+		let (value, mut errors) = match self.read_and_validate(path, &self.chorale_schema, false) {
+			Ok(result) => result,
+			Err(errors) => return errors,
+		};
+		let path_str = path.display().to_string();
+		let Some(chorale) = deserialize_model::<ChoraleMelody>(&value, &path_str, &mut errors) else {
+			return errors;
+		};
+
+		if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+			if chorale.id != stem {
+				errors.push(ValidationError::new(
+					path_str.clone(),
+					ErrorCode::IdMismatch,
+					format!("Chorale ID '{}' doesn't match filename '{}'", chorale.id, stem),
+				).at("id"));
 			}
+		}
+		errors
+	}
 
-			if let Some(catalog) = &entry.catalog {
+	fn validate_taxonomy_file(&self, path: &Path) -> Vec<ValidationError> {
+		let (value, mut errors) = match self.read_and_validate(path, &self.form_taxonomy_schema, false) {
+			Ok(result) => result,
+			Err(errors) => return errors,
+		};
+		let path_str = path.display().to_string();
+		let Some(forms) = deserialize_model::<FormTaxonomy>(&value, &path_str, &mut errors) else {
+			return errors;
+		};
+		errors.extend(forms.check().into_iter().map(|message| ValidationError::new(path_str.clone(), ErrorCode::InvalidTaxonomy, message)));
+		errors
+	}
+
+	fn validate_instruments_file(&self, path: &Path) -> Vec<ValidationError> {
+		let path_str = path.display().to_string();
+		let instruments = match read_instrument_vocabulary(path) {
+			Ok(Some(instruments)) => instruments,
+			Ok(None) => return Vec::new(),
+			Err(error @ InstrumentVocabularyError::Io { .. }) => {
+				return vec![ValidationError::new(path_str, ErrorCode::Io, error.to_string())];
+			}
+			Err(error) => return vec![ValidationError::new(path_str, ErrorCode::InvalidTaxonomy, error.to_string())],
+		};
+		instruments
+			.check()
+			.into_iter()
+			.map(|message| ValidationError::new(path_str.clone(), ErrorCode::InvalidTaxonomy, message))
+			.collect()
+	}
+
+	/// Work and movement forms must be in the form taxonomy, when the dataset
+	/// has one. Compound movement forms are checked part by part.
+	fn validate_forms(&self, comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+		let Some(forms) = &self.forms else {
+			return Vec::new();
+		};
+		let named = std::iter::once(("form".to_string(), comp.form.as_str())).chain(
+			walk_movements(comp).into_iter().filter_map(|visit| {
+				let form = visit.node.form()?;
+				Some((format!("{}.form", visit.location), form))
+			}),
+		);
+		let mut errors = Vec::new();
+		for (location, form) in named {
+			for part in forms.unknown_parts(form) {
+				let message = match forms.suggest(part) {
+					Some(suggestion) => format!("{}: unknown form '{}' (did you mean '{}'?)", location, part, suggestion),
+					None => format!("{}: unknown form '{}'", location, part),
+				};
+				errors.push(ValidationError::new(path_str.to_string(), ErrorCode::UnknownForm, message).at(&location));
+			}
+		}
+		errors
+	}
+
+	/// Chorale melodies named by the work or its movements must exist in
+	/// `chorales/`.
+	fn validate_chorales(&self, comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+		let named = comp
+			.chorale
+			.as_deref()
+			.map(|chorale| ("chorale".to_string(), chorale))
+			.into_iter()
+			.chain(walk_movements(comp).into_iter().filter_map(|visit| {
+				let chorale = visit.node.chorale()?;
+				Some((format!("{}.chorale", visit.location), chorale))
+			}));
+		named
+			.filter(|(_, chorale)| !self.chorales.contains(*chorale))
+			.map(|(location, chorale)| ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::UnknownChorale,
+				format!("{}: chorale '{}' not found in chorales/", location, chorale),
+			).at(&location))
+			.collect()
+	}
+
+	/// Sources cited by attribution and catalog entries, including
+	/// movement-level catalog numbers, must exist in `references/`.
+	fn validate_sources(&self, comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+		let mut cited = attribution_sources(&comp.attribution);
+		for visit in walk_movements(comp) {
+			for (j, catalog) in visit.node.catalog().iter().enumerate() {
+				cited.push((
+					format!("{}.catalog[{}]", visit.location, j),
+					catalog.sources.as_deref().unwrap_or_default(),
+				));
+			}
+		}
+		self.check_sources(cited, path_str)
+	}
+
+	fn check_sources(&self, cited: Vec<(String, &[SourceRef])>, path_str: &str) -> Vec<ValidationError> {
+		let mut errors = Vec::new();
+		for (location, sources) in cited {
+			for (k, source) in sources.iter().enumerate() {
+				if !self.references.contains(&source.reference) {
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::UnknownReference,
+						format!(
+							"{}.sources[{}]: reference '{}' not found in references/",
+							location, k, source.reference
+						),
+					).at(&format!("{}.sources[{}]", location, k)));
+				}
+			}
+		}
+		errors
+	}
+
+	fn validate_people(&self, comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+		let mut errors = Vec::new();
+		let mut check = |location: String, person: &str| {
+			if !self.people.contains(person) && !self.composers.contains(person) {
+				errors.push(ValidationError::new(
+					path_str.to_string(),
+					ErrorCode::UnknownPerson,
+					format!("{}: person '{}' not found in people/ or composers/", location, person),
+				).at(&location));
+			}
+		};
+
+		for (i, person_ref) in comp.people.iter().flatten().enumerate() {
+			check(format!("people[{}]", i), &person_ref.person);
+		}
+		if let Some(author) = comp.text.as_ref().and_then(|text| text.author.as_deref()) {
+			check("text.author".into(), author);
+		}
+		for visit in walk_movements(comp) {
+			if let Some(author) = visit.node.text().and_then(|text| text.author.as_deref()) {
+				check(format!("{}.text.author", visit.location), author);
+			}
+		}
+		errors
+	}
+
+	fn validate_instrumentation(
+		&self,
+		instrumentation: &Option<Instrumentation>,
+		path_str: &str,
+	) -> Vec<ValidationError> {
+		let (Some(Instrumentation::Parts(parts)), Some(instruments)) = (instrumentation, &self.instruments) else {
+			return Vec::new();
+		};
+
+		let mut errors = Vec::new();
+		for (i, part) in parts.iter().enumerate() {
+			let ids = std::iter::once(&part.instrument).chain(part.alternatives.iter().flatten());
+			for id in ids {
+				if instruments.get(id).is_none() {
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::UnknownInstrument,
+						format!("instrumentation[{}]: unknown instrument '{}'", i, id),
+					).at(&format!("instrumentation[{}]", i)));
+				}
+			}
+		}
+		errors
+	}
+
+	fn validate_dates(
+		&self,
+		dates: &Dates,
+		composer: Option<&str>,
+		path_str: &str,
+		location: &str,
+	) -> Vec<ValidationError> {
+		let lifespan = composer.and_then(|composer| self.composer_lifespans.get(composer));
+		let fields = [
+			("composed", dates.composed, true),
+			("published", dates.published, false),
+			("premiered", dates.premiered, false),
+			("revised", dates.revised, true),
+		];
+
+		let mut errors = Vec::new();
+		let finding = |code, field: &str, message: String| {
+			ValidationError::new(path_str.to_string(), code, format!("{}.{}: {}", location, field, message))
+				.at(&format!("{}.{}", location, field))
+		};
+		for (field, date, during_lifetime) in fields {
+			let Some(date) = date else { continue };
+			if !date.is_ordered() {
+				errors.push(finding(ErrorCode::InvalidDate, field, format!("range '{}' ends before it starts", date)));
+				continue;
+			}
+			let Some(lifespan) = lifespan else { continue };
+			if let (Some(born), Some(latest)) = (lifespan.born, date.latest) {
+				if latest.year < born.year {
+					errors.push(finding(
+						ErrorCode::OutsideLifespan,
+						field,
+						format!("{} is before the composer's birth ({})", date, born),
+					).warning());
+				}
+			}
+			if let (Some(died), Some(earliest)) = (lifespan.died, date.earliest) {
+				if earliest.year > died.year {
+					let message = format!("{} is after the composer's death ({})", date, died);
+					// Publication and premieres may be posthumous.
+					let code = if during_lifetime { ErrorCode::OutsideLifespan } else { ErrorCode::PosthumousDate };
+					errors.push(finding(code, field, message).warning());
+				}
+			}
+		}
+
+		let first_composed = dates.composed.and_then(|date| date.earliest);
+		let last_revised = dates.revised.and_then(|date| date.latest);
+		if let (Some(composed), Some(revised)) = (first_composed, last_revised) {
+			if is_before(revised, composed) {
+				errors.push(finding(
+					ErrorCode::RevisionOrder,
+					"revised",
+					format!("{} is before the work was composed ({})", revised, composed),
+				).warning());
+			}
+		}
+		errors
+	}
+
+	fn validate_attribution(
+		&self,
+		attribution: &[AttributionEntry],
+		path_str: &str,
+		require_nonempty: bool,
+	) -> Vec<ValidationError> {
+		let mut errors = Vec::new();
+
+		if require_nonempty && attribution.is_empty() {
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::EmptyAttribution,
+				"Attribution array is empty",
+			).at("attribution"));
+			return errors;
+		}
+
+		errors.extend(validate_attribution_history(attribution, path_str));
+
+		let fallback_composer = current_composer(attribution);
+		for (i, entry) in attribution.iter().enumerate() {
+			if let Some(dates) = &entry.dates {
+				let composer = entry.principal().or(fallback_composer);
+				errors.extend(self.validate_dates(dates, composer, path_str, &format!("attribution[{}].dates", i)));
+			}
+
+			if let Some(composer) = &entry.composer {
+				if !self.composers.contains(composer) {
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::UnknownComposer,
+						format!(
+							"attribution[{}]: composer '{}' not found in composers/",
+							i, composer
+						),
+					).at(&format!("attribution[{}].composer", i)));
+				}
+			}
+
+			for (j, contributor) in entry.contributors.iter().flatten().enumerate() {
+				if !self.composers.contains(&contributor.composer) && !self.people.contains(&contributor.composer) {
+					errors.push(ValidationError::new(
+						path_str.to_string(),
+						ErrorCode::UnknownComposer,
+						format!(
+							"attribution[{}].contributors[{}]: '{}' not found in composers/ or people/",
+							i, j, contributor.composer
+						),
+					).at(&format!("attribution[{}].contributors[{}]", i, j)));
+				}
+			}
+
+			if let Some(catalog) = &entry.catalog {
 				for (j, cat) in catalog.iter().enumerate() {
 					let location = format!("attribution[{}].catalog[{}]", i, j);
-					let scheme_defined = if let Some(composer) = &entry.composer {
+					let scheme_defined = if let Some(composer) = entry.principal() {
 						self.catalog_is_allowed_for_composer(composer, &cat.scheme)
 					} else {
 						self.catalog_schemes.contains(&cat.scheme)
 					};
 
 					if !scheme_defined {
-						errors.push(ValidationError {
-							path: path_str.to_string(),
-							message: if let Some(composer) = &entry.composer {
-								format!(
-									"{}: catalog scheme '{}' is not defined for composer '{}' or globally",
-									location, cat.scheme, composer
-								)
-							} else {
-								format!("{}: catalog scheme '{}' not defined", location, cat.scheme)
-							},
-						});
+						let message = match entry.principal() {
+							Some(composer) => format!(
+								"{}: catalog scheme '{}' is not defined for composer '{}' or globally",
+								location, cat.scheme, composer
+							),
+							None => format!("{}: catalog scheme '{}' not defined", location, cat.scheme),
+						};
+						errors.push(ValidationError::new(path_str.to_string(), ErrorCode::UnknownScheme, message).at(&location));
 					}
 
 					if cat.scheme != cat.scheme.to_lowercase() {
-						errors.push(ValidationError {
-							path: path_str.to_string(),
-							message: format!("{}: catalog scheme '{}' must be lowercase", location, cat.scheme),
-						});
+						errors.push(ValidationError::new(
+							path_str.to_string(),
+							ErrorCode::SchemeCase,
+							format!("{}: catalog scheme '{}' must be lowercase", location, cat.scheme),
+						).at(&location));
 					}
 
 
 					if scheme_defined {
-						if let Some(composer) = &entry.composer {
+						if let Some(composer) = entry.principal() {
 							errors.extend(self.validate_catalog_reference(
 								composer,
 								&cat.scheme,
@@ -1084,10 +1870,25 @@ impl Validator {
 		let mut paths = self.validation_paths.clone();
 		paths.sort();
 
+		let mut cache = ValidationCache::new(self.context.clone());
 		let mut errors = self.discovery_errors.clone();
 		for path in &paths {
-			errors.extend(self.validate_file(path));
+			if data_kind(path) != Some(DataKind::Composition) {
+				errors.extend(self.validate_file(path));
+				continue;
+			}
+			let (local, summary) = self.composition_errors(path);
+			errors.extend(local.iter().cloned());
+			let Some(summary) = summary else {
+				continue;
+			};
+			errors.extend(self.validate_composition_links(&summary, &path.display().to_string()));
+			if let (Some(key), Some(entry)) = (cache_key(&self.data_dir, path), self.cache_entry(path, &local, summary)) {
+				cache.files.insert(key, entry);
+			}
 		}
+		// The cache only saves time; a read-only dataset still validates.
+		let _ = cache.save(&self.data_dir);
 
 		let mut inventory_identities: HashMap<(String, String, Option<String>), Vec<String>> = HashMap::new();
 		for path in paths.iter().filter(|path| data_kind(path) == Some(DataKind::Inventory)) {
@@ -1108,27 +1909,69 @@ impl Validator {
 				|| format!("{}:{}", composer, scheme),
 				|edition| format!("{}:{} edition {}", composer, scheme, edition),
 			);
-			errors.push(ValidationError {
-				path: duplicate_paths.join(", "),
-				message: format!("duplicate inventory identity {}", identity),
-			});
+			errors.push(ValidationError::new(
+				duplicate_paths.join(", "),
+				ErrorCode::DuplicateInventory,
+				format!("duplicate inventory identity {}", identity),
+			));
 		}
 
 		errors
 	}
 }
 
+/// The `(composer, scheme, number)` identifiers a composition currently holds:
+/// the first entry per scheme in each attribution, then the first per scheme
+/// on each movement under the current composer.
+fn current_catalog_identifiers(composition: &Composition) -> Vec<(String, String, String)> {
+	let mut identifiers = Vec::new();
+	let mut schemes_seen = HashSet::new();
+	for attribution in &composition.attribution {
+		let (Some(composer), Some(catalog)) = (attribution.principal(), &attribution.catalog) else {
+			continue;
+		};
+		for entry in catalog {
+			if schemes_seen.insert((composer.to_string(), entry.scheme.clone())) {
+				identifiers.push((composer.to_string(), entry.scheme.clone(), entry.number.clone()));
+			}
+		}
+	}
+
+	if let Some(composer) = current_composer(&composition.attribution) {
+		for visit in walk_movements(composition) {
+			let mut schemes_seen = HashSet::new();
+			for entry in visit.node.catalog() {
+				if schemes_seen.insert(entry.scheme.as_str()) {
+					identifiers.push((composer.to_string(), entry.scheme.clone(), entry.number.clone()));
+				}
+			}
+		}
+	}
+
+	identifiers
+}
+
 /// Case-sensitive, so these stay outside the shared case-insensitive regex cache.
 fn id_pattern() -> &'static Regex {
 	static PATTERN: OnceLock<Regex> = OnceLock::new();
 	PATTERN.get_or_init(|| Regex::new(r"^[a-f0-9]{8}$").expect("valid composition id pattern"))
 }
 
-fn key_pattern() -> &'static Regex {
-	static PATTERN: OnceLock<Regex> = OnceLock::new();
-	PATTERN.get_or_init(|| {
-		Regex::new(r"^[A-Ga-g][#b]?(\.(dor|phr|lyd|mix|loc))?$").expect("valid key pattern")
-	})
+/// Work and movement keys must parse as key codes (`D`, `f#`, `e.phr`).
+fn validate_keys(comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+	let keys = comp.key.as_deref().map(|key| ("key".to_string(), key)).into_iter().chain(
+		walk_movements(comp).into_iter().filter_map(|visit| {
+			let key = visit.node.key()?;
+			Some((format!("{}.key", visit.location), key))
+		}),
+	);
+	keys.filter(|(_, key)| key.parse::<Key>().is_err())
+		.map(|(location, key)| ValidationError::new(
+			path_str.to_string(),
+			ErrorCode::InvalidKey,
+			format!("{}: invalid key '{}'", location, key),
+		).at(&location))
+		.collect()
 }
 
 fn deserialize_model<T: DeserializeOwned>(
@@ -1139,10 +1982,11 @@ fn deserialize_model<T: DeserializeOwned>(
 	match serde_json::from_value(value.clone()) {
 		Ok(model) => Some(model),
 		Err(error) => {
-			errors.push(ValidationError {
-				path: path_str.to_string(),
-				message: format!("Schema/model mismatch: {}", error),
-			});
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::ModelMismatch,
+				format!("Schema/model mismatch: {}", error),
+			));
 			None
 		}
 	}
@@ -1159,11 +2003,204 @@ fn load_schema_validator(path: &Path) -> Result<JsonSchemaValidator, String> {
 		.map_err(|error| format!("Failed to compile JSON Schema: {}", error))
 }
 
+/// Source lists on attribution entries and their catalog entries, by JSON location.
+fn attribution_sources(entries: &[AttributionEntry]) -> Vec<(String, &[SourceRef])> {
+	let mut cited = Vec::new();
+	for (i, entry) in entries.iter().enumerate() {
+		cited.push((format!("attribution[{}]", i), entry.sources.as_deref().unwrap_or_default()));
+		for (j, catalog) in entry.catalog.iter().flatten().enumerate() {
+			cited.push((
+				format!("attribution[{}].catalog[{}]", i, j),
+				catalog.sources.as_deref().unwrap_or_default(),
+			));
+		}
+	}
+	cited
+}
+
+fn validate_incipits(comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+	let incipits = comp
+		.incipit
+		.iter()
+		.map(|incipit| ("incipit".to_string(), incipit))
+		.chain(walk_movements(comp).into_iter().filter_map(|visit| {
+			let incipit = visit.node.incipit()?;
+			Some((format!("{}.incipit", visit.location), incipit))
+		}));
+	incipits
+		.filter_map(|(location, incipit)| {
+			let error = parse_incipit(incipit).err()?;
+			Some(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::InvalidIncipit,
+				format!("{}: {}", location, error),
+			).at(&location))
+		})
+		.collect()
+}
+
+fn validate_occasion(comp: &Composition, path_str: &str) -> Option<ValidationError> {
+	let occasion = comp.occasion.as_deref()?;
+	if occasion_label(occasion).is_some() {
+		return None;
+	}
+	Some(ValidationError::new(
+		path_str.to_string(),
+		ErrorCode::UnknownOccasion,
+		format!("occasion: unknown occasion '{}'", occasion),
+	).at("occasion"))
+}
+
+/// Entries go newest first, and a spurious entry needs a newer one saying who
+/// the work is now attributed to.
+fn validate_attribution_history(attribution: &[AttributionEntry], path_str: &str) -> Vec<ValidationError> {
+	let mut errors = Vec::new();
+
+	let dated: Vec<(usize, PartialDate)> = attribution
+		.iter()
+		.enumerate()
+		.filter_map(|(i, entry)| Some((i, entry.since.as_deref()?.parse().ok()?)))
+		.collect();
+	for pair in dated.windows(2) {
+		let ((newer, newer_since), (older, older_since)) = (pair[0], pair[1]);
+		if is_before(newer_since, older_since) {
+			let location = format!("attribution[{}].since", older);
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::AttributionOrder,
+				format!(
+					"{}: {} is later than attribution[{}].since ({}); entries go newest first",
+					location, older_since, newer, newer_since
+				),
+			).at(&location).warning());
+		}
+	}
+
+	for (i, entry) in attribution.iter().enumerate() {
+		if !matches!(entry.status, Some(Status::Spurious)) {
+			continue;
+		}
+		let superseded = attribution[..i]
+			.iter()
+			.any(|newer| !matches!(newer.status, Some(Status::Spurious)) && newer.principal().is_some());
+		if !superseded {
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::SpuriousAttribution,
+				format!("attribution[{}]: spurious, but no newer entry says who the work is now attributed to", i),
+			).at(&format!("attribution[{}].status", i)).warning());
+		}
+	}
+
+	errors
+}
+
+/// Whether `a` is earlier than `b` at the precision both give, so that 1990
+/// and 1990-05 are not out of order.
+fn is_before(a: PartialDate, b: PartialDate) -> bool {
+	let months = a.month.zip(b.month);
+	let days = a.day.zip(b.day);
+	(a.year, months.map(|(a, _)| a), days.map(|(a, _)| a)) < (b.year, months.map(|(_, b)| b), days.map(|(_, b)| b))
+}
+
+/// Relation problems visible in the composition itself; targets are checked
+/// by `validate_composition_links`.
+fn validate_relations(comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+	let mut errors = Vec::new();
+	for (i, relation) in comp.relations.iter().flatten().enumerate() {
+		let location = format!("relations[{}]", i);
+		let mut report = |message: String| {
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::InvalidRelation,
+				format!("{}: {}", location, message),
+			).at(&location));
+		};
+
+		if let Some(movement) = &relation.movement {
+			if !movement_path_exists(comp, movement) {
+				report(format!("movement '{}' not found in this composition", movement));
+			}
+		}
+		if relation.target == comp.id {
+			report("target is the composition itself".into());
+		}
+	}
+	errors
+}
+
+fn validate_repeated_identifiers(comp: &Composition, path_str: &str) -> Vec<ValidationError> {
+	let mut errors = Vec::new();
+	let mut seen = HashSet::new();
+	for key in current_catalog_identifiers(comp) {
+		if !seen.insert(key.clone()) {
+			let (composer, scheme, number) = key;
+			errors.push(ValidationError::new(
+				path_str.to_string(),
+				ErrorCode::DuplicateIdentifier,
+				format!(
+					"current catalog identifier {}:{}:{} is assigned more than once in this composition",
+					composer, scheme, number
+				),
+			));
+		}
+	}
+	errors
+}
+
+/// Fingerprint of what every composition's checks read besides its own
+/// composers and catalogs: the schemas, the form and instrument vocabularies
+/// and which names exist. Rules change between releases, so the version is
+/// part of it.
+fn validation_context(
+	schemas_dir: &Path,
+	taxonomy_paths: [&Path; 2],
+	names: [&HashSet<String>; 6],
+	inventory_index_error: Option<&str>,
+) -> String {
+	let mut fingerprint = Fingerprint::default();
+	fingerprint.update(env!("CARGO_PKG_VERSION").as_bytes());
+	let mut schema_paths: Vec<PathBuf> = fs::read_dir(schemas_dir)
+		.map(|entries| entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()).collect())
+		.unwrap_or_default();
+	schema_paths.sort();
+	for path in schema_paths {
+		fingerprint
+			.update(path.file_name().unwrap_or_default().as_encoded_bytes())
+			.update(&fs::read(&path).unwrap_or_default());
+	}
+	for path in taxonomy_paths {
+		fingerprint.update(&fs::read(path).unwrap_or_default());
+	}
+	for set in names {
+		let mut sorted: Vec<&str> = set.iter().map(String::as_str).collect();
+		sorted.sort_unstable();
+		fingerprint.update(sorted.join("\n").as_bytes());
+	}
+	fingerprint.update(inventory_index_error.unwrap_or_default().as_bytes());
+	fingerprint.finish()
+}
+
+fn cache_key(data_dir: &Path, path: &Path) -> Option<String> {
+	Some(path.strip_prefix(data_dir).ok()?.to_string_lossy().into_owned())
+}
+
+fn movement_path_exists(comp: &Composition, path: &str) -> bool {
+	parse_movement_path(path).is_some_and(|path| movement_at(comp, &path).is_some())
+}
+
 fn data_kind(path: &Path) -> Option<DataKind> {
 	for ancestor in path.ancestors() {
 		match ancestor.file_name().and_then(|name| name.to_str()) {
 			Some("compositions") => return Some(DataKind::Composition),
 			Some("composers") => return Some(DataKind::Composer),
+			Some("people") => return Some(DataKind::Person),
+			Some("references") => return Some(DataKind::Reference),
+			Some("chorales") => return Some(DataKind::Chorale),
+			Some("taxonomy") if path.file_name().is_some_and(|name| name == "instruments.toml") => {
+				return Some(DataKind::Instruments)
+			}
+			Some("taxonomy") => return Some(DataKind::Taxonomy),
 			Some("catalogs") => return Some(DataKind::Catalog),
 			Some("collections") => return Some(DataKind::Collection),
 			Some("inventories") => return Some(DataKind::Inventory),
@@ -1181,10 +2218,11 @@ fn collect_json_files(
 	let entries = match fs::read_dir(dir) {
 		Ok(entries) => entries,
 		Err(error) => {
-			errors.push(ValidationError {
-				path: dir.display().to_string(),
-				message: format!("failed to read directory: {}", error),
-			});
+			errors.push(ValidationError::new(
+				dir.display().to_string(),
+				ErrorCode::Io,
+				format!("failed to read directory: {}", error),
+			));
 			return;
 		}
 	};
@@ -1193,10 +2231,11 @@ fn collect_json_files(
 		let entry = match entry {
 			Ok(entry) => entry,
 			Err(error) => {
-				errors.push(ValidationError {
-					path: dir.display().to_string(),
-					message: format!("failed to read directory entry: {}", error),
-				});
+				errors.push(ValidationError::new(
+					dir.display().to_string(),
+					ErrorCode::Io,
+					format!("failed to read directory entry: {}", error),
+				));
 				continue;
 			}
 		};
@@ -1217,10 +2256,11 @@ fn collect_inventory_files(
 	let entries = match fs::read_dir(dir) {
 		Ok(entries) => entries,
 		Err(error) => {
-			errors.push(ValidationError {
-				path: dir.display().to_string(),
-				message: format!("failed to read directory: {}", error),
-			});
+			errors.push(ValidationError::new(
+				dir.display().to_string(),
+				ErrorCode::Io,
+				format!("failed to read directory: {}", error),
+			));
 			return;
 		}
 	};
@@ -1229,10 +2269,11 @@ fn collect_inventory_files(
 		let entry = match entry {
 			Ok(entry) => entry,
 			Err(error) => {
-				errors.push(ValidationError {
-					path: dir.display().to_string(),
-					message: format!("failed to read directory entry: {}", error),
-				});
+				errors.push(ValidationError::new(
+					dir.display().to_string(),
+					ErrorCode::Io,
+					format!("failed to read directory entry: {}", error),
+				));
 				continue;
 			}
 		};
@@ -1248,6 +2289,22 @@ fn collect_inventory_files(
 	}
 }
 
+fn catalog_example_errors(
+	scheme: &str,
+	definition: &CatalogDefinition,
+	path_str: &str,
+	location: &str,
+) -> Vec<ValidationError> {
+	check_examples(scheme, definition)
+		.into_iter()
+		.map(|problem| ValidationError::new(
+			path_str.to_string(),
+			ErrorCode::CatalogExample,
+			format!("{}: {}", location, problem),
+		))
+		.collect()
+}
+
 fn is_valid_catalog_number_case(number: &str, definition: &CatalogDefinition) -> bool {
 	if let Some(suffixes) = definition.allowed_uppercase_suffixes.as_ref() {
 		for suffix in suffixes {
@@ -1314,20 +2371,40 @@ mod tests {
 		}
 	}
 
+	fn summary(composition: &Composition) -> CompositionSummary {
+		CompositionSummary::of(composition, current_catalog_identifiers(composition))
+	}
+
 	fn test_validator() -> Validator {
 		Validator {
 			data_dir: PathBuf::new(),
 			composers: HashSet::new(),
+			people: HashSet::new(),
+			references: HashSet::new(),
+			chorales: HashSet::new(),
+			forms: None,
+			instruments: None,
 			catalog_schemes: HashSet::new(),
 			global_catalog_schemes: HashSet::new(),
 			composer_catalog_schemes: HashMap::new(),
+			composer_lifespans: HashMap::new(),
 			current_catalog_targets: HashMap::new(),
+			composition_ids: HashMap::new(),
 			composition_cache: HashMap::new(),
+			composition_summaries: HashMap::new(),
+			composition_hashes: HashMap::new(),
+			cache: ValidationCache::default(),
+			context: String::new(),
+			dependency_hashes: HashMap::new(),
 			validation_paths: Vec::new(),
 			discovery_errors: Vec::new(),
 			inventory_index_error: None,
 			composition_schema: empty_schema(),
 			composer_schema: empty_schema(),
+			person_schema: empty_schema(),
+			reference_schema: empty_schema(),
+			chorale_schema: empty_schema(),
+			form_taxonomy_schema: empty_schema(),
 			catalog_schema: empty_schema(),
 			collection_schema: empty_schema(),
 			inventory_index: InventoryIndex::default(),
@@ -1349,6 +2426,25 @@ mod tests {
 		assert!(errors.iter().any(|error| error.message.contains("invalid catalog pattern")));
 	}
 
+	#[test]
+	fn catalog_groups_must_exist_in_some_pattern() {
+		let definition: CatalogDefinition = serde_json::from_str(r#"{
+			"name": "BWV",
+			"patterns": ["^(?<number>\\d+)$", "^(?<appendix>anh\\.)\\s*(?<number>\\d+)$"],
+			"sort_keys": [{"group": "appendix", "type": "str"}, {"group": "number", "type": "int"}],
+			"group_by": ["volume"],
+			"constraints": [{"group": 3, "max": 1200}]
+		}"#).unwrap();
+		let errors = test_validator().validate_catalog_definition_domain(&definition, "composers/bach.json", "catalog 'bwv'");
+		assert_eq!(
+			errors.iter().map(|error| error.message.as_str()).collect::<Vec<_>>(),
+			vec![
+				"catalog 'bwv': group_by group 'volume' is not named in any pattern",
+				"catalog 'bwv': constraint group 3 exceeds the capture count of every pattern",
+			]
+		);
+	}
+
 	#[test]
 	fn catalog_number_case_exceptions_are_metadata_driven() {
 		let bwv = CatalogDefinition {
@@ -1383,7 +2479,7 @@ mod tests {
 		validator.composers.insert("bach".into());
 		validator
 			.composition_cache
-			.insert(path.clone(), CachedComposition::load(&path));
+			.insert(path.clone(), CachedComposition::parse(&fs::read_to_string(&path).unwrap()));
 
 		fs::write(&path, "{").unwrap();
 		let errors = validator.validate_composition_file(&path);
@@ -1414,6 +2510,11 @@ mod tests {
 	fn test_data_kind() {
 		assert_eq!(data_kind(Path::new("compositions/ab/cd1234.json")), Some(DataKind::Composition));
 		assert_eq!(data_kind(Path::new("composers/bach.json")), Some(DataKind::Composer));
+		assert_eq!(data_kind(Path::new("people/picander.json")), Some(DataKind::Person));
+		assert_eq!(data_kind(Path::new("references/kinsky-1955.json")), Some(DataKind::Reference));
+		assert_eq!(data_kind(Path::new("chorales/jesu-meine-freude.json")), Some(DataKind::Chorale));
+		assert_eq!(data_kind(Path::new("taxonomy/forms.json")), Some(DataKind::Taxonomy));
+		assert_eq!(data_kind(Path::new("taxonomy/instruments.toml")), Some(DataKind::Instruments));
 		assert_eq!(data_kind(Path::new("catalogs/op.json")), Some(DataKind::Catalog));
 		assert_eq!(data_kind(Path::new("collections/bach/wtc-1.json")), Some(DataKind::Collection));
 		assert_eq!(data_kind(Path::new("inventories/beethoven/op.toml")), Some(DataKind::Inventory));
@@ -1471,10 +2572,351 @@ mod tests {
 			}]
 		}"#).unwrap();
 
-		let errors = validator.validate_current_catalog_uniqueness(&composition, "test");
+		let errors = validator.validate_composition_links(&summary(&composition), "test");
 		assert_eq!(errors.len(), 1);
 	}
 
+	#[test]
+	fn test_movement_catalog_collision() {
+		let mut validator = test_validator();
+		validator.current_catalog_targets.insert(
+			("beethoven".into(), "op".into(), "33/1".into()),
+			vec!["11111111".into(), "22222222".into()],
+		);
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "bagatelles",
+			"attribution": [{
+				"composer": "beethoven",
+				"catalog": [{"scheme": "op", "number": "33"}]
+			}],
+			"movements": [
+				{"catalog": [{"scheme": "op", "number": "33/1"}]},
+				{"catalog": [{"scheme": "op", "number": "33"}]}
+			]
+		}"#).unwrap();
+
+		let mut errors = validate_repeated_identifiers(&composition, "test");
+		errors.extend(validator.validate_composition_links(&summary(&composition), "test"));
+		assert_eq!(errors.len(), 2);
+		assert!(errors.iter().any(|e| e.message.contains("op:33 is assigned more than once")));
+		assert!(errors.iter().any(|e| e.message.contains("op:33/1 is shared by compositions 11111111, 22222222")));
+	}
+
+	#[test]
+	fn test_dates_checked_against_range_order_and_lifespan() {
+		let mut validator = test_validator();
+		validator.composer_lifespans.insert(
+			"bach".into(),
+			Lifespan::from_composer(&serde_json::json!({"born": "1685-03-31", "died": "1750-07-28"})),
+		);
+		let dates: Dates = serde_json::from_str(r#"{
+			"composed": "1724..1723",
+			"published": 1752,
+			"premiered": "c. 1685",
+			"revised": "after 1751"
+		}"#).unwrap();
+
+		let errors = validator.validate_dates(&dates, Some("bach"), "test", "attribution[0].dates");
+		let messages = |severity| -> Vec<&str> {
+			errors.iter().filter(|e| e.severity == severity).map(|e| e.message.as_str()).collect()
+		};
+		assert_eq!(messages(Level::Error), [
+			"attribution[0].dates.composed: range '1724–1723' ends before it starts",
+		]);
+		assert_eq!(messages(Level::Warning), [
+			"attribution[0].dates.published: 1752 is after the composer's death (1750-07-28)",
+			"attribution[0].dates.revised: after 1751 is after the composer's death (1750-07-28)",
+		]);
+		assert_eq!(errors[0].code, ErrorCode::InvalidDate);
+		assert_eq!(errors[1].code, ErrorCode::PosthumousDate);
+		assert_eq!(errors[2].code, ErrorCode::OutsideLifespan);
+
+		let dates: Dates = serde_json::from_str(r#"{"composed": 1680}"#).unwrap();
+		let errors = validator.validate_dates(&dates, Some("bach"), "test", "dates");
+		assert_eq!(errors.len(), 1);
+		assert_eq!(errors[0].code, ErrorCode::OutsideLifespan);
+		assert_eq!(errors[0].severity, Level::Warning);
+		assert_eq!(errors[0].message, "dates.composed: 1680 is before the composer's birth (1685-03-31)");
+
+		let dates: Dates = serde_json::from_str(r#"{"composed": "1735..1740", "revised": 1731}"#).unwrap();
+		let errors = validator.validate_dates(&dates, Some("bach"), "test", "dates");
+		assert_eq!(errors.len(), 1);
+		assert_eq!(errors[0].code, ErrorCode::RevisionOrder);
+		assert_eq!(errors[0].severity, Level::Warning);
+		assert_eq!(errors[0].pointer, "/dates/revised");
+	}
+
+	#[test]
+	fn test_attribution_history() {
+		let mut validator = test_validator();
+		validator.composers.extend(["bach".to_string(), "telemann".to_string()]);
+		let attribution: Vec<AttributionEntry> = serde_json::from_str(r#"[
+			{"status": "spurious", "since": "1990-05"},
+			{"composer": "bach", "status": "spurious", "since": "1990"},
+			{"composer": "bach", "since": "1998"}
+		]"#).unwrap();
+
+		let errors = validator.validate_attribution(&attribution, "test", true);
+		let findings: Vec<(ErrorCode, &str)> = errors.iter().map(|e| (e.code, e.pointer.as_str())).collect();
+		assert_eq!(findings, [
+			(ErrorCode::AttributionOrder, "/attribution/2/since"),
+			(ErrorCode::SpuriousAttribution, "/attribution/0/status"),
+			(ErrorCode::SpuriousAttribution, "/attribution/1/status"),
+		]);
+		assert!(errors.iter().all(|e| e.severity == Level::Warning));
+
+		let attribution: Vec<AttributionEntry> = serde_json::from_str(r#"[
+			{"composer": "telemann", "since": "1998"},
+			{"composer": "bach", "status": "spurious"}
+		]"#).unwrap();
+		assert!(validator.validate_attribution(&attribution, "test", true).is_empty());
+	}
+
+	#[test]
+	fn test_person_references() {
+		let mut validator = test_validator();
+		validator.people.insert("picander".into());
+		validator.composers.insert("haydn".into());
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "cantata",
+			"attribution": [{"composer": "bach"}],
+			"people": [
+				{"person": "picander", "role": "librettist"},
+				{"person": "haydn", "role": "dedicatee"},
+				{"person": "salomo-franck", "role": "text-author"}
+			],
+			"text": {"author": "picander", "language": "de"},
+			"movements": [{"form": "aria", "text": {"author": "neumeister"}}]
+		}"#).unwrap();
+
+		let errors = validator.validate_people(&composition, "test");
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"people[2]: person 'salomo-franck' not found in people/ or composers/",
+			"movements[0].text.author: person 'neumeister' not found in people/ or composers/",
+		]);
+	}
+
+	#[test]
+	fn test_contributors_must_exist() {
+		let mut validator = test_validator();
+		validator.composers.insert("mozart".into());
+		validator.people.insert("sussmayr".into());
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "requiem",
+			"attribution": [{
+				"composer": "mozart",
+				"contributors": [
+					{"composer": "sussmayr", "role": "completer"},
+					{"composer": "eybler", "role": "completer"}
+				]
+			}]
+		}"#).unwrap();
+
+		let errors = validator.validate_attribution(&composition.attribution, "test", true);
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, ["attribution[0].contributors[1]: 'eybler' not found in composers/ or people/"]);
+	}
+
+	#[test]
+	fn test_source_references() {
+		let mut validator = test_validator();
+		validator.references.insert("schmieder-1990".into());
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "cantata",
+			"attribution": [
+				{"composer": "telemann", "sources": [{"reference": "ruhnke-1984", "locator": "p. 12"}]},
+				{
+					"composer": "bach",
+					"status": "spurious",
+					"catalog": [{"scheme": "bwv", "number": "anh. iii 141", "sources": [{"reference": "schmieder-1990"}]}]
+				}
+			],
+			"movements": [
+				{"form": "chorus", "catalog": [{"scheme": "bwv", "number": "141/1", "sources": [{"reference": "bwv3"}]}]}
+			]
+		}"#).unwrap();
+
+		let errors = validator.validate_sources(&composition, "test");
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"attribution[0].sources[0]: reference 'ruhnke-1984' not found in references/",
+			"movements[0].catalog[0].sources[0]: reference 'bwv3' not found in references/",
+		]);
+	}
+
+	#[test]
+	fn test_unknown_forms() {
+		let mut validator = test_validator();
+		validator.forms = Some(serde_json::from_str(r#"{
+			"cantata": {},
+			"sacred cantata": {"parent": "cantata", "synonyms": ["church cantata"]},
+			"recitative": {},
+			"chorale": {}
+		}"#).unwrap());
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "church cantata",
+			"attribution": [{"composer": "bach"}],
+			"movements": [
+				{"form": "recitative and chorale"},
+				{"form": "recitatve"},
+				{"form": "chorale e minuet"}
+			]
+		}"#).unwrap();
+
+		let errors = validator.validate_forms(&composition, "test");
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"movements[1].form: unknown form 'recitatve' (did you mean 'recitative'?)",
+			"movements[2].form: unknown form 'minuet'",
+		]);
+	}
+
+	#[test]
+	fn test_invalid_keys() {
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "sonata",
+			"key": "C#",
+			"attribution": [{"composer": "beethoven"}],
+			"movements": [
+				{"form": "allegro", "key": "Db"},
+				{"form": "adagio", "key": "H"},
+				{"form": "minuet", "key": "a.ion"}
+			]
+		}"#).unwrap();
+
+		let errors = validate_keys(&composition, "test");
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"movements[1].key: invalid key 'H'",
+			"movements[2].key: invalid key 'a.ion'",
+		]);
+	}
+
+	#[test]
+	fn test_chorale_references() {
+		let mut validator = test_validator();
+		validator.chorales.insert("jesu-meine-freude".into());
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "chorale cantata",
+			"chorale": "was-gott-tut",
+			"attribution": [{"composer": "bach"}],
+			"movements": [
+				{"form": "chorus"},
+				{"form": "chorale", "chorale": "jesu-meine-freude"},
+				{"form": "chorale", "chorale": "jesu-meine-freunde"}
+			]
+		}"#).unwrap();
+
+		let errors = validator.validate_chorales(&composition, "test");
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"chorale: chorale 'was-gott-tut' not found in chorales/",
+			"movements[2].chorale: chorale 'jesu-meine-freunde' not found in chorales/",
+		]);
+	}
+
+	#[test]
+	fn test_incipit_syntax() {
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "suite",
+			"attribution": [{"composer": "bach"}],
+			"incipit": {"clef": "G-2", "key": "xF", "time": "3/4", "data": "'4GAB/2C"},
+			"movements": [
+				{"form": "allemande", "incipit": {"clef": "G-7", "data": "4C"}},
+				{"form": "courante", "incipit": {"clef": "F-4", "data": "8CD(E"}}
+			]
+		}"#).unwrap();
+
+		let errors = validate_incipits(&composition, "test");
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"movements[0].incipit: invalid clef 'G-7' (expected e.g. G-2, F-4, C-3)",
+			"movements[1].incipit: data, position 6: unclosed '('",
+		]);
+	}
+
+	#[test]
+	fn test_occasion_vocabulary() {
+		let mut composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "cantata",
+			"occasion": "trinity+4",
+			"attribution": [{"composer": "bach"}]
+		}"#).unwrap();
+		assert!(validate_occasion(&composition, "test").is_none());
+
+		composition.occasion = Some("4th Sunday after Trinity".into());
+		let error = validate_occasion(&composition, "test").unwrap();
+		assert_eq!(error.message, "occasion: unknown occasion '4th Sunday after Trinity'");
+	}
+
+	#[test]
+	fn test_relation_targets_and_movements() {
+		let mut validator = test_validator();
+		let target: Composition = serde_json::from_str(r#"{
+			"id": "22222222",
+			"form": "cantata",
+			"attribution": [{"composer": "bach"}],
+			"movements": [{"form": "chorus"}, {"form": "aria"}]
+		}"#).unwrap();
+		let target_path = PathBuf::from("compositions/22/222222.json");
+		validator.composition_ids.insert("22222222".into(), target_path.clone());
+		validator.composition_summaries.insert(target_path, summary(&target));
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "11111111",
+			"form": "mass",
+			"attribution": [{"composer": "bach"}],
+			"movements": [{"form": "kyrie"}],
+			"relations": [
+				{"type": "parody-of", "target": "22222222", "movement": "1", "target_movement": "2"},
+				{"type": "parody-of", "target": "22222222", "movement": "2", "target_movement": "3"},
+				{"type": "version-of", "target": "33333333"},
+				{"type": "part-of", "target": "11111111"}
+			]
+		}"#).unwrap();
+
+		let mut errors = validate_relations(&composition, "test");
+		errors.extend(validator.validate_composition_links(&summary(&composition), "test"));
+		let messages: Vec<&str> = errors.iter().map(|e| e.message.as_str()).collect();
+		assert_eq!(messages, [
+			"relations[1]: movement '2' not found in this composition",
+			"relations[3]: target is the composition itself",
+			"relations[1]: movement '3' not found in target composition '22222222'",
+			"relations[2]: target composition '33333333' not found",
+		]);
+	}
+
+	#[test]
+	fn test_unknown_instrument() {
+		let mut validator = test_validator();
+		let instrumentation: Instrumentation = serde_json::from_str(r#"[{"instrument": "lute"}]"#).unwrap();
+		assert!(validator.validate_instrumentation(&Some(instrumentation), "test").is_empty());
+
+		validator.instruments = Some(toml::from_str(r#"
+			violin = {label = "violin", family = "strings"}
+			organ = {label = "organ", family = "keyboard"}
+			harpsichord = {label = "harpsichord", family = "keyboard"}
+		"#).unwrap());
+		let instrumentation = serde_json::from_str(r#"[
+			{"instrument": "violin", "count": 2},
+			{"instrument": "organ", "alternatives": ["harpsicord"]}
+		]"#).unwrap();
+
+		let errors = validator.validate_instrumentation(&Some(instrumentation), "test");
+		assert_eq!(errors.len(), 1);
+		assert_eq!(errors[0].message, "instrumentation[1]: unknown instrument 'harpsicord'");
+		assert!(validator.validate_instrumentation(&Some(Instrumentation::Text("lute".into())), "test").is_empty());
+	}
+
 	#[test]
 	fn test_catalog_pattern_edition_and_collection_resolution() {
 		let tmp = tempfile::tempdir().unwrap();
@@ -1523,4 +2965,57 @@ mod tests {
 		}"#).unwrap();
 		assert!(validator.validate_collection_file(&path).is_empty());
 	}
+
+	#[test]
+	fn composer_catalog_examples_are_checked_where_declared() {
+		let tmp = tempfile::tempdir().unwrap();
+		fs::create_dir_all(tmp.path().join("composers")).unwrap();
+		fs::create_dir_all(tmp.path().join("catalogs")).unwrap();
+		fs::write(tmp.path().join("catalogs/op.json"), r#"{
+			"id": "op",
+			"name": "Opus number",
+			"canonical_format": "op. {number}",
+			"pattern": "^\\d+$",
+			"examples": [{"number": "2", "display": "op. 2"}]
+		}"#).unwrap();
+		let path = tmp.path().join("composers/chopin.json");
+		fs::write(&path, r#"{
+			"id": "chopin",
+			"name": {"full": "Frédéric Chopin", "sort": "Chopin, Frédéric"},
+			"catalogs": {
+				"op": {"name": "Opus (Chopin)", "canonical_format": "Op. {number}"},
+				"kk": {
+					"name": "Kobylańska",
+					"pattern": "^\\d+$",
+					"examples": [{"number": "1201", "display": "KK 1201"}, {"number": "ia/1", "display": "KK Ia/1"}]
+				}
+			}
+		}"#).unwrap();
+
+		let mut validator = test_validator();
+		validator.data_dir = tmp.path().to_path_buf();
+		let errors = validator.validate_composer_file(&path);
+		assert_eq!(
+			errors.iter().map(|error| (error.code, error.message.as_str())).collect::<Vec<_>>(),
+			vec![(ErrorCode::CatalogExample, "catalog 'kk': example 'ia/1' does not match the pattern")]
+		);
+	}
+
+	#[test]
+	fn locations_become_json_pointers() {
+		assert_eq!(json_pointer("attribution[0].catalog[1]"), "/attribution/0/catalog/1");
+		assert_eq!(json_pointer("movements[2].key"), "/movements/2/key");
+		assert_eq!(json_pointer("a/b.c~d"), "/a~1b/c~0d");
+		assert_eq!(json_pointer(""), "");
+
+		let error = ValidationError::new("x.json", ErrorCode::UnknownComposer, "attribution[0]: unknown composer")
+			.at("attribution[0].composer");
+		assert_eq!(error.pointer, "/attribution/0/composer");
+		assert_eq!(error.severity, Level::Error);
+		assert_eq!(error.to_string(), "x.json: attribution[0]: unknown composer");
+		assert_eq!(serde_json::to_value(error.code).unwrap(), "unknown-composer");
+		assert_eq!(ErrorCode::CatalogPattern.to_string(), "catalog-pattern");
+		assert_eq!(ErrorCode::CatalogNumberCase.to_string(), "catalog-number-case");
+		assert_eq!(serde_json::to_value(Level::Warning).unwrap(), "warning");
+	}
 }
@@ -0,0 +1,193 @@
+//! Per-composition validation results kept in `.indexes/`, so that
+//! `wv validate` re-checks only what changed since the last run.
+//!
+//! An entry is reused when the file's content hash is unchanged, the shared
+//! context (schemas, taxonomy, the set of known names) is unchanged, and the
+//! composers and catalogs the composition refers to are unchanged. Checks
+//! that look at other compositions are not cached; they are recomputed from
+//! the summaries on every run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::index::write_atomic;
+use crate::movement::walk_movements;
+use crate::types::Composition;
+use crate::validate::ValidationError;
+
+/// Bump when a check changes, so results from the old rules are not reused.
+const CACHE_FORMAT_VERSION: u32 = 2;
+
+pub(crate) fn cache_path(data_dir: &Path) -> PathBuf {
+	data_dir.join(".indexes").join("validation-cache.json")
+}
+
+/// What other files need to know about a composition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct CompositionSummary {
+	pub id: String,
+	/// Current `(composer, scheme, number)` identifiers.
+	pub identifiers: Vec<(String, String, String)>,
+	/// 1-based position of every movement and section.
+	pub movements: Vec<Vec<usize>>,
+	pub relations: Vec<RelationLink>,
+	/// `composer:<slug>` and `catalog:<scheme>` keys the checks depend on.
+	pub dependencies: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RelationLink {
+	pub index: usize,
+	pub target: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub target_movement: Option<String>,
+}
+
+impl CompositionSummary {
+	pub fn of(composition: &Composition, identifiers: Vec<(String, String, String)>) -> Self {
+		let mut dependencies = BTreeSet::new();
+		for entry in &composition.attribution {
+			if let Some(composer) = entry.principal() {
+				dependencies.insert(format!("composer:{}", composer));
+			}
+			for catalog in entry.catalog.iter().flatten() {
+				dependencies.insert(format!("catalog:{}", catalog.scheme));
+			}
+		}
+
+		let visits = walk_movements(composition);
+		for visit in &visits {
+			for catalog in visit.node.catalog() {
+				dependencies.insert(format!("catalog:{}", catalog.scheme));
+			}
+		}
+
+		Self {
+			id: composition.id.clone(),
+			identifiers,
+			movements: visits.into_iter().map(|visit| visit.path).collect(),
+			relations: composition
+				.relations
+				.iter()
+				.flatten()
+				.enumerate()
+				.map(|(index, relation)| RelationLink {
+					index,
+					target: relation.target.clone(),
+					target_movement: relation.target_movement.clone(),
+				})
+				.collect(),
+			dependencies,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CacheEntry {
+	pub hash: String,
+	pub summary: CompositionSummary,
+	/// Fingerprint of each dependency when the entry was written.
+	pub dependencies: BTreeMap<String, String>,
+	/// Findings that depend on this file alone.
+	pub errors: Vec<ValidationError>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct ValidationCache {
+	pub format_version: u32,
+	pub context: String,
+	/// Keyed by path relative to the data directory.
+	pub files: BTreeMap<String, CacheEntry>,
+}
+
+impl ValidationCache {
+	/// A missing, unreadable or outdated cache is an empty one.
+	pub fn load(data_dir: &Path) -> Self {
+		fs::read_to_string(cache_path(data_dir))
+			.ok()
+			.and_then(|content| serde_json::from_str::<Self>(&content).ok())
+			.filter(|cache| cache.format_version == CACHE_FORMAT_VERSION)
+			.unwrap_or_default()
+	}
+
+	pub fn new(context: String) -> Self {
+		Self {
+			format_version: CACHE_FORMAT_VERSION,
+			context,
+			files: BTreeMap::new(),
+		}
+	}
+
+	pub fn save(&self, data_dir: &Path) -> std::io::Result<()> {
+		let json = serde_json::to_string(self)?;
+		write_atomic(&cache_path(data_dir), &(json + "\n"))
+	}
+}
+
+/// FNV-1a. The standard library's hasher is not stable across releases,
+/// which would throw the cache away on every toolchain update.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Fingerprint(u64);
+
+impl Default for Fingerprint {
+	fn default() -> Self {
+		Self(0xcbf2_9ce4_8422_2325)
+	}
+}
+
+impl Fingerprint {
+	pub fn update(&mut self, bytes: &[u8]) -> &mut Self {
+		for byte in bytes {
+			self.0 ^= u64::from(*byte);
+			self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+		}
+		// Separate fields, so "ab" + "c" differs from "a" + "bc".
+		self.0 ^= 0xff;
+		self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+		self
+	}
+
+	pub fn finish(&self) -> String {
+		format!("{:016x}", self.0)
+	}
+}
+
+pub(crate) fn content_hash(content: &str) -> String {
+	Fingerprint::default().update(content.as_bytes()).finish()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fingerprint_separates_fields() {
+		let joined = Fingerprint::default().update(b"ab").update(b"c").finish();
+		let split = Fingerprint::default().update(b"a").update(b"bc").finish();
+		assert_ne!(joined, split);
+		assert_eq!(content_hash("abc"), content_hash("abc"));
+		assert_ne!(content_hash("abc"), content_hash("abd"));
+	}
+
+	#[test]
+	fn summary_lists_dependencies_and_movements() {
+		let composition: Composition = serde_json::from_str(r#"{
+			"id": "abcd1234",
+			"form": "cantata",
+			"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "1"}]}],
+			"movements": [{"form": "chorus"}, {"form": "aria"}],
+			"relations": [{"type": "parody-of", "target": "ef567890", "target_movement": "2"}]
+		}"#).unwrap();
+
+		let summary = CompositionSummary::of(&composition, Vec::new());
+		assert_eq!(
+			summary.dependencies.into_iter().collect::<Vec<_>>(),
+			vec!["catalog:bwv", "composer:bach"]
+		);
+		assert_eq!(summary.movements, vec![vec![1], vec![2]]);
+		assert_eq!(summary.relations[0].target_movement.as_deref(), Some("2"));
+	}
+}
@@ -55,6 +55,14 @@ fn run_wv(root: &std::path::Path, args: &[&str]) -> Output {
 	command.output().unwrap()
 }
 
+fn copy_schemas(root: &std::path::Path) {
+	let schemas = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../schemas");
+	for entry in fs::read_dir(schemas).unwrap() {
+		let path = entry.unwrap().path();
+		fs::copy(&path, root.join("schemas").join(path.file_name().unwrap())).unwrap();
+	}
+}
+
 fn setup_inventory_cli_repo() -> TempDir {
 	let tmp = setup_test_repo();
 	let root = tmp.path();
@@ -1088,3 +1096,1031 @@ entries = ["2/1", "2/2", "2/3", "9/1", "9/2", "9/3", "138"]
 		"2 catalog entries known; detailed records not yet available"
 	);
 }
+
+#[test]
+fn test_cli_get_resolves_movement_catalog_number() {
+	let tmp = setup_inventory_cli_repo();
+	let root = tmp.path();
+	fs::write(
+		root.join("inventories/beethoven/op.toml"),
+		r#"composer = "beethoven"
+scheme = "op"
+complete = false
+entries = []
+"#,
+	)
+	.unwrap();
+	write_composition(root, "cd123456", r#"{
+		"id": "cd123456",
+		"title": {"en": "Seven Bagatelles"},
+		"form": "bagatelles",
+		"attribution": [{
+			"composer": "beethoven",
+			"catalog": [{"scheme": "op", "number": "33"}]
+		}],
+		"movements": [
+			{"form": "bagatelle", "key": "Eb", "catalog": [{"scheme": "op", "number": "33/1"}]},
+			{"title": "Scherzo", "key": "C", "catalog": [{"scheme": "op", "number": "33/2"}]}
+		]
+	}"#);
+
+	let output = run_wv(root, &["get", "beethoven", "op", "33/2"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).trim(),
+		"Seven Bagatelles: 2. Scherzo, op. 33 no. 2"
+	);
+
+	let output = run_wv(root, &["get", "beethoven", "op", "33/2", "--terse"]);
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "cd123456");
+}
+
+#[test]
+fn test_cli_migrate_instrumentation_writes_only_clean_parses() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab123456", "{\n\t\"id\": \"ab123456\",\n\t\"form\": \"sonata\",\n\t\"instrumentation\": \"violin and harpsichord obbligato\",\n\t\"attribution\": [{\"composer\": \"bach\"}]\n}\n");
+	write_composition(root, "cd123456", "{\n\t\"id\": \"cd123456\",\n\t\"form\": \"sonata\",\n\t\"instrumentation\": \"violin, theremin\",\n\t\"attribution\": [{\"composer\": \"bach\"}]\n}\n");
+
+	let output = run_wv(root, &["migrate-instrumentation"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert!(String::from_utf8_lossy(&output.stderr).contains("No instrument vocabulary at"));
+
+	fs::create_dir_all(root.join("taxonomy")).unwrap();
+	let vocabulary = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../taxonomy/instruments.toml");
+	fs::copy(vocabulary, root.join("taxonomy/instruments.toml")).unwrap();
+	let output = run_wv(root, &["migrate-instrumentation", "--write"]);
+	assert!(output.status.success());
+	let stdout = String::from_utf8(output.stdout).unwrap();
+	assert!(stdout.contains("ab/123456.json\tviolin, harpsichord obbligato"));
+	let stderr = String::from_utf8(output.stderr).unwrap();
+	assert!(stderr.contains("unrecognized: theremin"));
+	assert!(stderr.contains("migrated: 1, skipped: 1"));
+
+	let migrated = fs::read_to_string(root.join("compositions/ab/123456.json")).unwrap();
+	let value: serde_json::Value = serde_json::from_str(&migrated).unwrap();
+	assert_eq!(value["instrumentation"][1]["role"], "obbligato");
+	let untouched = fs::read_to_string(root.join("compositions/cd/123456.json")).unwrap();
+	assert!(untouched.contains("\"violin, theremin\""));
+}
+
+#[test]
+fn test_cli_get_filters_and_orders_by_composition_date() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	for (id, number, composed) in [
+		("ab000001", "1", r#""c. 1730""#),
+		("ab000002", "2", "1723"),
+		("ab000003", "3", r#""1724..1725""#),
+		("ab000004", "4", "null"),
+	] {
+		write_composition(root, id, &format!(r#"{{
+			"id": "{}",
+			"form": "cantata",
+			"attribution": [{{
+				"composer": "bach",
+				"dates": {{"composed": {}}},
+				"catalog": [{{"scheme": "bwv", "number": "{}"}}]
+			}}]
+		}}"#, id, composed, number));
+	}
+
+	let output = run_wv(root, &["get", "bach", "bwv", "--chronological", "-t"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		["ab000002", "ab000003", "ab000001", "ab000004"]
+	);
+
+	let output = run_wv(root, &["get", "bach", "bwv", "--composed", "1725..1729", "-t"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab000003");
+}
+
+#[test]
+fn test_cli_get_by_nickname() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab123456", r#"{
+		"id": "ab123456",
+		"form": "sonata",
+		"key": "c#",
+		"nicknames": [{"name": {"en": "Moonlight", "de": "Mondscheinsonate"}, "provenance": "Ludwig Rellstab"}],
+		"attribution": [{"composer": "beethoven", "catalog": [{"scheme": "op", "number": "27/2"}]}]
+	}"#);
+	write_composition(root, "cd123456", r#"{
+		"id": "cd123456",
+		"form": "symphony",
+		"key": "C",
+		"nicknames": [{"name": {"en": "Jupiter"}, "provenance": "Johann Peter Salomon"}],
+		"attribution": [{"composer": "mozart", "catalog": [{"scheme": "k", "number": "551"}]}]
+	}"#);
+
+	let output = run_wv(root, &["get", "--nickname", "moonlight", "-t"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab123456");
+
+	let output = run_wv(root, &["get", "--nickname", "Mondscheinsonate", "-t"]);
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab123456");
+
+	let output = run_wv(root, &["get", "beethoven", "--nickname", "jupiter", "-t"]);
+	assert!(output.stdout.is_empty());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("No results found."));
+}
+
+#[test]
+fn test_cli_get_related_shows_both_directions() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "mass",
+		"key": "b",
+		"movements": [{"title": "Kyrie"}, {"title": "Gloria"}],
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "232"}]}],
+		"relations": [{"type": "parody-of", "target": "cd000001", "movement": "2", "target_movement": "1"}]
+	}"#);
+	write_composition(root, "cd000001", r#"{
+		"id": "cd000001",
+		"form": "cantata",
+		"movements": [{"title": "Chorus"}],
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "191"}]}]
+	}"#);
+
+	let output = run_wv(root, &["get", "ab000001", "--related"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let lines: Vec<&str> = stdout.lines().collect();
+	assert_eq!(lines.len(), 2, "{}", stdout);
+	assert!(lines[1].starts_with("  parody of: "), "{}", stdout);
+	assert!(lines[1].contains(": 1. Chorus, BWV 191 (this work: 2. Gloria)"), "{}", stdout);
+
+	let output = run_wv(root, &["get", "cd000001", "--related"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let lines: Vec<&str> = stdout.lines().collect();
+	assert_eq!(lines.len(), 2, "{}", stdout);
+	assert!(lines[1].starts_with("  parodied in: "), "{}", stdout);
+	assert!(lines[1].contains(": 2. Gloria, BWV 232 (this work: 1. Chorus)"), "{}", stdout);
+}
+
+#[test]
+fn test_cli_get_by_person() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	fs::create_dir_all(root.join("people")).unwrap();
+	fs::write(root.join("people/picander.json"), r#"{
+		"id": "picander",
+		"name": {"full": "Christian Friedrich Henrici", "sort": "Henrici, Christian Friedrich"}
+	}"#).unwrap();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "passion",
+		"people": [{"person": "picander", "role": "librettist"}],
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "244"}]}]
+	}"#);
+	write_composition(root, "ab000002", r#"{
+		"id": "ab000002",
+		"form": "cantata",
+		"people": [{"person": "franck", "role": "text-author"}],
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "152"}]}]
+	}"#);
+
+	let output = run_wv(root, &["get", "--person", "Picander", "-t"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab000001");
+
+	let output = run_wv(root, &["get", "mozart", "--person", "picander", "-t"]);
+	assert!(output.stdout.is_empty());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("No results found."));
+}
+
+#[test]
+fn test_cli_first_line_lookup_and_movement_text() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "cantata",
+		"text": {"language": "de"},
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "140"}]}],
+		"movements": [
+			{"form": "chorus", "text": {"first_line": "Wachet auf, ruft uns die Stimme", "source": {"type": "chorale", "reference": "Wachet auf, verse 1"}}},
+			{"title": "Er kommt, er kommt", "form": "recitative", "text": {}},
+			{"form": "sinfonia"}
+		]
+	}"#);
+
+	let output = run_wv(root, &["get", "--first-line", "wachet auf", "-t"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab000001");
+
+	let output = run_wv(root, &["get", "--first-line", "Er kommt"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert!(String::from_utf8_lossy(&output.stdout).contains(": 2. Er kommt, er kommt, BWV 140"));
+
+	let output = run_wv(root, &["get", "bach", "bwv", "140", "--movements", "--text"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		[
+			"1. chorus",
+			"   \"Wachet auf, ruft uns die Stimme\" · chorale: Wachet auf, verse 1 · de",
+			"2. Er kommt, er kommt",
+			"   de",
+			"3. sinfonia",
+		]
+	);
+}
+
+#[test]
+fn test_cli_durations_roll_up_filter_and_total() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_collection(root, "bach", "suites", r#"{
+		"id": "bach-suites",
+		"title": {"en": "Suites"},
+		"attribution": [{"composer": "bach"}],
+		"scheme": "bwv",
+		"compositions": ["812", "813", "814"]
+	}"#);
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "suite",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "812"}]}],
+		"movements": [{"form": "allemande", "duration": 180}, {"form": "courante", "duration": "PT1M30S"}]
+	}"#);
+	write_composition(root, "ab000002", r#"{
+		"id": "ab000002",
+		"form": "suite",
+		"duration": "PT20M..PT25M",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "813"}]}]
+	}"#);
+	write_composition(root, "ab000003", r#"{
+		"id": "ab000003",
+		"form": "suite",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "814"}]}]
+	}"#);
+
+	let output = run_wv(root, &["get", "bach", "bwv", "812", "--movements"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		["1. allemande (3:00)", "2. courante (1:30)"]
+	);
+
+	let output = run_wv(root, &["get", "bach", "bwv", "--max-duration", "20:00", "-t"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		["ab000001", "ab000002"]
+	);
+
+	let output = run_wv(root, &["collection", "show", "bach-suites"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains(", BWV 812 (4:30)"), "{}", stdout);
+	assert!(
+		stdout.trim_end().ends_with("Total: at least 24:30–29:30 (1 of 3 without a duration)"),
+		"{}",
+		stdout
+	);
+}
+
+#[test]
+fn test_cli_incipit_render_and_interval_search() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "suite",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "1007"}]}],
+		"movements": [
+			{"title": "Prelude", "incipit": {"clef": "F-4", "key": "xF", "time": "4/4", "data": "6,,G,DBABDBD"}},
+			{"title": "Allemande"}
+		]
+	}"#);
+	write_composition(root, "ab000002", r#"{
+		"id": "ab000002",
+		"form": "invention",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "772"}]}],
+		"incipit": {"clef": "G-2", "time": "c", "data": "6-'CDEFDEC8G''C'B''C"}
+	}"#);
+
+	let output = run_wv(root, &["incipit", "ab000001"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		[
+			"1. Prelude",
+			"F-clef on line 4, F♯, 4/4",
+			"G2/16 D3/16 B3/16 A3/16 B3/16 D3/16 B3/16 D3/16",
+		]
+	);
+
+	let output = run_wv(root, &["incipit", "ab000002", "--svg"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert!(String::from_utf8_lossy(&output.stdout).starts_with("<svg"));
+
+	let output = run_wv(root, &["incipit", "ab000001", "--movement", "2"]);
+	assert!(!output.status.success());
+
+	// C D E F D E C transposed to G.
+	let output = run_wv(root, &["incipit", "--search", "'4GAB''C'AB"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("BWV 772"), "{}", stdout);
+	assert!(!stdout.contains("BWV 1007"), "{}", stdout);
+}
+
+#[test]
+fn test_cli_get_sources_lists_citations_per_claim() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	fs::create_dir_all(root.join("references")).unwrap();
+	fs::write(root.join("references/schmieder-1990.json"), r#"{
+		"id": "schmieder-1990",
+		"title": "Bach-Werke-Verzeichnis",
+		"author": "Wolfgang Schmieder",
+		"year": 1990
+	}"#).unwrap();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "cantata",
+		"attribution": [
+			{"composer": "telemann", "since": "1990", "sources": [{"reference": "ruhnke", "locator": "p. 12"}]},
+			{
+				"composer": "bach",
+				"status": "spurious",
+				"catalog": [{"scheme": "bwv", "number": "141", "sources": [{"reference": "schmieder-1990", "locator": "Anh. III"}]}]
+			}
+		]
+	}"#);
+	write_composition(root, "ab000002", r#"{
+		"id": "ab000002",
+		"form": "cantata",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "140"}]}]
+	}"#);
+
+	let output = run_wv(root, &["get", "bach", "bwv", "141", "--sources"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().skip(1).collect::<Vec<_>>(),
+		[
+			"  telemann, since 1990",
+			"    ruhnke (not in references/), p. 12",
+			"  bach, spurious",
+			"    BWV 141",
+			"      Wolfgang Schmieder, Bach-Werke-Verzeichnis (1990), Anh. III",
+		]
+	);
+
+	let output = run_wv(root, &["get", "bach", "bwv", "140", "--sources"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert!(String::from_utf8_lossy(&output.stdout).ends_with("  (no sources cited)\n"));
+}
+
+#[test]
+fn test_cli_occasion_lists_works_for_church_year_date() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"title": {"de": "Nun komm, der Heiden Heiland"},
+		"form": "cantata",
+		"occasion": "advent-1",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "61"}]}]
+	}"#);
+	write_composition(root, "ab000002", r#"{
+		"id": "ab000002",
+		"title": {"de": "Ein ungefärbt Gemüte"},
+		"form": "cantata",
+		"occasion": "trinity+4",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "24"}]}]
+	}"#);
+
+	let output = run_wv(root, &["occasion", "2026-11-29"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout),
+		"2026-11-29: 1st Sunday of Advent [advent-1]\nNun komm, der Heiden Heiland, BWV 61\n"
+	);
+
+	// A weekday without a feast belongs to the week of the preceding Sunday.
+	let output = run_wv(root, &["occasion", "2026-07-01", "--terse"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ab000002\n");
+
+	let output = run_wv(root, &["get", "bach", "--occasion", "trinity+4", "--terse"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ab000002\n");
+
+	let output = run_wv(root, &["get", "--occasion", "trinity+40"]);
+	assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_chorale_lists_settings_with_movement_paths() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	fs::create_dir_all(root.join("chorales")).unwrap();
+	fs::write(root.join("chorales/jesu-meine-freude.json"), r#"{
+		"id": "jesu-meine-freude",
+		"title": "Jesu, meine Freude",
+		"zahn": "8032"
+	}"#).unwrap();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"title": {"de": "Jesus schläft, was soll ich hoffen?"},
+		"form": "cantata",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "81"}]}],
+		"movements": [
+			{"title": "Jesus schläft, was soll ich hoffen?", "form": "aria"},
+			{"title": "Unter deinen Schirmen", "form": "chorale", "chorale": "jesu-meine-freude"}
+		]
+	}"#);
+	write_composition(root, "ab000002", r#"{
+		"id": "ab000002",
+		"title": {"de": "Jesu, meine Freude"},
+		"form": "motet",
+		"chorale": "jesu-meine-freude",
+		"attribution": [{"composer": "bach", "catalog": [{"scheme": "bwv", "number": "227"}]}]
+	}"#);
+
+	let output = run_wv(root, &["chorale", "Jesu, meine Freude"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		[
+			"Jesu, meine Freude (Zahn 8032)",
+			"Jesus schläft, was soll ich hoffen?: 2. Unter deinen Schirmen, BWV 81",
+			"Jesu, meine Freude, BWV 227",
+		]
+	);
+
+	let output = run_wv(root, &["chorale", "8032", "--terse"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ab000001\nab000002\n");
+
+	let output = run_wv(root, &["chorale", "Ein feste Burg"]);
+	assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_get_form_matches_subforms_and_synonyms() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	fs::create_dir_all(root.join("taxonomy")).unwrap();
+	fs::write(root.join("taxonomy/forms.json"), r#"{
+		"cantata": {"labels": {"en": "Cantata"}},
+		"sacred cantata": {"parent": "cantata", "synonyms": ["church cantata"]},
+		"chorale cantata": {"parent": "sacred cantata"},
+		"sonata": {}
+	}"#).unwrap();
+	for (id, number, form) in [
+		("ab000001", "1", "chorale cantata"),
+		("ab000002", "21", "church cantata"),
+		("ab000003", "1001", "sonata"),
+	] {
+		write_composition(root, id, &format!(r#"{{
+			"id": "{}",
+			"form": "{}",
+			"attribution": [{{"composer": "bach", "catalog": [{{"scheme": "bwv", "number": "{}"}}]}}]
+		}}"#, id, form, number));
+	}
+
+	let output = run_wv(root, &["get", "bach", "--form", "cantata", "--terse"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ab000001\nab000002\n");
+
+	let output = run_wv(root, &["get", "bach", "--form", "Church Cantata", "--terse"]);
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ab000001\nab000002\n");
+
+	let output = run_wv(root, &["get", "bach", "--form", "cantatta"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("did you mean 'cantata'?"));
+}
+
+#[test]
+fn test_cli_get_key_matches_enharmonics_modes_and_movements() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	for (id, number, key, movement_key) in [
+		("ab000001", "27", "c#", "Db"),
+		("ab000002", "31", "D", "d"),
+		("ab000003", "57", "f", "Db"),
+		("ab000004", "90", "e", "E"),
+	] {
+		write_composition(root, id, &format!(r#"{{
+			"id": "{}",
+			"form": "sonata",
+			"key": "{}",
+			"attribution": [{{"composer": "beethoven", "catalog": [{{"scheme": "op", "number": "{}"}}]}}],
+			"movements": [{{"form": "allegro", "key": "{}"}}]
+		}}"#, id, key, number, movement_key));
+	}
+
+	let get = |key: &str| {
+		let output = run_wv(root, &["get", "beethoven", "--key", key, "--terse"]);
+		assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		String::from_utf8_lossy(&output.stdout).into_owned()
+	};
+	assert_eq!(get("C#"), "ab000001\nab000003\n");
+	assert_eq!(get("d.*"), "ab000002\n");
+	assert_eq!(get("minor"), "ab000001\nab000002\nab000003\nab000004\n");
+	assert_eq!(get("e"), "ab000004\n");
+
+	let output = run_wv(root, &["get", "beethoven", "--key", "H"]);
+	assert!(!output.status.success());
+}
+
+#[test]
+fn test_cli_contributors_are_indexed_and_credited() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{
+		"id": "ab000001",
+		"form": "requiem",
+		"key": "d",
+		"attribution": [{
+			"composer": "mozart",
+			"contributors": [{"composer": "sussmayr", "role": "completer"}],
+			"catalog": [{"scheme": "k", "number": "626"}]
+		}]
+	}"#);
+
+	let output = run_wv(root, &["get", "sussmayr", "--terse"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout), "ab000001\n");
+
+	let output = run_wv(root, &["get", "mozart", "k", "626", "--sources"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert!(String::from_utf8_lossy(&output.stdout).contains("  mozart, completed by sussmayr\n"));
+}
+
+#[test]
+fn test_cli_lint_fixes_and_honors_config() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{
+	"id": "ab000001",
+	"form": "sonata",
+	"instrumentation": "two oboes, bc",
+	"attribution": [
+		{
+			"composer": "bach",
+			"catalog": [{"scheme": "bwv", "number": "Anh. 5"}]
+		}
+	],
+	"movements": [
+		{"title": "adagio"}
+	]
+}
+"#);
+
+	let output = run_wv(root, &["lint"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	for rule in ["title-case", "instrumentation-abbreviation", "catalog-number", "missing-key"] {
+		assert!(stdout.contains(&format!("warning[{}]", rule)), "{}", stdout);
+	}
+
+	fs::write(root.join(".wvlint.toml"), "[rules.missing-key]\nseverity = \"error\"\n").unwrap();
+	let output = run_wv(root, &["lint", "--fix"]);
+	assert!(!output.status.success());
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(),
+		[format!(
+			"{}: error[missing-key]: key: a sonata should state its key",
+			root.join("compositions/ab/000001.json").display()
+		)]
+	);
+	let fixed = fs::read_to_string(root.join("compositions/ab/000001.json")).unwrap();
+	assert!(fixed.contains("\"instrumentation\": \"2 oboes, basso continuo\",\n"));
+	assert!(fixed.contains("\"number\": \"anh. 5\""));
+	assert!(fixed.contains("{\"title\": \"Adagio\"}"));
+
+	fs::write(root.join(".wvlint.toml"), "[rules.missing-keys]\nseverity = \"off\"\n").unwrap();
+	let output = run_wv(root, &["lint"]);
+	assert!(!output.status.success());
+	assert!(String::from_utf8_lossy(&output.stderr).contains("unknown rule 'missing-keys'"));
+}
+
+#[test]
+fn test_cli_fmt_rewrites_and_checks() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{"form": "sonata", "id": "ab000001",
+  "title": {"en": "Sonata", "de": "Sonate"},
+  "attribution": [{"composer": "bach"}]}"#);
+	fs::create_dir_all(root.join("inventories/bach")).unwrap();
+	fs::write(root.join("catalogs/bwv.json"), r#"{"name": "BWV", "pattern": "^(\\d+)$", "sort_keys": [{"group": 1, "type": "int"}]}"#).unwrap();
+	fs::write(
+		root.join("inventories/bach/bwv.toml"),
+		"composer = \"bach\"\nscheme = \"bwv\"\ncomplete = false\n\nentries = [\n\t\"10\", # later\n\t# Cantatas\n\t\"2\", \"1\",\n]\n",
+	)
+	.unwrap();
+
+	let output = run_wv(root, &["fmt", "--check"]);
+	assert!(!output.status.success());
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.contains("compositions/ab/000001.json"), "{}", stdout);
+	assert!(stdout.contains("inventories/bach/bwv.toml"), "{}", stdout);
+
+	let output = run_wv(root, &["fmt"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		fs::read_to_string(root.join("compositions/ab/000001.json")).unwrap(),
+		"{\n\t\"id\": \"ab000001\",\n\t\"title\": {\n\t\t\"de\": \"Sonate\",\n\t\t\"en\": \"Sonata\"\n\t},\n\t\"attribution\": [\n\t\t{\n\t\t\t\"composer\": \"bach\"\n\t\t}\n\t],\n\t\"form\": \"sonata\"\n}\n"
+	);
+	assert_eq!(
+		fs::read_to_string(root.join("inventories/bach/bwv.toml")).unwrap(),
+		"composer = \"bach\"\nscheme = \"bwv\"\ncomplete = false\n\nentries = [\n\t# Cantatas\n\t\"1\", \"2\",\n\t\"10\", # later\n]\n"
+	);
+
+	let output = run_wv(root, &["fmt", "--check"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+}
+
+#[test]
+fn test_cli_validate_json_and_sarif() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	write_composition(root, "ab000001", r#"{"id": "ab000001", "title": {"en": "Sonata"}, "form": "sonata",
+		"attribution": [{"composer": "nobody"}]}"#);
+	copy_schemas(root);
+
+	let output = run_wv(root, &["validate", "--format", "json"]);
+	assert_eq!(output.status.code(), Some(1));
+	let findings: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+	let finding = findings
+		.as_array()
+		.unwrap()
+		.iter()
+		.find(|f| f["code"] == "unknown-composer")
+		.unwrap_or_else(|| panic!("{}", findings));
+	assert!(finding["file"].as_str().unwrap().ends_with("compositions/ab/000001.json"));
+	assert_eq!(finding["pointer"], "/attribution/0/composer");
+	assert_eq!(finding["severity"], "error");
+
+	let output = run_wv(root, &["validate", "--format", "sarif"]);
+	assert_eq!(output.status.code(), Some(1));
+	let log: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+	assert_eq!(log["version"], "2.1.0");
+	let run = &log["runs"][0];
+	assert_eq!(run["tool"]["driver"]["name"], "wv");
+	let result = run["results"]
+		.as_array()
+		.unwrap()
+		.iter()
+		.find(|r| r["ruleId"] == "unknown-composer")
+		.unwrap();
+	assert_eq!(result["level"], "error");
+	assert_eq!(result["locations"][0]["logicalLocations"][0]["fullyQualifiedName"], "/attribution/0/composer");
+}
+
+#[test]
+fn test_cli_validate_cache_follows_changes() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	copy_schemas(root);
+	let composer = |died: &str| {
+		fs::write(
+			root.join("composers/bach.json"),
+			format!(r#"{{"id": "bach", "name": {{"full": "Johann Sebastian Bach", "sort": "Bach, Johann Sebastian"}}, "born": "1685", "died": "{}"}}"#, died),
+		)
+		.unwrap();
+	};
+	composer("1750");
+	write_composition(root, "ab000001", r#"{"id": "ab000001", "title": {"en": "Mass"}, "form": "mass",
+		"attribution": [{"composer": "bach", "dates": {"composed": "1749"}}],
+		"relations": [{"type": "parody-of", "target": "cd000002"}]}"#);
+	let messages = |output: &Output| String::from_utf8_lossy(&output.stderr).to_string();
+
+	let output = run_wv(root, &["validate"]);
+	assert!(messages(&output).contains("target composition 'cd000002' not found"), "{}", messages(&output));
+	assert!(!messages(&output).contains("death"), "{}", messages(&output));
+	assert!(root.join(".indexes/validation-cache.json").is_file());
+
+	// The unchanged composition comes from the cache, but its relation is
+	// checked again against the new target.
+	write_composition(root, "cd000002", r#"{"id": "cd000002", "title": {"en": "Cantata"}, "form": "cantata",
+		"attribution": [{"composer": "bach"}]}"#);
+	let output = run_wv(root, &["validate"]);
+	assert!(output.status.success(), "{}", messages(&output));
+
+	// Changing the composer invalidates the works attributed to them.
+	composer("1740");
+	let output = run_wv(root, &["validate"]);
+	assert!(messages(&output).contains("1749 is after the composer's death (1740)"), "{}", messages(&output));
+}
+
+#[test]
+fn test_cli_validate_warnings_exit_code() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	copy_schemas(root);
+	fs::write(
+		root.join("composers/bach.json"),
+		r#"{"id": "bach", "name": {"full": "Johann Sebastian Bach", "sort": "Bach, Johann Sebastian"}, "born": "1685", "died": "1750"}"#,
+	)
+	.unwrap();
+	write_composition(root, "ab000001", r#"{"id": "ab000001", "title": {"en": "Sonatas"}, "form": "sonata",
+		"attribution": [{"composer": "bach", "dates": {"composed": 1720, "published": 1802}}]}"#);
+
+	let output = run_wv(root, &["validate"]);
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert_eq!(output.status.code(), Some(3), "{}", stderr);
+	assert!(stderr.contains("warning: "), "{}", stderr);
+	assert!(stderr.contains("1802 is after the composer's death (1750)"), "{}", stderr);
+}
+
+#[test]
+fn test_cli_dupes_lists_likely_duplicates() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	let record = |id: &str, number: &str| {
+		format!(
+			r#"{{"id": "{}", "title": {{"en": "Toccata and Fugue in D minor"}}, "form": "toccata and fugue", "key": "d",
+			"attribution": [{{"composer": "bach", "catalog": [{{"scheme": "bwv", "number": "{}"}}]}}]}}"#,
+			id, number
+		)
+	};
+	write_composition(root, "ab000001", &record("ab000001", "565"));
+	write_composition(root, "cd000002", &record("cd000002", "565"));
+	write_composition(root, "ef000003", &record("ef000003", "538"));
+
+	let output = run_wv(root, &["dupes"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let lines: Vec<&str> = stdout.lines().collect();
+	assert_eq!(lines.len(), 1, "{}", stdout);
+	assert!(lines[0].starts_with("100  ab000001  cd000002  same composer"), "{}", stdout);
+	assert!(lines[0].contains("shared catalog number bwv 565"), "{}", stdout);
+}
+
+#[test]
+fn test_cli_catalog_list_and_show_merged_definition() {
+	let tmp = setup_inventory_cli_repo();
+	let root = tmp.path();
+
+	let output = run_wv(root, &["catalog", "list"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(
+		String::from_utf8_lossy(&output.stdout).trim(),
+		"op\tglobal\tOpus number\t(1 record, 4 in inventory)\nop\tbeethoven\tOpus\t(1 record, 4 in inventory)"
+	);
+
+	let output = run_wv(root, &["catalog", "show", "op", "--composer", "beethoven"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	assert!(stdout.starts_with("op: Opus\n"), "{}", stdout);
+	assert!(stdout.contains("Parts: {main} no. {part}\n"), "{}", stdout);
+	assert!(stdout.contains("Group by: 1\n"), "{}", stdout);
+	assert!(stdout.contains("  group 1 (opus number): 1..138\n"), "{}", stdout);
+	assert!(stdout.contains("  group 2 (sub-number): at least 1\n"), "{}", stdout);
+	assert!(stdout.ends_with("Records: 1\nInventory: 4 entries, complete\n"), "{}", stdout);
+
+	let output = run_wv(root, &["catalog", "show", "bwv"]);
+	assert_eq!(output.status.code(), Some(1));
+	assert_eq!(String::from_utf8_lossy(&output.stderr).trim(), "Catalog not found: bwv");
+}
+
+#[test]
+fn test_cli_catalog_show_finds_composer_scheme() {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	let composer = |id: &str, name: &str, catalog: &str| {
+		fs::write(
+			root.join(format!("composers/{}.json", id)),
+			format!(
+				r#"{{"id": "{}", "name": {{"full": "{}", "sort": "{}"}},
+				"catalogs": {{"k": {{"name": "{}", "canonical_format": "K. {{number}}"}}}}}}"#,
+				id, name, name, catalog
+			),
+		)
+		.unwrap();
+	};
+	composer("mozart", "Wolfgang Amadeus Mozart", "Köchel-Verzeichnis");
+
+	let output = run_wv(root, &["catalog", "show", "k"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert!(String::from_utf8_lossy(&output.stdout).starts_with("k: Köchel-Verzeichnis\n"));
+
+	composer("scarlatti", "Domenico Scarlatti", "Kirkpatrick");
+	let output = run_wv(root, &["catalog", "show", "k"]);
+	assert_eq!(output.status.code(), Some(1));
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(stderr.contains("Catalog k is defined by several composers: mozart, scarlatti"), "{}", stderr);
+	assert!(stderr.contains("--composer"), "{}", stderr);
+
+	let output = run_wv(root, &["catalog", "show", "k", "--composer", "scarlatti"]);
+	assert!(String::from_utf8_lossy(&output.stdout).starts_with("k: Kirkpatrick\n"));
+}
+
+#[test]
+fn test_cli_typed_numbers_go_through_input_rules() {
+	let tmp = setup_inventory_cli_repo();
+	let root = tmp.path();
+	fs::create_dir_all(root.join("collections/beethoven")).unwrap();
+
+	fs::write(
+		root.join("catalogs/op.json"),
+		r#"{
+			"id": "op",
+			"name": "Opus number",
+			"canonical_format": "op. {number}",
+			"part_format": "{main} no. {part}",
+			"pattern": "^(\\d+)(?:/(\\d+))?$",
+			"sort_keys": [
+				{"group": 1, "type": "int"},
+				{"group": 2, "type": "int"}
+			],
+			"group_by": [1],
+			"input_rules": [
+				{"strip_prefix": ["op."]},
+				{"replace": {"pattern": "\\s*,?\\s*(?:no|nr)\\.?\\s*(\\d)", "with": "/$1"}}
+			]
+		}"#,
+	)
+	.unwrap();
+	write_collection(root, "beethoven", "early-sonatas", r#"{
+		"id": "beethoven-early-sonatas",
+		"title": {"en": "Piano Sonatas, Op. 2"},
+		"attribution": [{"composer": "beethoven"}],
+		"scheme": "op",
+		"compositions": ["2/1", "2/2", "2/3"]
+	}"#);
+
+	for typed in [&["op.2", "no.3"][..], &["Op.", "2,", "Nr.", "3"], &["2/3"]] {
+		let mut args = vec!["get", "beethoven", "op", "--terse"];
+		args.extend_from_slice(typed);
+		let output = run_wv(root, &args);
+		assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab123456", "{:?}", typed);
+	}
+
+	let output = run_wv(root, &["collection", "find", "op:Op. 2 No. 3"]);
+	assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+	assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "beethoven-early-sonatas");
+}
+
+fn setup_scheme_token_repo() -> TempDir {
+	let tmp = setup_test_repo();
+	let root = tmp.path();
+	fs::create_dir_all(root.join("collections/mozart")).unwrap();
+
+	fs::write(
+		root.join("catalogs/bwv.json"),
+		r#"{
+			"id": "bwv",
+			"name": "Bach-Werke-Verzeichnis",
+			"canonical_format": "BWV {number}",
+			"input_rules": [{"strip_prefix": ["bwv"]}]
+		}"#,
+	)
+	.unwrap();
+	fs::write(
+		root.join("composers/bach.json"),
+		r#"{"id": "bach", "name": {"full": "Johann Sebastian Bach", "sort": "Bach, Johann Sebastian"}}"#,
+	)
+	.unwrap();
+	fs::write(
+		root.join("composers/mozart.json"),
+		r#"{
+			"id": "mozart",
+			"name": {"full": "Wolfgang Amadeus Mozart", "sort": "Mozart, Wolfgang Amadeus"},
+			"catalogs": {
+				"k": {
+					"name": "Köchel-Verzeichnis",
+					"canonical_format": "K. {number}",
+					"input_rules": [{"strip_prefix": ["kv", "k"]}],
+					"aliases": ["kv"]
+				}
+			}
+		}"#,
+	)
+	.unwrap();
+
+	write_composition(root, "ab123456", r#"{
+		"id": "ab123456",
+		"form": "suite",
+		"key": "G",
+		"attribution": [{
+			"composer": "bach",
+			"catalog": [{"scheme": "bwv", "number": "1007"}]
+		}]
+	}"#);
+	write_composition(root, "cd123456", r#"{
+		"id": "cd123456",
+		"form": "sonata",
+		"key": "A",
+		"attribution": [{
+			"composer": "mozart",
+			"catalog": [{"scheme": "k", "number": "331"}]
+		}]
+	}"#);
+	write_collection(root, "bach", "cello-suites", r#"{
+		"id": "bach-cello-suites",
+		"title": {"en": "Cello Suites"},
+		"attribution": [{"composer": "bach"}],
+		"scheme": "bwv",
+		"compositions": ["1007", "1008"]
+	}"#);
+	write_collection(root, "mozart", "paris-sonatas", r#"{
+		"id": "mozart-paris-sonatas",
+		"title": {"en": "Paris Sonatas"},
+		"attribution": [{"composer": "mozart"}],
+		"scheme": "k",
+		"compositions": ["330", "331", "332"]
+	}"#);
+
+	tmp
+}
+
+#[test]
+fn test_cli_collection_find_resolves_scheme_tokens() {
+	let tmp = setup_scheme_token_repo();
+	let root = tmp.path();
+
+	for (query, expected) in [
+		("bwv:1007", "bach-cello-suites"),
+		("BWV:1007", "bach-cello-suites"),
+		("kv:331", "mozart-paris-sonatas"),
+		("K:331", "mozart-paris-sonatas"),
+	] {
+		let output = run_wv(root, &["collection", "find", query]);
+		assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), expected, "{}", query);
+	}
+}
+
+#[test]
+fn test_cli_set_resolves_scheme_tokens() {
+	let tmp = setup_scheme_token_repo();
+	let root = tmp.path();
+
+	let db_path = root.join("mb.db");
+	let conn = rusqlite::Connection::open(&db_path).unwrap();
+	conn.execute_batch(
+		"CREATE TABLE catalog_entries (work_id TEXT, work_title TEXT, catalog_number TEXT, composer_name TEXT);
+		 INSERT INTO catalog_entries VALUES ('mb-1007', 'Cello Suite no. 1', 'BWV 1007', 'Johann Sebastian Bach');
+		 INSERT INTO catalog_entries VALUES ('mb-331', 'Piano Sonata no. 11', '331', 'Wolfgang Amadeus Mozart');",
+	)
+	.unwrap();
+	drop(conn);
+	fs::create_dir_all(root.join("test-config/wv")).unwrap();
+	fs::write(
+		root.join("test-config/wv/config.toml"),
+		format!("[xref]\nmb_database = {:?}\n", db_path.to_str().unwrap()),
+	)
+	.unwrap();
+
+	for (args, path, mb_id) in [
+		(&["set", "bach", "BWV1007", "--xref", "mb"][..], "compositions/ab/123456.json", "mb-1007"),
+		(&["set", "mozart", "kv", "331", "--xref", "mb"], "compositions/cd/123456.json", "mb-331"),
+	] {
+		let output = run_wv(root, args);
+		assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		assert!(stdout.contains(&format!("{}\t[updated]", mb_id)), "{:?}: {}", args, stdout);
+		let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(root.join(path)).unwrap()).unwrap();
+		assert_eq!(json["xref"]["mb"], mb_id);
+	}
+}
+
+#[test]
+fn test_cli_get_reads_scheme_fused_to_number() {
+	let tmp = setup_inventory_cli_repo();
+	let root = tmp.path();
+
+	fs::write(
+		root.join("catalogs/op.json"),
+		r#"{
+			"id": "op",
+			"name": "Opus number",
+			"canonical_format": "op. {number}",
+			"part_format": "{main} no. {part}",
+			"pattern": "^(\\d+)(?:/(\\d+))?$",
+			"sort_keys": [
+				{"group": 1, "type": "int"},
+				{"group": 2, "type": "int"}
+			],
+			"group_by": [1],
+			"input_rules": [
+				{"strip_prefix": ["opus", "op.", "op"]},
+				{"replace": {"pattern": "\\s*,?\\s*(?:no|nr)\\.?\\s*(\\d)", "with": "/$1"}}
+			],
+			"aliases": ["opus"]
+		}"#,
+	)
+	.unwrap();
+
+	for typed in [&["op.2", "no.3"][..], &["Op. 2, Nr. 3"], &["OP2/3"], &["opus", "2/3"]] {
+		let mut args = vec!["get", "beethoven", "--terse"];
+		args.extend_from_slice(typed);
+		let output = run_wv(root, &args);
+		assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+		assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ab123456", "{:?}", typed);
+	}
+}